@@ -3,6 +3,8 @@
 extern crate random_choice;
 extern crate rand;
 extern crate test;
+#[cfg(feature = "rand-compat")]
+extern crate rand_compat;
 
 
 #[cfg(test)]
@@ -10,6 +12,9 @@ mod benches {
     use test::Bencher;
     use random_choice::random_choice;
     use random_choice::RandomChoice;
+    use random_choice::prepared::alias::AliasTable;
+    use random_choice::prepared::approximate::ApproximateSampler;
+    use random_choice::prepared::{Placement, PreparedChoice};
     use rand::SeedableRng;
 
     #[bench]
@@ -92,4 +97,107 @@ mod benches {
             random_choice.random_choice_f64(&samples, &weights, number_choices);
         });
     }
-}
\ No newline at end of file
+
+    #[bench]
+    fn bench_alias_table_1000_it_f64(b: &mut Bencher) {
+        let capacity: usize = 1000;
+        let mut weights: Vec<f64> = Vec::with_capacity(capacity);
+
+        for i in 0..capacity {
+            weights.push((i + 1usize) as f64);
+        }
+
+        let table = AliasTable::new(&weights);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        b.iter(|| {
+            table.sample_n(&mut rng, capacity);
+        });
+    }
+
+    #[bench]
+    fn bench_prepared_choice_skewed_1000000_f64(b: &mut Bencher) {
+        let capacity: usize = 1000000;
+        let mut weights: Vec<f64> = vec![1e-9; capacity];
+        weights[capacity / 2] = 1e9;
+
+        let prepared = PreparedChoice::new(&weights);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        b.iter(|| {
+            prepared.sample_n(&mut rng, 1000);
+        });
+    }
+
+    #[bench]
+    fn bench_prepared_choice_10_of_1000000_binary_search_f64(b: &mut Bencher) {
+        let capacity: usize = 1000000;
+        let weights: Vec<f64> = (0..capacity).map(|i| (i + 1usize) as f64).collect();
+
+        let prepared = PreparedChoice::new(&weights);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        b.iter(|| {
+            prepared.sample_n_with(&mut rng, 10, Placement::BinarySearch);
+        });
+    }
+
+    #[bench]
+    fn bench_alias_table_construction_100000_f64(b: &mut Bencher) {
+        let weights: Vec<f64> = (0..100000).map(|i| 1.0 / (i + 1usize) as f64).collect();
+
+        b.iter(|| AliasTable::new(&weights));
+    }
+
+    #[bench]
+    fn bench_approximate_sampler_construction_100000_f64(b: &mut Bencher) {
+        let weights: Vec<f64> = (0..100000).map(|i| 1.0 / (i + 1usize) as f64).collect();
+
+        b.iter(|| ApproximateSampler::new(&weights, 8));
+    }
+
+    /// Compares the drop-in replacement with rand's WeightedIndex on the same generator.
+    #[cfg(feature = "rand-compat")]
+    mod weighted_index {
+        use test::Bencher;
+        use random_choice::integrations::compat::WeightedIndex;
+        use rand_compat::distr::weighted::WeightedIndex as RandWeightedIndex;
+        use rand_compat::distr::Distribution;
+        use rand_compat::rngs::SmallRng;
+        use rand_compat::SeedableRng;
+
+        fn weights() -> Vec<f64> {
+            (0..1000).map(|i| (i + 1usize) as f64).collect()
+        }
+
+        #[bench]
+        fn bench_compat_weighted_index_1000_it_f64(b: &mut Bencher) {
+            let dist = WeightedIndex::new(&weights()).unwrap();
+            let mut rng = SmallRng::seed_from_u64(5000);
+
+            b.iter(|| (0..1000).map(|_| dist.sample(&mut rng)).sum::<usize>());
+        }
+
+        #[bench]
+        fn bench_rand_weighted_index_1000_it_f64(b: &mut Bencher) {
+            let dist = RandWeightedIndex::new(&weights()).unwrap();
+            let mut rng = SmallRng::seed_from_u64(5000);
+
+            b.iter(|| (0..1000).map(|_| dist.sample(&mut rng)).sum::<usize>());
+        }
+
+        #[bench]
+        fn bench_compat_weighted_index_construction_1000_f64(b: &mut Bencher) {
+            let weights = weights();
+
+            b.iter(|| WeightedIndex::new(&weights).unwrap());
+        }
+
+        #[bench]
+        fn bench_rand_weighted_index_construction_1000_f64(b: &mut Bencher) {
+            let weights = weights();
+
+            b.iter(|| RandWeightedIndex::new(&weights).unwrap());
+        }
+    }
+}
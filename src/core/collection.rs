@@ -0,0 +1,366 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Weighted Collections
+//! Weights don't have to be stored in one contiguous slice. The `WeightedCollection` trait visits
+//! them chunk by chunk, so shards of a rope or a segmented arena, e.g. `&[&[f64]]`, can be sampled
+//! without concatenating them into one giant allocation first.
+//!
+//! `RandomChoice::random_choice_indices_from` walks the chunks once per draw.
+//! `ChunkedPrefixSums` keeps one block of prefix sums per chunk for repeated draws.
+//! Collections, whose elements are addressed by keys, implement `KeyedCollection`, so that
+//! `RandomChoice::random_choice_keys_from` returns keys instead of indices. See the `arena` module.
+//! Both choose the same indices as `random_choice_indices_f64` on the concatenated weights.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//!
+//! # fn main() {
+//! let first: Vec<f64> = vec![5.6, 7.8];
+//! let second: Vec<f64> = vec![9.7, 1.1, 2.0];
+//! let shards: Vec<&[f64]> = vec![&first, &second];
+//!
+//! let choices = random_choice().random_choice_indices_from(&shards[..], 100);
+//!
+//! assert!(choices.iter().all(|&i| i < 5));
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::core::rescale::{self, Detector, Rescaling};
+use crate::RandomChoice;
+
+/// Weights of the indices 0..len(), which are stored in one or more contiguous chunks.
+pub trait WeightedCollection {
+    /// Returns the number of weights.
+    fn len(&self) -> usize;
+
+    /// Returns true, if there are no weights.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the weight of an index.
+    fn weight(&self, i: usize) -> f64;
+
+    /// Calls f with the chunks of weights in the order of their indices.
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[f64]));
+
+    /// Returns the sum of all weights.
+    fn total_weight(&self) -> f64 {
+        let mut sum: f64 = 0.0;
+        self.for_each_chunk(&mut |chunk| sum = chunk.iter().fold(sum, |acc, &w| acc + w));
+        sum
+    }
+}
+
+impl WeightedCollection for [f64] {
+    fn len(&self) -> usize {
+        <[f64]>::len(self)
+    }
+
+    fn weight(&self, i: usize) -> f64 {
+        self[i]
+    }
+
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[f64])) {
+        f(self)
+    }
+}
+
+impl WeightedCollection for [&[f64]] {
+    fn len(&self) -> usize {
+        self.iter().map(|chunk| chunk.len()).sum()
+    }
+
+    /// Finds the chunk of the index in O(number of chunks).
+    fn weight(&self, i: usize) -> f64 {
+        chunked_weight(self.iter().map(|chunk| &chunk[..]), i)
+    }
+
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[f64])) {
+        for chunk in self {
+            f(chunk)
+        }
+    }
+}
+
+impl WeightedCollection for [Vec<f64>] {
+    fn len(&self) -> usize {
+        self.iter().map(|chunk| chunk.len()).sum()
+    }
+
+    /// Finds the chunk of the index in O(number of chunks).
+    fn weight(&self, i: usize) -> f64 {
+        chunked_weight(self.iter().map(|chunk| &chunk[..]), i)
+    }
+
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[f64])) {
+        for chunk in self {
+            f(chunk)
+        }
+    }
+}
+
+impl<T> WeightedCollection for Vec<T>
+    where [T]: WeightedCollection
+{
+    fn len(&self) -> usize {
+        WeightedCollection::len(&self[..])
+    }
+
+    fn weight(&self, i: usize) -> f64 {
+        self[..].weight(i)
+    }
+
+    fn for_each_chunk(&self, f: &mut dyn FnMut(&[f64])) {
+        self[..].for_each_chunk(f)
+    }
+}
+
+/// A value, which carries its own weight, e.g. a component of an entity in an arena.
+pub trait Weighted {
+    /// Returns the weight of the value.
+    fn weight(&self) -> f64;
+}
+
+impl Weighted for f64 {
+    fn weight(&self) -> f64 {
+        *self
+    }
+}
+
+impl Weighted for f32 {
+    fn weight(&self) -> f64 {
+        *self as f64
+    }
+}
+
+/// A weighted collection, whose elements are addressed by keys instead of indices,
+/// e.g. a generational arena. Index i is the i-th element in the iteration order of the keys.
+pub trait KeyedCollection: WeightedCollection {
+    /// The key of an element.
+    type Key;
+
+    /// Returns the keys of the indices.
+    /// @invariant the indices are in ascending order, duplicates are allowed.
+    fn keys_of(&self, indices: &[usize]) -> Vec<Self::Key>;
+}
+
+fn chunked_weight<'a, I>(chunks: I, mut i: usize) -> f64
+    where I: Iterator<Item = &'a [f64]>
+{
+    for chunk in chunks {
+        if i < chunk.len() {
+            return chunk[i];
+        }
+        i -= chunk.len();
+    }
+
+    panic!("index out of bounds of the weighted collection")
+}
+
+/// Detects in one pass over the chunks, whether the weights need rescaling, see `rescale`.
+fn rescaling_of<C>(weights: &C) -> Option<Rescaling>
+    where C: WeightedCollection + ?Sized
+{
+    let mut detector = Detector::new();
+    weights.for_each_chunk(&mut |chunk| chunk.iter().for_each(|&weight| detector.add(weight)));
+    detector.rescaling()
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n indices by their weights, which may be stored in several chunks.
+    /// It chooses the same indices as `random_choice_indices_f64` on the concatenated weights,
+    /// which are rescaled the same way.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param n Number of randomly chosen indices by weight.
+    /// @return randomly selected indices in ascending order
+    pub fn random_choice_indices_from<C>(&mut self, weights: &C, n: usize) -> Vec<usize>
+        where C: WeightedCollection + ?Sized
+    {
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = self.rng.next_f64();
+
+        if weights.is_empty() || n == 0 {
+            return Vec::new();
+        }
+
+        let rescaling = if self.rescale { rescaling_of(weights) } else { None };
+        let sum = match rescaling {
+            Some(rescaling) => {
+                let mut sum: f64 = 0.0;
+                weights.for_each_chunk(&mut |chunk| {
+                    sum = chunk.iter().fold(sum, |acc, &weight| acc + rescaling.apply(weight))
+                });
+                sum
+            }
+            None => weights.total_weight(),
+        };
+        let spoke_gap: f64 = sum / n as f64;
+
+        let mut choices: Vec<usize> = Vec::with_capacity(n);
+        let mut current_spoke: f64 = spin * spoke_gap;
+        let mut accumulated_weights: f64 = 0.0;
+        let mut offset: usize = 0;
+
+        // the walk of `sus_indices` over the concatenation of the chunks
+        weights.for_each_chunk(&mut |chunk| {
+            for (i, &weight) in chunk.iter().enumerate() {
+                accumulated_weights += rescale::rescaled(rescaling, weight);
+
+                while current_spoke < sum && choices.len() < n &&
+                      accumulated_weights >= current_spoke {
+                    choices.push(offset + i);
+                    current_spoke += spoke_gap;
+                }
+            }
+
+            offset += chunk.len();
+        });
+
+        // add this condition, because float leads to inaccurate
+        // calculations which can miss some samples
+        let last = choices.last().cloned().unwrap_or(0);
+
+        while choices.len() < n {
+            choices.push(last);
+        }
+
+        choices
+    }
+
+    /// Chooses n keys of a collection by their weights.
+    /// It chooses the keys of the indices, which `random_choice_indices_from` chooses.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights the keyed collection, e.g. an arena of weighted values.
+    /// @param n Number of randomly chosen keys by weight.
+    /// @return randomly selected keys in the iteration order of the collection
+    pub fn random_choice_keys_from<C>(&mut self, weights: &C, n: usize) -> Vec<C::Key>
+        where C: KeyedCollection + ?Sized
+    {
+        let indices = self.random_choice_indices_from(weights, n);
+        weights.keys_of(&indices)
+    }
+}
+
+/// The prefix sums of chunked weights, one block per chunk, for repeated draws.
+/// Like `PreparedChoice`, but without one contiguous allocation for all weights.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChunkedPrefixSums {
+    /// The prefix sums over all weights, split like the non-empty chunks.
+    blocks: Vec<Vec<f64>>,
+    /// The index of the first weight of every block.
+    starts: Vec<usize>,
+    len: usize,
+}
+
+impl ChunkedPrefixSums {
+    /// Computes the prefix sums in O(n). Tiny and subnormal weights are rescaled like by
+    /// `PreparedChoice::new`.
+    /// @invariant sum of weights must not overflow and weights must not be negative.
+    pub fn new<C>(weights: &C) -> Self
+        where C: WeightedCollection + ?Sized
+    {
+        let rescaling = rescaling_of(weights);
+        let mut blocks: Vec<Vec<f64>> = Vec::new();
+        let mut starts: Vec<usize> = Vec::new();
+        let mut sum: f64 = 0.0;
+        let mut len: usize = 0;
+
+        weights.for_each_chunk(&mut |chunk| {
+            if !chunk.is_empty() {
+                starts.push(len);
+                blocks.push(chunk.iter()
+                    .map(|&weight| {
+                        sum += rescale::rescaled(rescaling, weight);
+                        sum
+                    })
+                    .collect());
+                len += chunk.len();
+            }
+        });
+
+        ChunkedPrefixSums {
+            blocks,
+            starts,
+            len,
+        }
+    }
+
+    /// Returns the number of weights.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true, if there are no weights.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of non-empty chunks.
+    pub fn chunks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the sum of all weights.
+    pub fn total_weight(&self) -> f64 {
+        self.blocks.last().and_then(|block| block.last()).cloned().unwrap_or(0.0)
+    }
+
+    /// Chooses n indices by their weights with stochastic universal sampling.
+    /// Every spoke is found by binary search, first over the blocks, then within its block.
+    /// @return n indices in ascending order or nothing, if there are no weights.
+    pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        if self.is_empty() || n == 0 {
+            return Vec::new();
+        }
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = rng.next_f64();
+        let sum = self.total_weight();
+        let spoke_gap: f64 = sum / n as f64;
+
+        let mut block: usize = 0;
+        let mut i: usize = 0;
+        let mut choices: Vec<usize> = Vec::with_capacity(n);
+        let mut current_spoke: f64 = spin * spoke_gap;
+
+        while current_spoke < sum && choices.len() < n {
+            let spoke = current_spoke;
+
+            if self.end_of(block) < spoke {
+                let last_block = self.blocks.len() - 1;
+                block += 1 + self.blocks[block + 1..last_block]
+                    .partition_point(|prefix_sums| prefix_sums[prefix_sums.len() - 1] < spoke);
+                i = 0;
+            }
+
+            let prefix_sums = &self.blocks[block];
+            i += prefix_sums[i..prefix_sums.len() - 1].partition_point(|&s| s < spoke);
+
+            choices.push(self.starts[block] + i);
+            current_spoke += spoke_gap;
+        }
+
+        // add this condition, because float leads to inaccurate
+        // calculations which can miss some samples
+        let last = choices.last().cloned().unwrap_or(0);
+
+        while choices.len() < n {
+            choices.push(last);
+        }
+
+        choices
+    }
+
+    fn end_of(&self, block: usize) -> f64 {
+        let prefix_sums = &self.blocks[block];
+        prefix_sums[prefix_sums.len() - 1]
+    }
+}
@@ -0,0 +1,172 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Counter-Based Generators
+//! A counter-based generator computes every block of random bits as a keyed bijection of its
+//! position instead of advancing a state. `CounterRng` addresses the draws by a seed, a stream
+//! and an index: draw i of stream s is the pure function `CounterRng::draw(seed, s, i)`.
+//! Distributed workers therefore compute disjoint draws independently and reproducibly,
+//! e.g. worker w uses stream w or starts at index w * k, without sharing any state.
+//!
+//! Two bijections are available: `Philox4x32` and `Threefry2x64`
+//! (Salmon, Moraes, Dror and Shaw, "Parallel random numbers: as easy as 1, 2, 3", 2011).
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::RandomChoice;
+//! use random_choice::core::counter::{CounterRng, Threefry2x64};
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! // every worker draws from its own stream
+//! let choices: Vec<Vec<usize>> = (0..4)
+//!     .map(|worker| {
+//!         let rng = CounterRng::<Threefry2x64>::new(5000, worker);
+//!         RandomChoice::new(rng).random_choice_indices_f64(&weights, 10)
+//!     })
+//!     .collect();
+//!
+//! // worker 2 can be replayed without the others
+//! let rng = CounterRng::<Threefry2x64>::new(5000, 2);
+//! assert_eq!(RandomChoice::new(rng).random_choice_indices_f64(&weights, 10), choices[2]);
+//! # }
+//! ```
+
+use std::marker::PhantomData;
+
+use rand::Rng;
+
+use crate::core::entropy::Philox4x32;
+
+/// A keyed bijection of 128 bit counters.
+pub trait CounterBased {
+    /// Computes the block of a counter.
+    /// @param seed the key of the bijection.
+    /// @param counter the 128 bit counter as two words, the least significant one first.
+    fn block(seed: u64, counter: [u64; 2]) -> [u64; 2];
+}
+
+impl CounterBased for Philox4x32 {
+    fn block(seed: u64, counter: [u64; 2]) -> [u64; 2] {
+        Philox4x32::block(seed, counter)
+    }
+}
+
+/// The counter-based bijection Threefry2x64-20, which is derived from the Threefish block cipher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Threefry2x64;
+
+impl Threefry2x64 {
+    const ROUNDS: usize = 20;
+    const ROTATIONS: [u32; 8] = [16, 42, 12, 31, 16, 32, 24, 21];
+    const PARITY: u64 = 0x1BD1_1BDA_A9FC_1A22;
+
+    /// Computes the block of a counter with a 128 bit key.
+    pub fn keyed_block(key: [u64; 2], counter: [u64; 2]) -> [u64; 2] {
+        let schedule = [key[0], key[1], Threefry2x64::PARITY ^ key[0] ^ key[1]];
+        let mut x = [counter[0].wrapping_add(schedule[0]), counter[1].wrapping_add(schedule[1])];
+
+        for round in 0..Threefry2x64::ROUNDS {
+            x[0] = x[0].wrapping_add(x[1]);
+            x[1] = x[1].rotate_left(Threefry2x64::ROTATIONS[round % 8]) ^ x[0];
+
+            // the key is injected after every 4 rounds
+            if round % 4 == 3 {
+                let injection = round / 4 + 1;
+
+                x[0] = x[0].wrapping_add(schedule[injection % 3]);
+                x[1] = x[1]
+                    .wrapping_add(schedule[(injection + 1) % 3])
+                    .wrapping_add(injection as u64);
+            }
+        }
+
+        x
+    }
+}
+
+impl CounterBased for Threefry2x64 {
+    fn block(seed: u64, counter: [u64; 2]) -> [u64; 2] {
+        Threefry2x64::keyed_block([seed, 0], counter)
+    }
+}
+
+/// A generator, whose draws are addressed by a seed, a stream and an index.
+/// As every `rand::Rng`, it is an `EntropySource`.
+/// Block j of stream s is the block of the counter (j, s), which holds the draws 2j and 2j + 1.
+#[derive(Debug, Clone)]
+pub struct CounterRng<C: CounterBased = Philox4x32> {
+    seed: u64,
+    stream: u64,
+    position: u64,
+    /// The index and the content of the last computed block.
+    cached: Option<(u64, [u64; 2])>,
+    bijection: PhantomData<C>,
+}
+
+impl<C: CounterBased> CounterRng<C> {
+    /// Creates a generator at the first draw of a stream.
+    pub fn new(seed: u64, stream: u64) -> Self {
+        CounterRng::at(seed, stream, 0)
+    }
+
+    /// Creates a generator, whose next draw is the draw of the position.
+    pub fn at(seed: u64, stream: u64, position: u64) -> Self {
+        CounterRng {
+            seed,
+            stream,
+            position,
+            cached: None,
+            bijection: PhantomData,
+        }
+    }
+
+    /// Returns draw i of a stream without a generator.
+    pub fn draw(seed: u64, stream: u64, i: u64) -> u64 {
+        C::block(seed, [i / 2, stream])[(i % 2) as usize]
+    }
+
+    /// Returns the seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the stream.
+    pub fn stream(&self) -> u64 {
+        self.stream
+    }
+
+    /// Returns the index of the next draw.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Jumps to a draw in O(1).
+    pub fn seek(&mut self, position: u64) {
+        self.position = position;
+    }
+}
+
+impl<C: CounterBased> Rng for CounterRng<C> {
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let index = self.position / 2;
+
+        let block = match self.cached {
+            Some((cached, block)) if cached == index => block,
+            _ => {
+                let block = C::block(self.seed, [index, self.stream]);
+                self.cached = Some((index, block));
+                block
+            }
+        };
+
+        let value = block[(self.position % 2) as usize];
+        self.position = self.position.wrapping_add(1);
+        value
+    }
+}
@@ -0,0 +1,132 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Compile-Time Distributions
+//! Embedded and game code often samples from small distributions, which are known at compile
+//! time, e.g. loot tables. `CumulativeWeights::new_const` computes the prefix sums in a
+//! `const fn`, so that the table is baked into the binary as a `static` or a `const` and no
+//! allocation or initialization happens at runtime. Sampling searches the prefix sums like a
+//! `PreparedChoice`.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::core::cumulative::CumulativeWeights;
+//!
+//! static TABLE: CumulativeWeights<4> = CumulativeWeights::new_const([0.5, 0.25, 0.15, 0.1]);
+//!
+//! # fn main() {
+//! let mut rng = rand::thread_rng();
+//! let drop = TABLE.sample(&mut rng).unwrap();
+//!
+//! assert!(drop < 4);
+//! assert_eq!(TABLE.prefix_sums(), &[0.5, 0.75, 0.9, 1.0]);
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::prepared::PreparedChoice;
+
+/// The prefix sums of N weights, which are computed at compile time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CumulativeWeights<const N: usize> {
+    prefix_sums: [f64; N],
+}
+
+impl<const N: usize> CumulativeWeights<N> {
+    /// Computes the prefix sums, also in constant contexts.
+    /// A negative, infinite or NaN weight or an overflowing sum fails the compilation of a
+    /// constant.
+    ///
+    /// @param weights the weights of the indices 0..N. They don't have to sum up to 1.0.
+    pub const fn new_const(weights: [f64; N]) -> Self {
+        let mut prefix_sums = [0.0; N];
+        let mut sum = 0.0;
+        let mut i = 0;
+
+        while i < N {
+            assert!(weights[i] >= 0.0, "weights must not be negative or NaN");
+
+            sum += weights[i];
+            assert!(sum <= f64::MAX, "weights must be finite and their sum must not overflow");
+            prefix_sums[i] = sum;
+            i += 1;
+        }
+
+        CumulativeWeights { prefix_sums }
+    }
+
+    /// Returns the number of indices.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns true, if there are no indices.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns the sum of all weights.
+    pub const fn total_weight(&self) -> f64 {
+        if N == 0 { 0.0 } else { self.prefix_sums[N - 1] }
+    }
+
+    /// Returns the prefix sums.
+    pub const fn prefix_sums(&self) -> &[f64; N] {
+        &self.prefix_sums
+    }
+
+    /// Returns the weight of an index.
+    pub const fn weight(&self, i: usize) -> f64 {
+        if i == 0 {
+            self.prefix_sums[0]
+        } else {
+            self.prefix_sums[i] - self.prefix_sums[i - 1]
+        }
+    }
+
+    /// Copies the prefix sums into a `PreparedChoice`, e.g. to update the weights at runtime.
+    pub fn to_prepared(&self) -> PreparedChoice {
+        PreparedChoice::from_cumulative(self.prefix_sums.to_vec())
+            .expect("prefix sums of non-negative weights don't decrease")
+    }
+
+    /// Chooses one index by its weight in O(log N).
+    /// @return None, if there are no weights.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        self.sample_n(rng, 1).pop()
+    }
+
+    /// Chooses n indices by their weights with stochastic universal sampling. The indices are
+    /// the same as the ones of `to_prepared().sample_n` with the same generator.
+    /// @return n indices in ascending order or nothing, if there are no weights.
+    pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        if N == 0 || n == 0 {
+            return Vec::new();
+        }
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = rng.next_f64();
+        let sum = self.total_weight();
+        let spoke_gap: f64 = sum / n as f64;
+
+        let mut i: usize = 0;
+        let mut choices: Vec<usize> = Vec::with_capacity(n);
+        let mut current_spoke: f64 = spin * spoke_gap;
+
+        while current_spoke < sum && choices.len() < n {
+            i += self.prefix_sums[i..].partition_point(|&s| s < current_spoke);
+            choices.push(i);
+            current_spoke += spoke_gap;
+        }
+
+        // add this condition, because float leads to inaccurate
+        // calculations which can miss some samples
+        while choices.len() < n {
+            choices.push(i);
+        }
+
+        choices
+    }
+}
@@ -0,0 +1,218 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Entropy Sources
+//! All samplers draw their randomness from an `EntropySource`, which only has to supply
+//! uniformly distributed `u64`s. Every `rand::Rng` is an entropy source. Besides, this module
+//! ships the hardware generator `RdRand` of x86-64 CPUs and the counter-based generator
+//! `Philox4x32`, whose output is a pure function of its seed and counter.
+//!
+//! An `EntropyRng` turns any entropy source into a `rand::Rng`, so that it can drive
+//! `RandomChoice` and every sampler of this crate.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::RandomChoice;
+//! use random_choice::core::entropy::Philox4x32;
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! let mut random_choice = RandomChoice::from_entropy(Philox4x32::new(5000));
+//! let choices = random_choice.random_choice_indices_f64(&weights, 100);
+//!
+//! let mut again = RandomChoice::from_entropy(Philox4x32::new(5000));
+//! assert_eq!(again.random_choice_indices_f64(&weights, 100), choices);
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+/// A source of uniformly distributed random bits.
+pub trait EntropySource {
+    /// Returns the next 64 uniformly distributed random bits.
+    fn next_bits(&mut self) -> u64;
+}
+
+impl<R: Rng + ?Sized> EntropySource for R {
+    fn next_bits(&mut self) -> u64 {
+        self.next_u64()
+    }
+}
+
+/// Adapts an `EntropySource` to a `rand::Rng`.
+#[derive(Debug, Clone)]
+pub struct EntropyRng<E: EntropySource> {
+    source: E,
+}
+
+impl<E: EntropySource> EntropyRng<E> {
+    /// Creates a `rand::Rng`, which returns the bits of the entropy source.
+    pub fn new(source: E) -> Self {
+        EntropyRng { source }
+    }
+
+    /// Returns the entropy source.
+    pub fn source(&self) -> &E {
+        &self.source
+    }
+
+    /// Unwraps the entropy source.
+    pub fn into_source(self) -> E {
+        self.source
+    }
+}
+
+impl<E: EntropySource> Rng for EntropyRng<E> {
+    fn next_u32(&mut self) -> u32 {
+        (self.source.next_bits() >> 32) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.source.next_bits()
+    }
+}
+
+impl<E: EntropySource> RandomChoice<EntropyRng<E>> {
+    /// Creates a new RandomChoice struct, which draws its randomness from an entropy source.
+    pub fn from_entropy(source: E) -> Self {
+        RandomChoice::new(EntropyRng::new(source))
+    }
+}
+
+/// The hardware random number generator of x86-64 CPUs with the `RDRAND` instruction.
+#[cfg(target_arch = "x86_64")]
+#[derive(Debug, Clone, Copy)]
+pub struct RdRand {
+    _supported: (),
+}
+
+#[cfg(target_arch = "x86_64")]
+impl RdRand {
+    /// The number of retries, after which Intel considers the generator to be broken.
+    const RETRIES: usize = 10;
+
+    /// @return None, if the CPU doesn't support `RDRAND`.
+    pub fn new() -> Option<Self> {
+        if is_x86_feature_detected!("rdrand") {
+            Some(RdRand { _supported: () })
+        } else {
+            None
+        }
+    }
+
+    #[target_feature(enable = "rdrand")]
+    unsafe fn step() -> Option<u64> {
+        let mut value: u64 = 0;
+
+        if ::std::arch::x86_64::_rdrand64_step(&mut value) == 1 {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+impl EntropySource for RdRand {
+    /// Panics, if the hardware fails to deliver random bits after 10 retries.
+    fn next_bits(&mut self) -> u64 {
+        for _ in 0..RdRand::RETRIES {
+            // RdRand is only constructed, if the CPU supports the instruction
+            if let Some(value) = unsafe { RdRand::step() } {
+                return value;
+            }
+        }
+
+        panic!("RDRAND failed {} times in a row", RdRand::RETRIES)
+    }
+}
+
+/// The counter-based generator Philox4x32-10 (Salmon, Moraes, Dror and Shaw, "Parallel random
+/// numbers: as easy as 1, 2, 3", 2011). Every block of 128 bits is a bijection of a 128 bit
+/// counter keyed by the seed, so the generator can jump to any position in O(1).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Philox4x32 {
+    seed: u64,
+    counter: [u64; 2],
+    /// The second half of the current block, which hasn't been returned yet.
+    buffered: Option<u64>,
+}
+
+impl Philox4x32 {
+    const ROUNDS: usize = 10;
+    const M0: u32 = 0xD251_1F53;
+    const M1: u32 = 0xCD9E_8D57;
+    const W0: u32 = 0x9E37_79B9;
+    const W1: u32 = 0xBB67_AE85;
+
+    /// Creates a generator at counter zero.
+    pub fn new(seed: u64) -> Self {
+        Philox4x32::with_counter(seed, [0, 0])
+    }
+
+    /// Creates a generator, whose next block is the block of the counter.
+    pub fn with_counter(seed: u64, counter: [u64; 2]) -> Self {
+        Philox4x32 {
+            seed,
+            counter,
+            buffered: None,
+        }
+    }
+
+    /// Returns the seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the counter of the next block.
+    pub fn counter(&self) -> [u64; 2] {
+        self.counter
+    }
+
+    /// Computes the block of a counter.
+    /// @param seed the key of the bijection.
+    /// @param counter the 128 bit counter as two words, the least significant one first.
+    pub fn block(seed: u64, counter: [u64; 2]) -> [u64; 2] {
+        let mut key = [seed as u32, (seed >> 32) as u32];
+        let mut c = [counter[0] as u32,
+                     (counter[0] >> 32) as u32,
+                     counter[1] as u32,
+                     (counter[1] >> 32) as u32];
+
+        for round in 0..Philox4x32::ROUNDS {
+            if round > 0 {
+                key[0] = key[0].wrapping_add(Philox4x32::W0);
+                key[1] = key[1].wrapping_add(Philox4x32::W1);
+            }
+
+            let product0 = u64::from(Philox4x32::M0) * u64::from(c[0]);
+            let product1 = u64::from(Philox4x32::M1) * u64::from(c[2]);
+
+            c = [(product1 >> 32) as u32 ^ c[1] ^ key[0],
+                 product1 as u32,
+                 (product0 >> 32) as u32 ^ c[3] ^ key[1],
+                 product0 as u32];
+        }
+
+        [u64::from(c[0]) | u64::from(c[1]) << 32, u64::from(c[2]) | u64::from(c[3]) << 32]
+    }
+}
+
+impl EntropySource for Philox4x32 {
+    fn next_bits(&mut self) -> u64 {
+        if let Some(value) = self.buffered.take() {
+            return value;
+        }
+
+        let block = Philox4x32::block(self.seed, self.counter);
+
+        let (low, carry) = self.counter[0].overflowing_add(1);
+        self.counter = [low, self.counter[1].wrapping_add(carry as u64)];
+        self.buffered = Some(block[1]);
+
+        block[0]
+    }
+}
@@ -0,0 +1,236 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Core Sampler
+//! `RandomChoice` and its stochastic universal sampling over slices of weights, and the checked
+//! 1.0 entry point `Sampler` on top of it. It only depends on `rand` and is available without
+//! any feature. Its submodules are the foundations, which the other modules build on: weighted
+//! collections, compile-time cumulative tables, entropy sources, counter-based and recording
+//! generators, progress hooks, the rescaling of tiny weights and the golden test vectors.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::core::random_choice;
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! assert_eq!(random_choice().random_choice_indices_f64(&weights, 10).len(), 10);
+//! # }
+//! ```
+
+pub mod collection;
+pub mod counter;
+pub mod cumulative;
+pub mod entropy;
+pub mod progress;
+pub mod recording;
+pub mod rescale;
+pub mod sampler;
+pub mod test_vectors;
+
+use rand::{thread_rng, ThreadRng, Rng};
+
+use self::rescale::Rescaling;
+
+pub struct RandomChoice<RNG: Rng> {
+    pub(crate) rng: RNG,
+    /// Whether tiny and subnormal f64 weights are rescaled, see `rescale`.
+    pub(crate) rescale: bool,
+}
+
+/// Creates a new RandomChoice struct using the ThreadRng
+pub fn random_choice() -> RandomChoice<ThreadRng> {
+    RandomChoice::new(thread_rng())
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Creates a new RandomChoice struct.
+    /// @param rng the random number generator to use with this stuct.
+    pub fn new(rng: RNG) -> Self {
+        RandomChoice { rng, rescale: true }
+    }
+
+    /// Switches the automatic rescaling of tiny and subnormal f64 weights on or off.
+    /// It is on by default, see the module `rescale`.
+    pub fn with_rescaling(mut self, enabled: bool) -> Self {
+        self.rescale = enabled;
+        self
+    }
+
+    /// Chooses n indices by their weights. The greater their weights the more likely they get chosen.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param n Number of randomly chosen indices by weight.
+    /// @return randomly selected indices in ascending order
+    pub fn random_choice_indices_f64(&mut self, weights: &[f64], n: usize) -> Vec<usize> {
+        let sum: f64 = match self.rescaling(weights) {
+            (_, Some(rescaling)) => {
+                return self.random_choice_indices_rescaled(weights, n, rescaling);
+            }
+            (sum, None) => sum,
+        };
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = self.rng.next_f64();
+
+        sus_indices(weights.len(), n, spin, sum, |i| weights[i])
+    }
+
+    /// Returns the sum of the weights and how they are rescaled, if rescaling is switched on.
+    fn rescaling(&self, weights: &[f64]) -> (f64, Option<Rescaling>) {
+        if self.rescale {
+            rescale::scan(weights)
+        } else {
+            (weights.iter().fold(0.0, |acc, &i| acc + i), None)
+        }
+    }
+
+    /// Returns the sum of the weights after they are rescaled and how they are rescaled, i.e.
+    /// the wheel of `random_choice_indices_f64`.
+    pub(crate) fn rescaled_sum(&self, weights: &[f64]) -> (f64, Option<Rescaling>) {
+        match self.rescaling(weights) {
+            (_, Some(rescaling)) => {
+                let sum: f64 = weights.iter().fold(0.0, |acc, &w| acc + rescaling.apply(w));
+                (sum, Some(rescaling))
+            }
+            unscaled => unscaled,
+        }
+    }
+
+    /// Stochastic universal sampling over the rescaled weights.
+    fn random_choice_indices_rescaled(&mut self,
+                                      weights: &[f64],
+                                      n: usize,
+                                      rescaling: Rescaling)
+                                      -> Vec<usize> {
+        let sum: f64 = weights.iter().fold(0.0, |acc, &w| acc + rescaling.apply(w));
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = self.rng.next_f64();
+
+        sus_indices(weights.len(), n, spin, sum, |i| rescaling.apply(weights[i]))
+    }
+
+    /// Chooses the samples of `random_choice_indices_f64` without checking the input or
+    /// rescaling the weights, like the pre-1.0 `random_choice_f64`.
+    pub(crate) fn choose_f64<'a, T>(&mut self,
+                                    samples: &'a [T],
+                                    weights: &[f64],
+                                    n: usize)
+                                    -> Vec<&'a T> {
+        if weights.is_empty() || samples.is_empty() || n == 0 {
+            return Vec::new();
+        }
+
+        let sum: f64 = weights.iter().fold(0.0, |acc, &i| acc + i);
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = self.rng.next_f64();
+
+        sus_indices(weights.len(), n, spin, sum, |i| weights[i])
+            .into_iter()
+            .map(|i| &samples[i])
+            .collect()
+    }
+
+    /// Chooses the samples of `random_choice_indices_f32` without checking the input, like
+    /// the pre-1.0 `random_choice_f32`.
+    pub(crate) fn choose_f32<'a, T>(&mut self,
+                                    samples: &'a [T],
+                                    weights: &[f32],
+                                    n: usize)
+                                    -> Vec<&'a T> {
+        if weights.is_empty() || samples.is_empty() || n == 0 {
+            return Vec::new();
+        }
+
+        self.random_choice_indices_f32(weights, n).into_iter().map(|i| &samples[i]).collect()
+    }
+
+    /// Stochastic universal sampling over f32 weights, which are summed up as f64 and not
+    /// rescaled, like before 1.0.
+    pub(crate) fn random_choice_indices_f32(&mut self, weights: &[f32], n: usize) -> Vec<usize> {
+        let sum: f64 = weights.iter().fold(0.0, |acc, &i| acc + i as f64);
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = self.rng.next_f64();
+
+        sus_indices(weights.len(), n, spin, sum, |i| weights[i] as f64)
+    }
+
+    /// Chooses n distinct indices by their weights, i.e. without replacement.
+    /// The greater their weights the more likely they get chosen and the earlier they get drawn.
+    /// Indices with a weight of zero are never chosen.
+    ///
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param n Number of randomly chosen indices.
+    ///          If less than n weights are positive, all indices with positive weights are chosen.
+    /// @return randomly selected indices in the order they were drawn
+    pub fn random_choice_indices_without_replacement_f64(&mut self,
+                                                         weights: &[f64],
+                                                         n: usize)
+                                                         -> Vec<usize> {
+        if n == 0 {
+            return Vec::new();
+        }
+
+        // Efraimidis and Spirakis: the indices with the n greatest keys u^(1/w) are a weighted
+        // sample without replacement. The logarithm of the keys keeps small weights accurate.
+        let rng = &mut self.rng;
+        let mut keys: Vec<(f64, usize)> = weights.iter()
+            .enumerate()
+            .filter(|&(_, &w)| w > 0.0)
+            .map(|(i, &w)| ((1.0 - rng.next_f64()).ln() / w, i))
+            .collect();
+
+        let descending = |a: &(f64, usize), b: &(f64, usize)| b.0.partial_cmp(&a.0).unwrap();
+
+        if keys.len() > n {
+            keys.select_nth_unstable_by(n, descending);
+            keys.truncate(n);
+        }
+
+        keys.sort_by(descending);
+        keys.into_iter().map(|(_, i)| i).collect()
+    }
+}
+
+/// Stochastic universal sampling over the indices 0..len, whose weights are given by a function.
+/// Integrations use this to sample from weights, which are not stored in a slice of f64.
+///
+/// @param spin offset of the first spoke as fraction of the spoke gap ∈ [0.0, 1.0)
+/// @param sum the sum of all weights
+/// @return n indices in ascending order or nothing, if len or n is zero.
+pub(crate) fn sus_indices<F>(len: usize, n: usize, spin: f64, sum: f64, weight: F) -> Vec<usize>
+    where F: Fn(usize) -> f64
+{
+    if len == 0 || n == 0 {
+        return Vec::new();
+    }
+
+    let spoke_gap: f64 = sum / n as f64;
+
+    let mut i: usize = 0;
+    let mut accumulated_weights = weight(0);
+    let mut choices: Vec<usize> = Vec::with_capacity(n);
+    let mut current_spoke: f64 = spin * spoke_gap;
+
+    while current_spoke < sum && choices.len() < n {
+        while accumulated_weights < current_spoke {
+            i += 1;
+            accumulated_weights += weight(i);
+        }
+        choices.push(i);
+        current_spoke += spoke_gap;
+    }
+
+    // add this condition, because float leads to inaccurate
+    // calculations which can miss some samples
+    while choices.len() < n {
+        choices.push(i);
+    }
+
+    choices
+}
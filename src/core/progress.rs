@@ -0,0 +1,134 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Progress of Table Construction
+//! Building a table over hundreds of millions of weights takes a while. The constructors, which
+//! accept a progress hook, call it every k processed elements and at the end of every phase,
+//! so that CLIs and services can show the progress, and abort the construction,
+//! e.g. because a timeout has elapsed on a pathological input.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use std::ops::ControlFlow;
+//! use std::time::{Duration, Instant};
+//! use random_choice::prepared::alias::AliasTable;
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = (0..100000).map(|i| i as f64).collect();
+//! let deadline = Instant::now() + Duration::from_secs(60);
+//!
+//! let table = AliasTable::with_progress(&weights, 10000, |progress| {
+//!     println!("{:?}: {} of {}", progress.phase, progress.processed, progress.total);
+//!
+//!     if Instant::now() < deadline {
+//!         ControlFlow::Continue(())
+//!     } else {
+//!         ControlFlow::Break(())
+//!     }
+//! });
+//!
+//! assert!(table.is_ok());
+//! # }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::ops::ControlFlow;
+
+/// The phases of a table construction. Each of them processes every element once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// The weights are summed up.
+    Summing,
+    /// The weights are scaled and sorted into small and large ones.
+    Partitioning,
+    /// Small and large slots are paired up.
+    Pairing,
+}
+
+/// The progress of the current phase of a table construction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    /// The phase, which is running.
+    pub phase: Phase,
+    /// The number of elements processed in this phase.
+    pub processed: usize,
+    /// The number of elements this phase processes.
+    pub total: usize,
+}
+
+/// The error of a table construction, whose progress hook has returned `ControlFlow::Break`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aborted {
+    /// The progress, at which the construction was aborted.
+    pub progress: Progress,
+}
+
+impl fmt::Display for Aborted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "table construction was aborted in phase {:?} after {} of {} elements",
+               self.progress.phase,
+               self.progress.processed,
+               self.progress.total)
+    }
+}
+
+impl Error for Aborted {}
+
+/// The progress hook of a construction.
+pub(crate) type Hook<'a> = &'a mut dyn FnMut(&Progress) -> ControlFlow<()>;
+
+/// Calls the progress hook of a construction every k elements.
+pub(crate) struct Reporter<'a> {
+    every: usize,
+    countdown: usize,
+    hook: Option<Hook<'a>>,
+}
+
+impl<'a> Reporter<'a> {
+    /// A reporter without a hook, which never aborts.
+    pub fn none() -> Self {
+        Reporter {
+            every: usize::MAX,
+            countdown: usize::MAX,
+            hook: None,
+        }
+    }
+
+    /// @param every number of elements between two calls of the hook. Zero is treated as one.
+    pub fn new(every: usize, hook: Hook<'a>) -> Self {
+        let every = if every == 0 { 1 } else { every };
+
+        Reporter {
+            every,
+            countdown: every,
+            hook: Some(hook),
+        }
+    }
+
+    /// Reports once per k processed elements and when the phase is done.
+    /// It has to be called once per processed element.
+    #[inline]
+    pub fn report(&mut self, phase: Phase, processed: usize, total: usize) -> Result<(), Aborted> {
+        if let Some(ref mut hook) = self.hook {
+            self.countdown -= 1;
+
+            if self.countdown == 0 || processed == total {
+                self.countdown = self.every;
+
+                let progress = Progress {
+                    phase,
+                    processed,
+                    total,
+                };
+
+                if let ControlFlow::Break(()) = hook(&progress) {
+                    return Err(Aborted { progress });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,145 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Recording and Replaying Generators
+//! To investigate why a production draw chose an item, its raw random values can be recorded and
+//! replayed later, e.g. in a debugger against the same weights. A `RecordingRng` wraps the
+//! generator of the draw and records every value, which it returns. A `ReplayRng` returns the
+//! recorded values in the same order, so the same draw chooses the same items again.
+//!
+//! The replay panics, if it diverges from the recording, e.g. because the weights differ and
+//! the draw asks for more or other values than were recorded.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::RandomChoice;
+//! use random_choice::core::recording::{RecordingRng, ReplayRng};
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! // production
+//! let mut recorder = RecordingRng::new(rand::thread_rng());
+//! let chosen = RandomChoice::new(&mut recorder).random_choice_indices_f64(&weights, 3);
+//! let draws = recorder.take_draws();
+//!
+//! // investigation
+//! let replay = ReplayRng::new(draws);
+//! assert_eq!(RandomChoice::new(replay).random_choice_indices_f64(&weights, 3), chosen);
+//! # }
+//! ```
+
+use rand::Rng;
+
+/// A raw random value, which was returned by a generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "wire", derive(serde::Serialize, serde::Deserialize))]
+pub enum Draw {
+    /// The value of `next_u32`.
+    U32(u32),
+    /// The value of `next_u64`.
+    U64(u64),
+}
+
+/// Records the values of a generator, see `ReplayRng`.
+/// The floats and ranges of `Rng` are derived from `next_u32` and `next_u64`, whose values are
+/// recorded.
+#[derive(Debug, Clone)]
+pub struct RecordingRng<R: Rng> {
+    rng: R,
+    draws: Vec<Draw>,
+}
+
+impl<R: Rng> RecordingRng<R> {
+    /// Wraps a generator without recorded values.
+    pub fn new(rng: R) -> Self {
+        RecordingRng {
+            rng,
+            draws: Vec::new(),
+        }
+    }
+
+    /// Returns the values, which have been recorded so far.
+    pub fn draws(&self) -> &[Draw] {
+        &self.draws
+    }
+
+    /// Returns the recorded values and starts a new recording, e.g. for the next request.
+    pub fn take_draws(&mut self) -> Vec<Draw> {
+        std::mem::take(&mut self.draws)
+    }
+
+    /// Unwraps the generator and the recorded values.
+    pub fn into_parts(self) -> (R, Vec<Draw>) {
+        (self.rng, self.draws)
+    }
+}
+
+impl<R: Rng> Rng for RecordingRng<R> {
+    fn next_u32(&mut self) -> u32 {
+        let value = self.rng.next_u32();
+        self.draws.push(Draw::U32(value));
+        value
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let value = self.rng.next_u64();
+        self.draws.push(Draw::U64(value));
+        value
+    }
+}
+
+/// Returns recorded values in the order they were recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayRng {
+    draws: Vec<Draw>,
+    position: usize,
+}
+
+impl ReplayRng {
+    /// @param draws the values of a `RecordingRng`.
+    pub fn new(draws: Vec<Draw>) -> Self {
+        ReplayRng { draws, position: 0 }
+    }
+
+    /// Returns the index of the next value.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Returns the number of values, which are left.
+    pub fn remaining(&self) -> usize {
+        self.draws.len() - self.position
+    }
+
+    /// Returns the next value and panics, if no value is left.
+    fn next_draw(&mut self) -> Draw {
+        let draw = *self.draws
+            .get(self.position)
+            .unwrap_or_else(|| panic!("replay exhausted after {} values", self.position));
+
+        self.position += 1;
+        draw
+    }
+}
+
+impl Rng for ReplayRng {
+    fn next_u32(&mut self) -> u32 {
+        match self.next_draw() {
+            Draw::U32(value) => value,
+            draw => {
+                panic!("replay diverged at value {}: next_u32 for {:?}", self.position - 1, draw)
+            }
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self.next_draw() {
+            Draw::U64(value) => value,
+            draw => {
+                panic!("replay diverged at value {}: next_u64 for {:?}", self.position - 1, draw)
+            }
+        }
+    }
+}
@@ -0,0 +1,162 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Rescaling Tiny Weights
+//! Weights like likelihoods can be so small, that they are subnormal floats. The arithmetic of
+//! subnormal floats is dramatically slower on some CPUs, and their precision is so low, that
+//! the spokes of stochastic universal sampling are placed inaccurately and the draw is skewed.
+//!
+//! `Rescaling::detect` finds two cases in one pass over the weights:
+//!
+//! - the greatest weight is below `TINY`: all weights are multiplied by the power of two, which
+//!   brings the greatest weight to about 1.0. Multiplying by a power of two is exact and doesn't
+//!   change the probabilities.
+//! - some positive weights are below `RELATIVE_EPSILON` times the greatest weight, e.g. because
+//!   they are subnormal: these weights are set to zero. Their probability is below 2^-52 of the
+//!   greatest one each.
+//!
+//! The core sampler `random_choice_indices_f64`, the lazy `spokes_f64`, `sample_per_group` and
+//! `random_choice_indices_from` rescale automatically, unless it is switched off with
+//! `RandomChoice::with_rescaling(false)`. The prepared `PreparedChoice` and `ChunkedPrefixSums`
+//! always rescale, `PreparedChoice::from_cumulative` takes prefix sums as given. The legacy
+//! wrappers `random_choice_f64` and `random_choice_f32` keep the draws of the releases before
+//! 1.0 and never rescale. Weights, which are kept elsewhere, can be rescaled by
+//! `rescale_in_place`.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//! use random_choice::core::rescale::Rescaling;
+//!
+//! # fn main() {
+//! // likelihoods of 3:1, which are subnormal
+//! let weights: Vec<f64> = vec![1.5e-320, 0.5e-320];
+//! assert!(Rescaling::detect(&weights).is_some());
+//!
+//! let indices = random_choice().random_choice_indices_f64(&weights, 100);
+//! assert_eq!(indices.iter().filter(|&&i| i == 0).count(), 75);
+//! # }
+//! ```
+
+/// Weights are rescaled, if the greatest one is below 2^-1022 / 2^-52 = 2^-970. Then the sums
+/// and the spokes of a draw can become subnormal.
+pub const TINY: f64 = f64::MIN_POSITIVE / f64::EPSILON;
+
+/// Positive weights below this fraction of the greatest weight are set to zero. Every subnormal
+/// weight is below it, unless the greatest weight is below `TINY` as well.
+pub const RELATIVE_EPSILON: f64 = f64::EPSILON;
+
+/// How the weights of a draw are rescaled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rescaling {
+    /// The weights are multiplied by 2^exponent.
+    exponent: i32,
+    /// Weights below the floor are set to zero.
+    floor: f64,
+}
+
+impl Rescaling {
+    /// Returns how the weights are rescaled or None, if they don't need to be.
+    pub fn detect(weights: &[f64]) -> Option<Self> {
+        scan(weights).1
+    }
+
+    /// Returns the exponent e, the weights are multiplied by 2^e.
+    pub fn exponent(&self) -> i32 {
+        self.exponent
+    }
+
+    /// Returns the floor, weights below it are set to zero.
+    pub fn floor(&self) -> f64 {
+        self.floor
+    }
+
+    /// Rescales one weight. Weights below the floor become zero.
+    pub fn apply(&self, weight: f64) -> f64 {
+        // comparing first avoids arithmetic with the weights, which are set to zero
+        if weight < self.floor {
+            return 0.0;
+        }
+
+        if self.exponent == 0 {
+            return weight;
+        }
+
+        // 2^exponent may exceed f64, its halves don't
+        let half = self.exponent / 2;
+        weight * 2f64.powi(half) * 2f64.powi(self.exponent - half)
+    }
+}
+
+/// Rescales weights in place, e.g. before they are prepared for many draws.
+/// @return how the weights were rescaled or None, if they were left unchanged.
+pub fn rescale_in_place(weights: &mut [f64]) -> Option<Rescaling> {
+    let rescaling = Rescaling::detect(weights)?;
+
+    for weight in weights.iter_mut() {
+        *weight = rescaling.apply(*weight);
+    }
+
+    Some(rescaling)
+}
+
+/// Finds the greatest and the smallest positive weight in one pass over weights, which may come
+/// in chunks or interleaved with the weights of other wheels, and sums them up.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Detector {
+    sum: f64,
+    max: f64,
+    min: f64,
+}
+
+impl Detector {
+    pub(crate) fn new() -> Self {
+        Detector { sum: 0.0, max: 0.0, min: f64::INFINITY }
+    }
+
+    pub(crate) fn add(&mut self, weight: f64) {
+        self.sum += weight;
+        self.max = self.max.max(weight);
+
+        if weight > 0.0 {
+            self.min = self.min.min(weight);
+        }
+    }
+
+    /// Returns the sum of the weights as given.
+    pub(crate) fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Returns how the weights are rescaled or None, if they don't need to be.
+    pub(crate) fn rescaling(&self) -> Option<Rescaling> {
+        let floor = self.max * RELATIVE_EPSILON;
+
+        if self.max > 0.0 && self.max < TINY {
+            Some(Rescaling { exponent: -(self.max.log2().floor() as i32), floor })
+        } else if self.max.is_finite() && self.min < floor {
+            Some(Rescaling { exponent: 0, floor })
+        } else {
+            None
+        }
+    }
+}
+
+/// Sums up the weights and detects in the same pass, whether they need rescaling.
+pub(crate) fn scan(weights: &[f64]) -> (f64, Option<Rescaling>) {
+    let mut detector = Detector::new();
+
+    for &weight in weights {
+        detector.add(weight);
+    }
+
+    (detector.sum(), detector.rescaling())
+}
+
+/// Returns a weight, as it is placed on the wheel, i.e. after the rescaling, if there is one.
+pub(crate) fn rescaled(rescaling: Option<Rescaling>, weight: f64) -> f64 {
+    match rescaling {
+        Some(rescaling) => rescaling.apply(weight),
+        None => weight,
+    }
+}
@@ -0,0 +1,227 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Sampler
+//! The 1.0 entry point: a `Sampler` owns the injected random number generator and draws by its
+//! `Strategy`. In contrast to the methods of `RandomChoice`, which trust their input, it checks
+//! the samples and weights and returns a `SampleError` instead of choosing arbitrarily or
+//! panicking, e.g. if the lengths differ, a weight is negative or NaN or no weight is positive.
+//! Weights are `f64` or `f32`, see `Weight`.
+//!
+//! The sampling designs of the other modules are still methods of `RandomChoice`, which
+//! `random_choice_mut` lends out with the same generator.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::{SampleError, Sampler, Strategy};
+//!
+//! # fn main() {
+//! let samples = vec!["hi", "this", "is", "a", "test!"];
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! let mut sampler = Sampler::new(rand::thread_rng());
+//! assert_eq!(sampler.sample(&samples, &weights, 100).unwrap().len(), 100);
+//!
+//! let mut sampler = sampler.with_strategy(Strategy::WithoutReplacement);
+//! assert_eq!(sampler.sample_indices(&weights, 5).unwrap().len(), 5);
+//! assert_eq!(sampler.sample_indices(&weights, 6),
+//!            Err(SampleError::NotEnoughPositiveWeights { n: 6, positive: 5 }));
+//! # }
+//! ```
+
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+
+use rand::Rng;
+
+use crate::prepared::PreparedChoice;
+use crate::RandomChoice;
+
+/// How a `Sampler` chooses n indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Strategy {
+    /// Stochastic universal sampling: one spin of a wheel with n equally spaced spokes.
+    /// The indices are returned in ascending order.
+    #[default]
+    Stochastic,
+    /// n independent draws with replacement in the order they were drawn.
+    Independent,
+    /// n distinct indices in the order they were drawn, see
+    /// `RandomChoice::random_choice_indices_without_replacement_f64`.
+    WithoutReplacement,
+}
+
+/// The errors of the input of a draw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SampleError {
+    /// There are not as many samples as weights.
+    LengthMismatch { samples: usize, weights: usize },
+    /// The weight at the index is negative, infinite or NaN.
+    InvalidWeight { index: usize, weight: f64 },
+    /// The sum of the weights overflows.
+    Overflow,
+    /// At least one index is to be chosen, but no weight is positive.
+    NoPositiveWeight,
+    /// More distinct indices are to be chosen than weights are positive.
+    NotEnoughPositiveWeights { n: usize, positive: usize },
+}
+
+impl fmt::Display for SampleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SampleError::LengthMismatch { samples, weights } => {
+                write!(f, "{} samples, but {} weights", samples, weights)
+            }
+            SampleError::InvalidWeight { index, weight } => {
+                write!(f,
+                       "weight {} at index {} is not a finite, non-negative number",
+                       weight,
+                       index)
+            }
+            SampleError::Overflow => write!(f, "sum of weights overflows"),
+            SampleError::NoPositiveWeight => write!(f, "no weight is positive"),
+            SampleError::NotEnoughPositiveWeights { n, positive } => {
+                write!(f,
+                       "{} distinct indices requested, but only {} weights are positive",
+                       n,
+                       positive)
+            }
+        }
+    }
+}
+
+impl Error for SampleError {}
+
+/// The float types of weights.
+pub trait Weight: Copy {
+    /// Returns the weights as f64, which f64 weights are borrowed for.
+    fn to_f64_slice(weights: &[Self]) -> Cow<'_, [f64]>;
+}
+
+impl Weight for f64 {
+    fn to_f64_slice(weights: &[f64]) -> Cow<'_, [f64]> {
+        Cow::Borrowed(weights)
+    }
+}
+
+impl Weight for f32 {
+    fn to_f64_slice(weights: &[f32]) -> Cow<'_, [f64]> {
+        Cow::Owned(weights.iter().map(|&w| w as f64).collect())
+    }
+}
+
+/// Draws samples by their weights with an injected random number generator.
+pub struct Sampler<RNG: Rng> {
+    random_choice: RandomChoice<RNG>,
+    strategy: Strategy,
+}
+
+impl<RNG: Rng> Sampler<RNG> {
+    /// Creates a sampler with `Strategy::Stochastic`.
+    /// @param rng the random number generator of all draws.
+    pub fn new(rng: RNG) -> Self {
+        Sampler {
+            random_choice: RandomChoice::new(rng),
+            strategy: Strategy::default(),
+        }
+    }
+
+    /// Sets the strategy of the following draws.
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Returns the strategy of the draws.
+    pub fn strategy(&self) -> Strategy {
+        self.strategy
+    }
+
+    /// Returns the random number generator, e.g. to draw something else in between.
+    pub fn rng_mut(&mut self) -> &mut RNG {
+        &mut self.random_choice.rng
+    }
+
+    /// Returns the random number generator.
+    pub fn into_rng(self) -> RNG {
+        self.random_choice.rng
+    }
+
+    /// Lends out the sampler as `RandomChoice` for the sampling designs of the other modules.
+    pub fn random_choice_mut(&mut self) -> &mut RandomChoice<RNG> {
+        &mut self.random_choice
+    }
+
+    /// Chooses n samples by their weights. See `sample_indices`.
+    /// @return the chosen samples in the order of the strategy.
+    pub fn sample<'a, T, W: Weight>(&mut self,
+                                    samples: &'a [T],
+                                    weights: &[W],
+                                    n: usize)
+                                    -> Result<Vec<&'a T>, SampleError> {
+        if samples.len() != weights.len() {
+            return Err(SampleError::LengthMismatch {
+                samples: samples.len(),
+                weights: weights.len(),
+            });
+        }
+
+        Ok(self.sample_indices(weights, n)?.into_iter().map(|i| &samples[i]).collect())
+    }
+
+    /// Chooses n indices by their weights.
+    ///
+    /// @param weights Weights of the indices 0..weights.len(). They don't have to sum up to 1.0.
+    /// @param n Number of chosen indices.
+    /// @return the chosen indices in the order of the strategy or the first invalid input.
+    ///         Choosing no index always succeeds for valid weights.
+    pub fn sample_indices<W: Weight>(&mut self,
+                                     weights: &[W],
+                                     n: usize)
+                                     -> Result<Vec<usize>, SampleError> {
+        let weights = W::to_f64_slice(weights);
+        let mut sum = 0.0;
+        let mut positive = 0;
+
+        for (index, &weight) in weights.iter().enumerate() {
+            if !weight.is_finite() || weight < 0.0 {
+                return Err(SampleError::InvalidWeight { index, weight });
+            }
+
+            sum += weight;
+            positive += (weight > 0.0) as usize;
+        }
+
+        if sum.is_infinite() {
+            return Err(SampleError::Overflow);
+        }
+
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        if positive == 0 {
+            return Err(SampleError::NoPositiveWeight);
+        }
+
+        match self.strategy {
+            Strategy::Stochastic => Ok(self.random_choice.random_choice_indices_f64(&weights, n)),
+            Strategy::Independent => {
+                let prepared = PreparedChoice::new(&weights);
+                let rng = &mut self.random_choice.rng;
+
+                Ok((0..n)
+                    .map(|_| prepared.sample(rng).expect("weights are not empty"))
+                    .collect())
+            }
+            Strategy::WithoutReplacement if positive < n => {
+                Err(SampleError::NotEnoughPositiveWeights { n, positive })
+            }
+            Strategy::WithoutReplacement => {
+                Ok(self.random_choice.random_choice_indices_without_replacement_f64(&weights, n))
+            }
+        }
+    }
+}
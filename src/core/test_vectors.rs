@@ -0,0 +1,717 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Golden Test Vectors
+//! Fixed inputs and seeds with the outputs, which every algorithm of this crate produces for them.
+//! Replay systems can check them, e.g. at startup or in their own test suite, to detect that a
+//! new version of this crate would draw differently than the one that recorded their draws.
+//!
+//! ## Sampling Epoch
+//! The draws of the algorithms listed in `Algorithm` are a pure function of their inputs and the
+//! output of the random number generator. This is kept across versions. If a release has to change
+//! the order or the number of random values an algorithm consumes, or which indices it derives
+//! from them, `SAMPLING_EPOCH` is increased and these vectors are updated in the same release.
+//! Draws recorded under one epoch can be replayed by any version with the same epoch.
+//!
+//! The vectors use the `ChaChaRng`, because its output doesn't depend on the platform.
+//! The draws of `DrawPlan` are pinned by `DRAW_PLAN_TEST_VECTORS` with the `wire` feature.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::core::test_vectors::{self, SAMPLING_EPOCH};
+//!
+//! # fn main() {
+//! assert_eq!(SAMPLING_EPOCH, 1);
+//! assert_eq!(test_vectors::verify(), Ok(()));
+//! # }
+//! ```
+
+use std::convert::TryInto;
+
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+use crate::prepared::alias::{AliasTable, AliasTable32};
+use crate::prepared::approximate::ApproximateSampler;
+use crate::core::collection::ChunkedPrefixSums;
+use crate::strategies::conditional_poisson::ConditionalPoisson;
+use crate::core::cumulative::CumulativeWeights;
+use crate::prepared::fenwick::FenwickSampler;
+use crate::prepared::{Placement, PreparedChoice};
+use crate::strategies::bandit::{BetaBandit, Exp3};
+use crate::strategies::capacity::CapacitySampler;
+use crate::strategies::categorical::Categorical;
+use crate::strategies::kfold::weighted_kfold;
+use crate::strategies::markov::MarkovSampler;
+use crate::strategies::subsets::SubsetWeighting;
+use crate::strategies::temporal::TemporalSampler;
+use crate::strategies::ties::TieBreaking;
+use crate::strategies::walk::WeightedRandomWalk;
+#[cfg(feature = "wire")]
+use crate::integrations::wire::DrawPlan;
+use crate::RandomChoice;
+
+/// The epoch of the draws. It is increased whenever an algorithm draws differently than before.
+pub const SAMPLING_EPOCH: u32 = 1;
+
+/// The algorithms, which are covered by the test vectors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// `Sampler::sample` with f64 weights and the legacy `RandomChoice::random_choice_f64`
+    RandomChoiceF64,
+    /// `Sampler::sample` with f32 weights and the legacy `RandomChoice::random_choice_f32`,
+    /// the weights are converted to f32.
+    RandomChoiceF32,
+    /// `RandomChoice::random_choice_indices_f64` and `RandomChoice::spokes_f64`
+    IndicesF64,
+    /// `RandomChoice::random_choice_indices_without_replacement_f64`
+    IndicesWithoutReplacementF64,
+    /// `AliasTable::sample_n`
+    AliasTable,
+    /// `PreparedChoice::sample_n_with` with a placement of the spokes
+    PreparedChoice(Placement),
+    /// `ChunkedPrefixSums::sample_n`, the weights are split into chunks of `CHUNK_SIZE`.
+    ChunkedPrefixSums,
+    /// `FenwickSampler::sample_n`
+    FenwickSampler,
+    /// `CumulativeWeights::sample_n` of 5, 6 or 20 weights
+    CumulativeWeights,
+    /// `ApproximateSampler::sample_n` with a number of precision bits
+    Approximate(u32),
+    /// `RandomChoice::reservoir_sample_f64`, the items are the indices.
+    WeightedReservoir,
+    /// `RandomChoice::varopt_sample_f64`, the kept indices in ascending order.
+    VarOptReservoir,
+    /// `RandomChoice::priority_sample_f64`, the kept indices by descending priority.
+    PrioritySketch,
+    /// `ConditionalPoisson::sample`, the weights are the working probabilities and n is the
+    /// sample size.
+    ConditionalPoisson,
+    /// `RandomChoice::random_subset_indices_f64` with a weighting of the subsets
+    Subset(SubsetWeighting),
+    /// `AliasTable32::sample_n`
+    AliasTable32,
+    /// `RandomChoice::sample_per_group` with n per group, index i is in group i % `GROUPS`.
+    /// The indices of the groups are concatenated in the order of the groups.
+    SamplePerGroup,
+    /// `RandomChoice::random_choice_indices_with_ties_f64` with `TieBreaking::Index`
+    TiesByIndex,
+    /// `RandomChoice::random_choice_indices_with_ties_f64` with `TieBreaking::Random`
+    TiesRandom,
+    /// `BetaBandit::select_arm` n times, after every arm was updated once with its weight
+    /// divided by the greatest weight as reward.
+    BetaBandit,
+    /// `Exp3::draw` n times with `EXP3_GAMMA`, after every arm was updated once with one minus
+    /// its weight divided by the greatest weight as loss.
+    Exp3,
+    /// `Categorical::sample_n`, the label of index i is i, which is parsed back.
+    Categorical,
+    /// `TemporalSampler::sample_n` at `TIME_STEP`, the weight of index i at time step t is the
+    /// weight of index (i + t) % len.
+    TemporalSampler,
+    /// `MarkovSampler::walk` from state 0 over n steps, the transition from state s to state j
+    /// has the weight of index (s + j) % len.
+    MarkovSampler,
+    /// `CapacitySampler::sample_n` with a capacity of `CAPACITY` per index
+    CapacitySampler,
+    /// `weighted_kfold` with n folds, the fold of every index.
+    WeightedKfold,
+    /// `RandomChoice::resize_population` to n samples, which are the indices.
+    ResizePopulation,
+    /// `RandomChoice::random_choice_indices_capped_f64` with `CAPPED_MAX_RATIO`
+    Capped,
+    /// `RandomChoice::sample_quotas` with a quota of n per cell, index i is in cell
+    /// i % `GROUPS`.
+    SampleQuotas,
+    /// `WeightedRandomWalk` from node 0 without restarts, its first n nodes. The edge from node
+    /// s to node j has the weight of index (s + j) % len.
+    WeightedRandomWalk,
+    /// `DrawPlan::sample_range` over all spokes, the seed of the plan is made of the first two
+    /// words of the seed.
+    #[cfg(feature = "wire")]
+    DrawPlan,
+}
+
+/// The number of weights per chunk of `Algorithm::ChunkedPrefixSums`.
+pub const CHUNK_SIZE: usize = 4;
+
+/// The number of groups of `Algorithm::SamplePerGroup` and cells of `Algorithm::SampleQuotas`.
+pub const GROUPS: usize = 3;
+
+/// The exploration rate of `Algorithm::Exp3`.
+pub const EXP3_GAMMA: f64 = 0.1;
+
+/// The time step of `Algorithm::TemporalSampler`.
+pub const TIME_STEP: u64 = 3;
+
+/// The capacity of every index of `Algorithm::CapacitySampler`.
+pub const CAPACITY: usize = 2;
+
+/// The greatest share of the choices per index of `Algorithm::Capped`.
+pub const CAPPED_MAX_RATIO: f64 = 0.25;
+
+/// One seed with the inputs of a draw and its expected output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TestVector {
+    /// The algorithm, which draws.
+    pub algorithm: Algorithm,
+    /// The seed of the `ChaChaRng`.
+    pub seed: [u32; 4],
+    /// The weights of the indices 0..weights.len().
+    pub weights: &'static [f64],
+    /// The number of chosen indices.
+    pub n: usize,
+    /// The chosen indices in the order they are returned.
+    pub expected: &'static [usize],
+}
+
+impl TestVector {
+    /// Creates the random number generator of this vector.
+    pub fn rng(&self) -> ChaChaRng {
+        ChaChaRng::from_seed(&self.seed)
+    }
+
+    /// Draws with the current implementation of the algorithm.
+    pub fn draw(&self) -> Vec<usize> {
+        let mut rng = self.rng();
+
+        match self.algorithm {
+            Algorithm::RandomChoiceF64 => {
+                let samples: Vec<usize> = (0..self.weights.len()).collect();
+                RandomChoice::new(rng)
+                    .choose_f64(&samples, self.weights, self.n)
+                    .into_iter()
+                    .cloned()
+                    .collect()
+            }
+            Algorithm::RandomChoiceF32 => {
+                let samples: Vec<usize> = (0..self.weights.len()).collect();
+                let weights: Vec<f32> = self.weights.iter().map(|&w| w as f32).collect();
+                RandomChoice::new(rng)
+                    .choose_f32(&samples, &weights, self.n)
+                    .into_iter()
+                    .cloned()
+                    .collect()
+            }
+            Algorithm::IndicesF64 => {
+                RandomChoice::new(rng).random_choice_indices_f64(self.weights, self.n)
+            }
+            Algorithm::IndicesWithoutReplacementF64 => {
+                RandomChoice::new(rng)
+                    .random_choice_indices_without_replacement_f64(self.weights, self.n)
+            }
+            Algorithm::AliasTable => AliasTable::new(self.weights).sample_n(&mut rng, self.n),
+            Algorithm::PreparedChoice(placement) => {
+                PreparedChoice::new(self.weights).sample_n_with(&mut rng, self.n, placement)
+            }
+            Algorithm::ChunkedPrefixSums => {
+                let chunks: Vec<&[f64]> = self.weights.chunks(CHUNK_SIZE).collect();
+                ChunkedPrefixSums::new(&chunks[..]).sample_n(&mut rng, self.n)
+            }
+            Algorithm::FenwickSampler => {
+                FenwickSampler::new(self.weights).sample_n(&mut rng, self.n)
+            }
+            Algorithm::CumulativeWeights => {
+                match self.weights.len() {
+                    5 => cumulative_sample_n::<_, 5>(self.weights, &mut rng, self.n),
+                    6 => cumulative_sample_n::<_, 6>(self.weights, &mut rng, self.n),
+                    20 => cumulative_sample_n::<_, 20>(self.weights, &mut rng, self.n),
+                    len => panic!("no test vector of CumulativeWeights has {} weights", len),
+                }
+            }
+            Algorithm::Approximate(precision_bits) => {
+                ApproximateSampler::new(self.weights, precision_bits).sample_n(&mut rng, self.n)
+            }
+            Algorithm::WeightedReservoir => {
+                let items = self.weights.iter().cloned().enumerate();
+                RandomChoice::new(rng).reservoir_sample_f64(items, self.n)
+            }
+            Algorithm::VarOptReservoir => {
+                let items = self.weights.iter().cloned().enumerate();
+                let mut kept: Vec<usize> = RandomChoice::new(rng)
+                    .varopt_sample_f64(items, self.n)
+                    .into_samples()
+                    .into_iter()
+                    .map(|(i, _)| i)
+                    .collect();
+
+                kept.sort_unstable();
+                kept
+            }
+            Algorithm::PrioritySketch => {
+                let items = self.weights.iter().cloned().enumerate();
+                RandomChoice::new(rng)
+                    .priority_sample_f64(items, self.n)
+                    .into_samples()
+                    .into_iter()
+                    .map(|(i, _)| i)
+                    .collect()
+            }
+            Algorithm::ConditionalPoisson => {
+                ConditionalPoisson::new(self.weights, self.n)
+                    .expect("the weights are working probabilities")
+                    .sample(&mut RandomChoice::new(rng))
+            }
+            Algorithm::Subset(weighting) => {
+                RandomChoice::new(rng).random_subset_indices_f64(self.weights, self.n, weighting)
+            }
+            Algorithm::AliasTable32 => {
+                AliasTable32::new(self.weights)
+                    .expect("the weights are exact in f32")
+                    .sample_n(&mut rng, self.n)
+            }
+            Algorithm::SamplePerGroup => {
+                let group_ids: Vec<usize> = (0..self.weights.len()).map(|i| i % GROUPS).collect();
+                RandomChoice::new(rng)
+                    .sample_per_group(&group_ids, self.weights, self.n)
+                    .into_iter()
+                    .flat_map(|(_, indices)| indices)
+                    .collect()
+            }
+            Algorithm::TiesByIndex => {
+                RandomChoice::new(rng)
+                    .random_choice_indices_with_ties_f64(self.weights, self.n, TieBreaking::Index)
+            }
+            Algorithm::TiesRandom => {
+                RandomChoice::new(rng)
+                    .random_choice_indices_with_ties_f64(self.weights, self.n, TieBreaking::Random)
+            }
+            Algorithm::BetaBandit => {
+                let max = self.weights.iter().cloned().fold(0.0, f64::max);
+                let mut bandit = BetaBandit::new(self.weights.len());
+
+                for (arm, &weight) in self.weights.iter().enumerate() {
+                    bandit.update(arm, weight / max);
+                }
+
+                (0..self.n).filter_map(|_| bandit.select_arm(&mut rng)).collect()
+            }
+            Algorithm::Exp3 => {
+                let max = self.weights.iter().cloned().fold(0.0, f64::max);
+                let mut bandit = Exp3::new(self.weights.len(), EXP3_GAMMA);
+
+                for (arm, &weight) in self.weights.iter().enumerate() {
+                    bandit.update(arm, 1.0 - weight / max);
+                }
+
+                (0..self.n).filter_map(|_| bandit.draw(&mut rng)).collect()
+            }
+            Algorithm::Categorical => {
+                let pairs: Vec<(String, f64)> = self.weights.iter()
+                    .enumerate()
+                    .map(|(i, &weight)| (i.to_string(), weight))
+                    .collect();
+
+                Categorical::new(&pairs)
+                    .expect("the labels are distinct and the weights are valid")
+                    .sample_n(&mut rng, self.n)
+                    .into_iter()
+                    .map(|label| label.parse().expect("the labels are indices"))
+                    .collect()
+            }
+            Algorithm::TemporalSampler => {
+                let len = self.weights.len();
+                let weights = self.weights;
+
+                TemporalSampler::new(len, |t, i| weights[(i + t as usize) % len])
+                    .sample_n(&mut rng, TIME_STEP, self.n)
+            }
+            Algorithm::MarkovSampler => {
+                let len = self.weights.len();
+                let mut markov: MarkovSampler<usize> = MarkovSampler::new();
+
+                for from in 0..len {
+                    for to in 0..len {
+                        markov.observe_weighted(from, to, self.weights[(from + to) % len]);
+                    }
+                }
+
+                markov.walk(&mut rng, 0, self.n)
+            }
+            Algorithm::CapacitySampler => {
+                let capacities = vec![CAPACITY; self.weights.len()];
+                CapacitySampler::new(self.weights, &capacities).sample_n(&mut rng, self.n)
+            }
+            Algorithm::WeightedKfold => weighted_kfold(self.weights, self.n, &mut rng),
+            Algorithm::ResizePopulation => {
+                let samples: Vec<usize> = (0..self.weights.len()).collect();
+                RandomChoice::new(rng).resize_population(&samples, self.weights, self.n).0
+            }
+            Algorithm::Capped => {
+                RandomChoice::new(rng)
+                    .random_choice_indices_capped_f64(self.weights, self.n, CAPPED_MAX_RATIO)
+            }
+            Algorithm::SampleQuotas => {
+                let cells: Vec<usize> = (0..self.weights.len()).map(|i| i % GROUPS).collect();
+                let quotas: Vec<(usize, usize)> = (0..GROUPS).map(|cell| (cell, self.n)).collect();
+
+                RandomChoice::new(rng)
+                    .sample_quotas(&cells, self.weights, &quotas)
+                    .expect("every cell has enough indices of positive weight")
+            }
+            Algorithm::WeightedRandomWalk => {
+                let len = self.weights.len();
+                let adjacency: Vec<Vec<(usize, f64)>> = (0..len)
+                    .map(|s| (0..len).map(|j| (j, self.weights[(s + j) % len])).collect())
+                    .collect();
+                let neighbors = |s: usize| &adjacency[s][..];
+
+                WeightedRandomWalk::new(&neighbors, 0, rng).take(self.n).collect()
+            }
+            #[cfg(feature = "wire")]
+            Algorithm::DrawPlan => {
+                let seed = self.seed[0] as u64 | (self.seed[1] as u64) << 32;
+                DrawPlan::new(PreparedChoice::new(self.weights), seed, self.n as u64)
+                    .sample_range(0..self.n as u64)
+            }
+        }
+    }
+
+    /// Returns true, if the current implementation draws the expected indices.
+    pub fn check(&self) -> bool {
+        self.draw() == self.expected
+    }
+}
+
+/// Samples `CumulativeWeights`, whose number of weights is only known at compile time.
+fn cumulative_sample_n<R: Rng, const N: usize>(weights: &[f64],
+                                               rng: &mut R,
+                                               n: usize)
+                                               -> Vec<usize> {
+    let weights: [f64; N] = weights.try_into().expect("N weights");
+    CumulativeWeights::new_const(weights).sample_n(rng, n)
+}
+
+const WEIGHTS_README: &[f64] = &[5.6, 7.8, 9.7, 1.1, 2.0];
+
+const WEIGHTS_LINEAR: &[f64] = &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0,
+                                 12.0, 13.0, 14.0, 15.0, 16.0, 17.0, 18.0, 19.0];
+
+const WEIGHTS_SKEWED: &[f64] = &[0.001, 1000.0, 0.001, 0.0, 5.0, 0.25];
+
+const WEIGHTS_PROBABILITIES: &[f64] = &[0.1, 0.9, 0.5, 1.0, 0.0, 0.3, 0.7, 0.25];
+
+const WEIGHTS_TIES: &[f64] = &[2.0, 1.0, 2.0, 0.5, 1.0, 2.0, 0.0, 1.0];
+
+/// All test vectors of the current `SAMPLING_EPOCH`.
+pub const TEST_VECTORS: &[TestVector] = &[
+    TestVector {
+        algorithm: Algorithm::RandomChoiceF64,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 10,
+        expected: &[0, 0, 1, 1, 1, 2, 2, 2, 2, 4],
+    },
+    TestVector {
+        algorithm: Algorithm::RandomChoiceF64,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_LINEAR,
+        n: 12,
+        expected: &[3, 6, 9, 10, 12, 13, 14, 15, 16, 17, 18, 19],
+    },
+    TestVector {
+        algorithm: Algorithm::RandomChoiceF32,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 10,
+        expected: &[0, 0, 1, 1, 1, 2, 2, 2, 2, 4],
+    },
+    TestVector {
+        algorithm: Algorithm::IndicesF64,
+        seed: [42, 0, 0, 0],
+        weights: WEIGHTS_SKEWED,
+        n: 6,
+        expected: &[1, 1, 1, 1, 1, 1],
+    },
+    TestVector {
+        algorithm: Algorithm::IndicesWithoutReplacementF64,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_LINEAR,
+        n: 8,
+        expected: &[17, 6, 4, 7, 5, 14, 19, 12],
+    },
+    TestVector {
+        algorithm: Algorithm::IndicesWithoutReplacementF64,
+        seed: [42, 0, 0, 0],
+        weights: WEIGHTS_SKEWED,
+        n: 3,
+        expected: &[1, 4, 5],
+    },
+    TestVector {
+        algorithm: Algorithm::AliasTable,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 10,
+        expected: &[2, 0, 4, 2, 1, 0, 2, 2, 0, 1],
+    },
+    TestVector {
+        algorithm: Algorithm::AliasTable,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_SKEWED,
+        n: 8,
+        expected: &[1, 1, 1, 1, 1, 1, 1, 1],
+    },
+    TestVector {
+        algorithm: Algorithm::PreparedChoice(Placement::Linear),
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 10,
+        expected: &[0, 0, 1, 1, 1, 2, 2, 2, 2, 4],
+    },
+    TestVector {
+        algorithm: Algorithm::PreparedChoice(Placement::Gallop),
+        seed: [42, 0, 0, 0],
+        weights: WEIGHTS_SKEWED,
+        n: 6,
+        expected: &[1, 1, 1, 1, 1, 1],
+    },
+    TestVector {
+        algorithm: Algorithm::PreparedChoice(Placement::BinarySearch),
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_LINEAR,
+        n: 12,
+        expected: &[3, 6, 9, 10, 12, 13, 14, 15, 16, 17, 18, 19],
+    },
+    TestVector {
+        algorithm: Algorithm::PreparedChoice(Placement::Auto),
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_LINEAR,
+        n: 30,
+        expected: &[3, 4, 6, 7, 8, 8, 9, 10, 10, 11, 12, 12, 13, 13, 14, 14, 14, 15, 15, 16, 16,
+                    17, 17, 17, 18, 18, 18, 19, 19, 19],
+    },
+    TestVector {
+        algorithm: Algorithm::ChunkedPrefixSums,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_LINEAR,
+        n: 12,
+        expected: &[3, 6, 9, 10, 12, 13, 14, 15, 16, 17, 18, 19],
+    },
+    TestVector {
+        algorithm: Algorithm::FenwickSampler,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 10,
+        expected: &[1, 1, 1, 0, 0, 0, 0, 4, 1, 2],
+    },
+    TestVector {
+        algorithm: Algorithm::FenwickSampler,
+        seed: [42, 0, 0, 0],
+        weights: WEIGHTS_SKEWED,
+        n: 8,
+        expected: &[1, 1, 1, 1, 1, 1, 1, 1],
+    },
+    TestVector {
+        algorithm: Algorithm::CumulativeWeights,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 10,
+        expected: &[0, 0, 1, 1, 1, 2, 2, 2, 2, 4],
+    },
+    TestVector {
+        algorithm: Algorithm::CumulativeWeights,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_LINEAR,
+        n: 12,
+        expected: &[3, 6, 9, 10, 12, 13, 14, 15, 16, 17, 18, 19],
+    },
+    TestVector {
+        algorithm: Algorithm::Approximate(8),
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_LINEAR,
+        n: 10,
+        expected: &[17, 8, 16, 15, 13, 8, 12, 14, 18, 1],
+    },
+    TestVector {
+        algorithm: Algorithm::Approximate(2),
+        seed: [42, 0, 0, 0],
+        weights: WEIGHTS_README,
+        n: 8,
+        expected: &[0, 2, 0, 2, 3, 1, 1, 1],
+    },
+    TestVector {
+        algorithm: Algorithm::WeightedReservoir,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_LINEAR,
+        n: 5,
+        expected: &[17, 6, 4, 7, 5],
+    },
+    TestVector {
+        algorithm: Algorithm::VarOptReservoir,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_LINEAR,
+        n: 6,
+        expected: &[7, 11, 13, 14, 15, 16],
+    },
+    TestVector {
+        algorithm: Algorithm::PrioritySketch,
+        seed: [42, 0, 0, 0],
+        weights: WEIGHTS_LINEAR,
+        n: 6,
+        expected: &[6, 19, 16, 18, 13, 17],
+    },
+    TestVector {
+        algorithm: Algorithm::ConditionalPoisson,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_PROBABILITIES,
+        n: 4,
+        expected: &[1, 2, 3, 5],
+    },
+    TestVector {
+        algorithm: Algorithm::Subset(SubsetWeighting::Product),
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_LINEAR,
+        n: 4,
+        expected: &[3, 5, 16, 19],
+    },
+    TestVector {
+        algorithm: Algorithm::Subset(SubsetWeighting::Sum),
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 3,
+        expected: &[0, 1, 2],
+    },
+    TestVector {
+        algorithm: Algorithm::AliasTable32,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 10,
+        expected: &[2, 0, 4, 2, 1, 0, 2, 2, 0, 1],
+    },
+    TestVector {
+        algorithm: Algorithm::AliasTable32,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_SKEWED,
+        n: 8,
+        expected: &[1, 1, 1, 1, 1, 1, 1, 1],
+    },
+    TestVector {
+        algorithm: Algorithm::SamplePerGroup,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_LINEAR,
+        n: 4,
+        expected: &[6, 12, 15, 18, 7, 13, 16, 19, 5, 11, 14, 17],
+    },
+    TestVector {
+        algorithm: Algorithm::TiesByIndex,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_TIES,
+        n: 10,
+        expected: &[0, 0, 2, 2, 5, 5, 5, 1, 4, 7],
+    },
+    TestVector {
+        algorithm: Algorithm::TiesRandom,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_TIES,
+        n: 10,
+        expected: &[0, 0, 5, 5, 2, 2, 4, 7, 1, 3],
+    },
+    TestVector {
+        algorithm: Algorithm::BetaBandit,
+        seed: [42, 0, 0, 0],
+        weights: WEIGHTS_README,
+        n: 8,
+        expected: &[2, 1, 0, 1, 1, 1, 0, 2],
+    },
+    TestVector {
+        algorithm: Algorithm::Exp3,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 8,
+        expected: &[2, 2, 2, 0, 0, 0, 0, 4],
+    },
+    TestVector {
+        algorithm: Algorithm::Categorical,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_README,
+        n: 10,
+        expected: &[0, 0, 1, 1, 1, 2, 2, 2, 2, 4],
+    },
+    TestVector {
+        algorithm: Algorithm::TemporalSampler,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_LINEAR,
+        n: 10,
+        expected: &[2, 5, 7, 9, 10, 12, 13, 14, 15, 16],
+    },
+    TestVector {
+        algorithm: Algorithm::MarkovSampler,
+        seed: [42, 0, 0, 0],
+        weights: WEIGHTS_README,
+        n: 8,
+        expected: &[0, 1, 3, 4, 2, 0, 4, 2, 1],
+    },
+    TestVector {
+        algorithm: Algorithm::CapacitySampler,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 8,
+        expected: &[1, 1, 2, 0, 0, 2, 3, 4],
+    },
+    TestVector {
+        algorithm: Algorithm::WeightedKfold,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_LINEAR,
+        n: 4,
+        expected: &[0, 3, 2, 1, 0, 1, 3, 2, 2, 3, 1, 0, 0, 1, 2, 3, 3, 2, 1, 0],
+    },
+    TestVector {
+        algorithm: Algorithm::ResizePopulation,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 10,
+        expected: &[0, 0, 1, 1, 1, 2, 2, 2, 2, 4],
+    },
+    TestVector {
+        algorithm: Algorithm::Capped,
+        seed: [42, 0, 0, 0],
+        weights: WEIGHTS_SKEWED,
+        n: 8,
+        expected: &[0, 1, 1, 2, 4, 4, 5, 5],
+    },
+    TestVector {
+        algorithm: Algorithm::SampleQuotas,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_LINEAR,
+        n: 2,
+        expected: &[11, 12, 13, 16, 17, 18],
+    },
+    TestVector {
+        algorithm: Algorithm::WeightedRandomWalk,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_README,
+        n: 8,
+        expected: &[0, 1, 0, 2, 0, 0, 0, 2],
+    },
+];
+
+/// The test vectors of `DrawPlan`, which needs the `wire` feature.
+#[cfg(feature = "wire")]
+pub const DRAW_PLAN_TEST_VECTORS: &[TestVector] = &[
+    TestVector {
+        algorithm: Algorithm::DrawPlan,
+        seed: [5000, 44, 55, 199],
+        weights: WEIGHTS_README,
+        n: 10,
+        expected: &[0, 0, 1, 1, 1, 2, 2, 2, 3, 4],
+    },
+    TestVector {
+        algorithm: Algorithm::DrawPlan,
+        seed: [1, 2, 3, 4],
+        weights: WEIGHTS_LINEAR,
+        n: 12,
+        expected: &[2, 6, 8, 10, 11, 13, 14, 15, 16, 17, 18, 19],
+    },
+];
+
+/// Checks all test vectors, with the `wire` feature also `DRAW_PLAN_TEST_VECTORS`.
+/// @return the first vector, which the current implementation doesn't reproduce.
+pub fn verify() -> Result<(), &'static TestVector> {
+    let vectors = TEST_VECTORS.iter();
+    #[cfg(feature = "wire")]
+    let vectors = vectors.chain(DRAW_PLAN_TEST_VECTORS);
+
+    match vectors.into_iter().find(|vector| !vector.check()) {
+        Some(vector) => Err(vector),
+        None => Ok(()),
+    }
+}
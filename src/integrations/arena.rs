@@ -0,0 +1,151 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Generational Arenas
+//! ECS-style code stores its populations in arenas instead of `Vec`s. With the `slotmap` and
+//! the `generational-arena` features, `slotmap::SlotMap` and `generational_arena::Arena` of
+//! `Weighted` values are `KeyedCollection`s: they are sampled in place and
+//! `RandomChoice::random_choice_keys_from` returns their keys instead of indices.
+//!
+//! The weights are visited in the iteration order of the arena and buffered in chunks of
+//! `CHUNK_SIZE`, so no `Vec` of all weights is allocated.
+//!
+//! ```rust
+//! # #[cfg(feature = "slotmap")]
+//! # fn main() {
+//! use random_choice::random_choice;
+//! use random_choice::core::collection::Weighted;
+//! use slotmap::SlotMap;
+//!
+//! struct Creature {
+//!     fitness: f64,
+//! }
+//!
+//! impl Weighted for Creature {
+//!     fn weight(&self) -> f64 {
+//!         self.fitness
+//!     }
+//! }
+//!
+//! let mut population = SlotMap::new();
+//! let strong = population.insert(Creature { fitness: 9.7 });
+//! let weak = population.insert(Creature { fitness: 0.0 });
+//!
+//! let parents = random_choice().random_choice_keys_from(&population, 2);
+//!
+//! assert_eq!(parents, vec![strong, strong]);
+//! assert!(!parents.contains(&weak));
+//! # }
+//! # #[cfg(not(feature = "slotmap"))]
+//! # fn main() {}
+//! ```
+
+use crate::core::collection::Weighted;
+
+/// The number of weights, which are buffered per chunk.
+pub const CHUNK_SIZE: usize = 256;
+
+/// Calls f with the weights of the values in chunks of `CHUNK_SIZE`.
+fn for_each_buffered_chunk<'a, V, I>(values: I, f: &mut dyn FnMut(&[f64]))
+    where V: Weighted + 'a,
+          I: Iterator<Item = &'a V>
+{
+    let mut buffer = [0.0; CHUNK_SIZE];
+    let mut len = 0;
+
+    for value in values {
+        buffer[len] = value.weight();
+        len += 1;
+
+        if len == CHUNK_SIZE {
+            f(&buffer);
+            len = 0;
+        }
+    }
+
+    if len > 0 {
+        f(&buffer[..len]);
+    }
+}
+
+/// Returns the keys of ascending indices, which may repeat, in one pass over the keys.
+fn keys_of_sorted<K: Clone, I>(keys: I, indices: &[usize]) -> Vec<K>
+    where I: Iterator<Item = K>
+{
+    let mut chosen: Vec<K> = Vec::with_capacity(indices.len());
+    let mut remaining = indices.iter().peekable();
+
+    for (i, key) in keys.enumerate() {
+        while remaining.next_if(|&&index| index == i).is_some() {
+            chosen.push(key.clone());
+        }
+
+        if remaining.peek().is_none() {
+            break;
+        }
+    }
+
+    chosen
+}
+
+#[cfg(feature = "slotmap")]
+mod slot_map {
+    use slotmap::{Key, SlotMap};
+
+    use super::{for_each_buffered_chunk, keys_of_sorted};
+    use crate::core::collection::{KeyedCollection, Weighted, WeightedCollection};
+
+    impl<K: Key, V: Weighted> WeightedCollection for SlotMap<K, V> {
+        fn len(&self) -> usize {
+            SlotMap::len(self)
+        }
+
+        /// Finds the value of the index in O(n).
+        fn weight(&self, i: usize) -> f64 {
+            self.values().nth(i).expect("index out of bounds of the slot map").weight()
+        }
+
+        fn for_each_chunk(&self, f: &mut dyn FnMut(&[f64])) {
+            for_each_buffered_chunk(self.values(), f)
+        }
+    }
+
+    impl<K: Key, V: Weighted> KeyedCollection for SlotMap<K, V> {
+        type Key = K;
+
+        fn keys_of(&self, indices: &[usize]) -> Vec<K> {
+            keys_of_sorted(self.keys(), indices)
+        }
+    }
+}
+
+#[cfg(feature = "generational-arena")]
+mod generational {
+    use generational_arena::{Arena, Index};
+
+    use super::{for_each_buffered_chunk, keys_of_sorted};
+    use crate::core::collection::{KeyedCollection, Weighted, WeightedCollection};
+
+    impl<V: Weighted> WeightedCollection for Arena<V> {
+        fn len(&self) -> usize {
+            Arena::len(self)
+        }
+
+        /// Finds the value of the index in O(n).
+        fn weight(&self, i: usize) -> f64 {
+            self.iter().nth(i).expect("index out of bounds of the arena").1.weight()
+        }
+
+        fn for_each_chunk(&self, f: &mut dyn FnMut(&[f64])) {
+            for_each_buffered_chunk(self.iter().map(|(_, value)| value), f)
+        }
+    }
+
+    impl<V: Weighted> KeyedCollection for Arena<V> {
+        type Key = Index;
+
+        fn keys_of(&self, indices: &[usize]) -> Vec<Index> {
+            keys_of_sorted(self.iter().map(|(index, _)| index), indices)
+        }
+    }
+}
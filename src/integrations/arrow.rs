@@ -0,0 +1,95 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Apache Arrow Integration
+//! With the `arrow` feature, weight columns stored as Arrow arrays are sampled in place,
+//! without copying them into a `Vec` first. The chosen indices are returned as an Arrow array.
+//!
+//! Null entries are treated as zero weights, so they are never chosen. So are negative, infinite
+//! and NaN entries. If no entry has a positive weight, nothing is chosen.
+//!
+//! ```rust
+//! extern crate arrow_array;
+//! extern crate random_choice;
+//! use arrow_array::Float64Array;
+//! use random_choice::random_choice;
+//!
+//! # fn main() {
+//! let scores = Float64Array::from(vec![Some(5.6), None, Some(9.7), Some(1.1), Some(2.0)]);
+//!
+//! let indices = random_choice().random_choice_arrow_f64(&scores, 100);
+//!
+//! assert_eq!(indices.len(), 100);
+//! assert!(indices.values().iter().all(|&i| i != 1));
+//! # }
+//! ```
+
+use arrow_array::{Array, Float64Array, UInt64Array};
+use rand::Rng;
+
+use crate::{sus_indices, RandomChoice};
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n indices of a weight column by their weights.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices. Null, negative and non-finite entries have the
+    ///        weight zero.
+    /// @param n Number of randomly chosen indices by weight.
+    /// @return randomly selected indices in ascending order
+    pub fn random_choice_arrow_f64(&mut self, weights: &Float64Array, n: usize) -> UInt64Array {
+        let values = weights.values();
+
+        if weights.null_count() == 0 {
+            choose_indices(&mut self.rng, values.len(), n, |i| values[i])
+        } else {
+            choose_indices(&mut self.rng,
+                           values.len(),
+                           n,
+                           |i| if weights.is_valid(i) { values[i] } else { 0.0 })
+        }
+    }
+
+    /// Chooses n indices of an integer weight column by their weights.
+    /// The weights are accumulated as f64, so sums beyond 2^53 are rounded.
+    ///
+    /// @param weights Weights of the indices. Null entries have the weight zero.
+    /// @param n Number of randomly chosen indices by weight.
+    /// @return randomly selected indices in ascending order
+    pub fn random_choice_arrow_u64(&mut self, weights: &UInt64Array, n: usize) -> UInt64Array {
+        let values = weights.values();
+
+        if weights.null_count() == 0 {
+            choose_indices(&mut self.rng, values.len(), n, |i| values[i] as f64)
+        } else {
+            choose_indices(&mut self.rng,
+                           values.len(),
+                           n,
+                           |i| if weights.is_valid(i) { values[i] as f64 } else { 0.0 })
+        }
+    }
+}
+
+fn choose_indices<RNG, F>(rng: &mut RNG, len: usize, n: usize, weight: F) -> UInt64Array
+    where RNG: Rng,
+          F: Fn(usize) -> f64
+{
+    // invalid entries are skipped like nulls, a NaN would spoil the sum of all weights
+    let weight = |i| {
+        let w = weight(i);
+        if w.is_finite() && w > 0.0 { w } else { 0.0 }
+    };
+    let sum: f64 = (0..len).fold(0.0, |acc, i| acc + weight(i));
+
+    // all entries are null or zero, so every spoke would land on a zero weight,
+    // and an overflowing sum has no spoke gap
+    if sum <= 0.0 || !sum.is_finite() {
+        return UInt64Array::from(Vec::<u64>::new());
+    }
+
+    // next_f64() ∈ [0.0, 1.0)
+    let spin = rng.next_f64();
+    let indices = sus_indices(len, n, spin, sum, weight);
+
+    UInt64Array::from_iter_values(indices.into_iter().map(|i| i as u64))
+}
@@ -0,0 +1,139 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Bevy Integration
+//! With the `bevy` feature, a system samples entities by a weight component with the system
+//! parameter `WeightedQuerySampler`. The entities and the prefix sums of their weights are
+//! cached between the runs of the system. The cache remembers the change tick of its rebuild
+//! and is only rebuilt, if a weight component was added or changed since then or the number of
+//! weight components differs, e.g. after a despawn. This holds, even if the system didn't sample
+//! in the runs in between. Checking the ticks is a scan without allocations once per run, so
+//! systems, which draw every frame from a stable population, don't rebuild the prefix sums.
+//!
+//! The weight component implements `collection::Weighted`.
+//!
+//! ```rust
+//! use bevy_ecs::prelude::*;
+//! use random_choice::integrations::bevy::WeightedQuerySampler;
+//! use random_choice::core::collection::Weighted;
+//!
+//! #[derive(Component)]
+//! struct Aggro(f64);
+//!
+//! impl Weighted for Aggro {
+//!     fn weight(&self) -> f64 {
+//!         self.0
+//!     }
+//! }
+//!
+//! #[derive(Resource, Default)]
+//! struct Target(Option<Entity>);
+//!
+//! fn pick_target(mut sampler: WeightedQuerySampler<Aggro>, mut target: ResMut<Target>) {
+//!     target.0 = sampler.sample(&mut rand::thread_rng());
+//! }
+//!
+//! # fn main() {
+//! let mut world = World::new();
+//! world.init_resource::<Target>();
+//! let tank = world.spawn(Aggro(9.7)).id();
+//! world.spawn(Aggro(0.0));
+//!
+//! let mut schedule = Schedule::default();
+//! schedule.add_systems(pick_target);
+//! schedule.run(&mut world);
+//!
+//! assert_eq!(world.resource::<Target>().0, Some(tank));
+//! # }
+//! ```
+
+use bevy_ecs::change_detection::{DetectChanges, Ref};
+use bevy_ecs::component::{Component, Tick};
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::{Local, Query, SystemChangeTick, SystemParam};
+use rand::Rng;
+
+use crate::core::collection::Weighted;
+use crate::prepared::PreparedChoice;
+
+/// The entities and the prefix sums of their weights from the last rebuild.
+#[derive(Debug, Default)]
+pub struct SamplerCache {
+    entities: Vec<Entity>,
+    prepared: Option<PreparedChoice>,
+    /// The tick of the system run, which rebuilt the cache.
+    built: Option<Tick>,
+    /// The tick of the system run, which last checked the cache.
+    checked: Option<Tick>,
+    rebuilds: usize,
+}
+
+/// A system parameter, which samples the entities with the component C by its weight.
+#[derive(SystemParam)]
+pub struct WeightedQuerySampler<'w, 's, C: Component + Weighted> {
+    weights: Query<'w, 's, (Entity, Ref<'static, C>)>,
+    ticks: SystemChangeTick,
+    cache: Local<'s, SamplerCache>,
+}
+
+impl<'w, 's, C: Component + Weighted> WeightedQuerySampler<'w, 's, C> {
+    /// Chooses one entity by the weight of its component.
+    /// @return None, if no entity has a positive weight.
+    pub fn sample<R: Rng>(&mut self, rng: &mut R) -> Option<Entity> {
+        self.sample_n(rng, 1).pop()
+    }
+
+    /// Chooses n entities by the weights of their components with stochastic universal sampling.
+    /// @return n entities in the order of the cache or nothing, if no entity has a positive weight.
+    pub fn sample_n<R: Rng>(&mut self, rng: &mut R, n: usize) -> Vec<Entity> {
+        self.refresh();
+
+        match self.cache.prepared {
+            Some(ref prepared) if prepared.total_weight() > 0.0 => {
+                prepared.sample_n(rng, n).into_iter().map(|i| self.cache.entities[i]).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Returns the number of times the cache has been rebuilt by this system.
+    pub fn rebuilds(&self) -> usize {
+        self.cache.rebuilds
+    }
+
+    /// Rebuilds the cache, if a weight component was added or changed since the last rebuild
+    /// or the number of weight components differs. It is checked once per system run.
+    fn refresh(&mut self) {
+        let this_run = self.ticks.this_run();
+
+        if self.cache.checked == Some(this_run) {
+            return;
+        }
+
+        self.cache.checked = Some(this_run);
+
+        if let Some(built) = self.cache.built {
+            let mut len = 0;
+            let mut changed = false;
+
+            for (_, component) in self.weights.iter() {
+                len += 1;
+                changed |= component.last_changed().is_newer_than(built, this_run);
+            }
+
+            if !changed && len == self.cache.entities.len() {
+                return;
+            }
+        }
+
+        let (entities, weights): (Vec<Entity>, Vec<f64>) = self.weights
+            .iter()
+            .map(|(entity, component)| (entity, component.weight()))
+            .unzip();
+
+        self.cache.entities = entities;
+        self.cache.prepared = Some(PreparedChoice::new(&weights));
+        self.cache.built = Some(this_run);
+        self.cache.rebuilds += 1;
+    }
+}
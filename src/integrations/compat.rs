@@ -0,0 +1,199 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Migrating from rand's WeightedIndex
+//! With the `rand-compat` feature, `compat::WeightedIndex` behaves like
+//! `rand::distr::weighted::WeightedIndex<f64>` of rand 0.10: it is built and updated with the
+//! same arguments, fails with the same errors and implements rand's `Distribution<usize>`.
+//! Replacing the import is enough to measure this crate in an existing code base.
+//! Internally, it draws from an `AliasTable` in O(1) instead of a binary search over the
+//! cumulative weights.
+//!
+//! Existing distributions convert into a `WeightedIndex`, an `AliasTable` or a
+//! `PreparedChoice` with `From`.
+//!
+//! ```rust
+//! extern crate rand_compat as rand;
+//! extern crate random_choice;
+//! use rand::distr::Distribution;
+//! use rand::rngs::SmallRng;
+//! use rand::SeedableRng;
+//! use random_choice::integrations::compat::WeightedIndex;
+//!
+//! # fn main() {
+//! let samples = vec!["hi", "this", "is", "a", "test!"];
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! let dist = WeightedIndex::new(&weights).unwrap();
+//! let mut rng = SmallRng::seed_from_u64(5000);
+//!
+//! for _ in 0..100 {
+//!     print!("{}, ", samples[dist.sample(&mut rng)]);
+//! }
+//! # }
+//! ```
+
+use std::borrow::Borrow;
+use std::iter::Copied;
+use std::slice;
+
+use rand_compat::distr::weighted::WeightedIndex as RandWeightedIndex;
+use rand_compat::distr::Distribution;
+use rand_compat::{Rng, RngExt};
+
+use crate::prepared::alias::AliasTable;
+use crate::prepared::PreparedChoice;
+
+/// The errors of rand's `WeightedIndex`, which are returned unchanged.
+pub use rand_compat::distr::weighted::Error as WeightError;
+
+/// A drop-in replacement of rand's `WeightedIndex<f64>`, which samples from an alias table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightedIndex {
+    weights: Vec<f64>,
+    total_weight: f64,
+    table: AliasTable,
+}
+
+impl WeightedIndex {
+    /// Builds the distribution in O(n).
+    ///
+    /// @return `InvalidInput`, if there are no weights, `InvalidWeight`, if a weight is
+    ///         negative or NaN, `InsufficientNonZero`, if all weights are zero, and `Overflow`,
+    ///         if the sum of the weights is infinite.
+    pub fn new<I>(weights: I) -> Result<Self, WeightError>
+        where I: IntoIterator,
+              I::Item: Borrow<f64>
+    {
+        let weights: Vec<f64> = weights.into_iter().map(|w| *w.borrow()).collect();
+
+        if weights.is_empty() {
+            return Err(WeightError::InvalidInput);
+        }
+
+        if weights.iter().any(|&w| w.is_nan() || w < 0.0) {
+            return Err(WeightError::InvalidWeight);
+        }
+
+        let total_weight = checked_total(&weights)?;
+
+        Ok(WeightedIndex {
+            table: AliasTable::new(&weights),
+            weights,
+            total_weight,
+        })
+    }
+
+    /// Replaces some of the weights and rebuilds the distribution in O(n).
+    /// If an error is returned, the distribution is not modified.
+    ///
+    /// @param new_weights pairs of an index and its new weight, ordered by the index.
+    /// @return `InvalidInput`, if the indices are not strictly ascending or out of bounds.
+    ///         Otherwise the same errors as `new`.
+    pub fn update_weights(&mut self, new_weights: &[(usize, &f64)]) -> Result<(), WeightError> {
+        if new_weights.is_empty() {
+            return Ok(());
+        }
+
+        let mut previous: Option<usize> = None;
+
+        for &(i, &w) in new_weights {
+            if previous.is_some_and(|p| p >= i) || i >= self.weights.len() {
+                return Err(WeightError::InvalidInput);
+            }
+
+            if w.is_nan() || w < 0.0 {
+                return Err(WeightError::InvalidWeight);
+            }
+
+            previous = Some(i);
+        }
+
+        let mut weights = self.weights.clone();
+
+        for &(i, &w) in new_weights {
+            weights[i] = w;
+        }
+
+        self.total_weight = checked_total(&weights)?;
+        self.table = AliasTable::new(&weights);
+        self.weights = weights;
+
+        Ok(())
+    }
+
+    /// Returns the weight of an index or None, if the index is out of bounds.
+    pub fn weight(&self, index: usize) -> Option<f64> {
+        self.weights.get(index).cloned()
+    }
+
+    /// Returns the current weights of the distribution.
+    pub fn weights(&self) -> Copied<slice::Iter<'_, f64>> {
+        self.weights.iter().copied()
+    }
+
+    /// Returns the sum of all weights.
+    pub fn total_weight(&self) -> f64 {
+        self.total_weight
+    }
+
+    /// Returns the alias table, which the indices are drawn from.
+    pub fn alias_table(&self) -> &AliasTable {
+        &self.table
+    }
+}
+
+impl Distribution<usize> for WeightedIndex {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let slot = rng.random_range(0..self.table.len());
+
+        if rng.random::<f64>() < self.table.probabilities()[slot] {
+            slot
+        } else {
+            self.table.aliases()[slot]
+        }
+    }
+}
+
+impl From<RandWeightedIndex<f64>> for WeightedIndex {
+    fn from(dist: RandWeightedIndex<f64>) -> Self {
+        WeightedIndex::from(&dist)
+    }
+}
+
+impl From<&RandWeightedIndex<f64>> for WeightedIndex {
+    fn from(dist: &RandWeightedIndex<f64>) -> Self {
+        let weights: Vec<f64> = dist.weights().collect();
+
+        WeightedIndex {
+            table: AliasTable::new(&weights),
+            weights,
+            total_weight: dist.total_weight(),
+        }
+    }
+}
+
+impl From<&RandWeightedIndex<f64>> for AliasTable {
+    fn from(dist: &RandWeightedIndex<f64>) -> Self {
+        AliasTable::new(&dist.weights().collect::<Vec<f64>>())
+    }
+}
+
+impl From<&RandWeightedIndex<f64>> for PreparedChoice {
+    fn from(dist: &RandWeightedIndex<f64>) -> Self {
+        PreparedChoice::new(&dist.weights().collect::<Vec<f64>>())
+    }
+}
+
+/// Sums up valid weights like rand's `WeightedIndex`.
+fn checked_total(weights: &[f64]) -> Result<f64, WeightError> {
+    let total_weight = weights.iter().fold(0.0, |acc, &w| acc + w);
+
+    if total_weight == 0.0 {
+        Err(WeightError::InsufficientNonZero)
+    } else if total_weight.is_infinite() {
+        Err(WeightError::Overflow)
+    } else {
+        Ok(total_weight)
+    }
+}
@@ -0,0 +1,132 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Configuration Files
+//! With the `config` feature, a `Categorical` is loaded from a TOML or a YAML file, whose top
+//! level maps every label to its weight, e.g. a traffic split or a loot table:
+//!
+//! ```toml
+//! control = 90
+//! variant = 10
+//! ```
+//!
+//! The file is validated like `Categorical::new`: the weights must be finite and non-negative
+//! and the labels unique. The labels keep the order of the file. A service, which reloads the
+//! file, only replaces its sampler, if the new file is valid, e.g. by swapping an
+//! `Arc<Categorical>` behind a lock, so that every draw sees either the old or the new
+//! distribution.
+//!
+//! ```rust
+//! # #[cfg(feature = "config")]
+//! # fn main() {
+//! use random_choice::strategies::categorical::Categorical;
+//!
+//! let split = Categorical::from_toml_str("control = 90\nvariant = 10").unwrap();
+//! assert_eq!(split.probability("variant"), Some(0.1));
+//!
+//! let loot = Categorical::from_yaml_str("sword: 1\nshield: 2\ngold: 7.5").unwrap();
+//! assert_eq!(loot.labels(), ["sword", "shield", "gold"]);
+//!
+//! assert!(Categorical::from_yaml_str("sword: -1").is_err());
+//! # }
+//! # #[cfg(not(feature = "config"))]
+//! # fn main() {}
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+
+use crate::strategies::categorical::{Categorical, CategoricalError};
+
+/// Errors of loading a `Categorical` from a configuration file.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file can't be read.
+    Io(io::Error),
+    /// The file isn't a TOML table of labels and weights.
+    Toml(toml::de::Error),
+    /// The file isn't a YAML mapping of labels and weights.
+    Yaml(serde_yaml::Error),
+    /// A label is duplicate or has an invalid weight.
+    Invalid(CategoricalError),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConfigError::Io(ref error) => write!(f, "can't read configuration: {}", error),
+            ConfigError::Toml(ref error) => write!(f, "invalid TOML: {}", error),
+            ConfigError::Yaml(ref error) => write!(f, "invalid YAML: {}", error),
+            ConfigError::Invalid(ref error) => write!(f, "invalid distribution: {}", error),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match *self {
+            ConfigError::Io(ref error) => Some(error),
+            ConfigError::Toml(ref error) => Some(error),
+            ConfigError::Yaml(ref error) => Some(error),
+            ConfigError::Invalid(ref error) => Some(error),
+        }
+    }
+}
+
+/// The labels and weights of a mapping in the order of the document.
+struct Pairs(Vec<(String, f64)>);
+
+impl<'de> Deserialize<'de> for Pairs {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PairsVisitor;
+
+        impl<'de> Visitor<'de> for PairsVisitor {
+            type Value = Pairs;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a mapping of labels to weights")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Pairs, A::Error> {
+                let mut pairs = Vec::with_capacity(map.size_hint().unwrap_or(0));
+
+                while let Some(pair) = map.next_entry::<String, f64>()? {
+                    pairs.push(pair);
+                }
+
+                Ok(Pairs(pairs))
+            }
+        }
+
+        deserializer.deserialize_map(PairsVisitor)
+    }
+}
+
+impl Categorical {
+    /// Loads the distribution from a TOML file, see the module documentation.
+    pub fn from_toml<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        Categorical::from_toml_str(&fs::read_to_string(path).map_err(ConfigError::Io)?)
+    }
+
+    /// Parses the distribution from a TOML document.
+    pub fn from_toml_str(document: &str) -> Result<Self, ConfigError> {
+        let Pairs(pairs) = toml::from_str(document).map_err(ConfigError::Toml)?;
+        Categorical::new(&pairs).map_err(ConfigError::Invalid)
+    }
+
+    /// Loads the distribution from a YAML file, see the module documentation.
+    pub fn from_yaml<P: AsRef<Path>>(path: P) -> Result<Self, ConfigError> {
+        Categorical::from_yaml_str(&fs::read_to_string(path).map_err(ConfigError::Io)?)
+    }
+
+    /// Parses the distribution from a YAML document.
+    pub fn from_yaml_str(document: &str) -> Result<Self, ConfigError> {
+        let Pairs(pairs) = serde_yaml::from_str(document).map_err(ConfigError::Yaml)?;
+        Categorical::new(&pairs).map_err(ConfigError::Invalid)
+    }
+}
@@ -0,0 +1,36 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Integrations
+//! Every integration with another crate is behind its own feature, so that the core sampler
+//! only depends on `rand`:
+//!
+//! | Module   | Feature                            | Crate                                |
+//! |----------|------------------------------------|--------------------------------------|
+//! | `arena`  | `slotmap`, `generational-arena`    | `slotmap`, `generational-arena`      |
+//! | `arrow`  | `arrow`                            | `arrow-array`                        |
+//! | `bevy`   | `bevy`                             | `bevy_ecs`                           |
+//! | `compat` | `rand-compat`                      | `rand` 0.10                          |
+//! | `config` | `config`                           | `serde`, `toml`, `serde_yaml`        |
+//! | `polars` | `polars`                           | `polars-core`, `-plan`, `-lazy`      |
+//! | `source` | `async`                            | `futures`                            |
+//! | `wire`   | `wire`                             | `serde`, `postcard`                  |
+//!
+//! The modules are also available at the crate root, e.g. `random_choice::integrations::arrow`.
+
+#[cfg(any(feature = "slotmap", feature = "generational-arena"))]
+pub mod arena;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "bevy")]
+pub mod bevy;
+#[cfg(feature = "rand-compat")]
+pub mod compat;
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "polars")]
+pub mod polars;
+#[cfg(feature = "async")]
+pub mod source;
+#[cfg(feature = "wire")]
+pub mod wire;
@@ -0,0 +1,150 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Polars Integration
+//! With the `polars` feature, the rows of a `DataFrame` are sampled by the values of a weight
+//! column, without extracting the weights manually.
+//!
+//! With replacement, the rows are chosen by stochastic universal sampling.
+//! Without replacement, they are chosen by the algorithm of Efraimidis and Spirakis.
+//! Null weights count as zero, so these rows are never chosen.
+//!
+//! In lazy queries, `sample_weighted_expr` is an expression, which evaluates to the chosen row
+//! indices of a weight column. Other columns are sampled by gathering these indices.
+//!
+//! ```rust
+//! #[macro_use]
+//! extern crate polars_core;
+//! extern crate polars_lazy;
+//! extern crate random_choice;
+//! use polars_lazy::prelude::*;
+//! use random_choice::integrations::polars::{sample_weighted, sample_weighted_expr};
+//!
+//! # fn main() {
+//! let df = df!("name" => &["hi", "this", "is", "a", "test!"],
+//!              "weight" => &[5.6, 7.8, 9.7, 1.1, 2.0]).unwrap();
+//!
+//! let sample = sample_weighted(&df, "weight", 100, true).unwrap();
+//!
+//! assert_eq!(sample.height(), 100);
+//!
+//! let indices = sample_weighted_expr(col("weight"), 3, false, Some(42));
+//! let sample = df.lazy().select([col("name").gather(indices, false)]).collect().unwrap();
+//!
+//! assert_eq!(sample.height(), 3);
+//! # }
+//! ```
+
+use polars_core::prelude::*;
+use polars_plan::dsl::Expr;
+use rand::{ChaChaRng, Rng, SeedableRng};
+
+use crate::{random_choice, RandomChoice};
+
+/// Samples n rows of a data frame by the values of its weight column with the ThreadRng.
+/// See `RandomChoice::sample_weighted`.
+pub fn sample_weighted(df: &DataFrame,
+                       weight_col: &str,
+                       n: usize,
+                       replace: bool)
+                       -> PolarsResult<DataFrame> {
+    random_choice().sample_weighted(df, weight_col, n, replace)
+}
+
+/// Creates an expression, which chooses n row indices by the values of a weight expression.
+/// It is evaluated on the whole weight column like `RandomChoice::sample_weighted_indices`.
+///
+/// @param weights an expression of a numeric column. Null weights count as zero.
+/// @param n Number of randomly chosen indices.
+/// @param replace Whether an index can be chosen more than once.
+/// @param seed the seed of a `ChaChaRng`, which draws the same indices on every evaluation.
+///        Without a seed, every evaluation draws with the ThreadRng.
+/// @return an expression of the chosen indices, which has the name of the weights.
+pub fn sample_weighted_expr(weights: Expr, n: usize, replace: bool, seed: Option<u64>) -> Expr {
+    weights.apply(move |column: Column| {
+                      let weights = column.as_materialized_series();
+                      let indices = match seed {
+                          Some(seed) => {
+                              let seed = [seed as u32, (seed >> 32) as u32];
+                              RandomChoice::new(ChaChaRng::from_seed(&seed))
+                                  .sample_weighted_indices(weights, n, replace)?
+                          }
+                          None => random_choice().sample_weighted_indices(weights, n, replace)?,
+                      };
+
+                      Ok(indices.with_name(column.name().clone()).into_column())
+                  },
+                  |_: &Schema, field: &Field| Ok(Field::new(field.name().clone(), IDX_DTYPE)))
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Samples n rows of a data frame by the values of its weight column.
+    ///
+    /// @param df the data frame to sample from
+    /// @param weight_col the name of the numeric column with the weights. Null weights count as zero.
+    /// @param n Number of randomly chosen rows.
+    /// @param replace Whether a row can be chosen more than once.
+    /// @return the chosen rows. With replacement in the order of the data frame,
+    ///         without replacement in the order they were drawn.
+    pub fn sample_weighted(&mut self,
+                           df: &DataFrame,
+                           weight_col: &str,
+                           n: usize,
+                           replace: bool)
+                           -> PolarsResult<DataFrame> {
+        let weights = df.column(weight_col)?.as_materialized_series();
+        let indices = self.sample_weighted_indices(weights, n, replace)?;
+
+        df.take(&indices)
+    }
+
+    /// Chooses n row indices by the values of a weight series.
+    ///
+    /// @param weights a numeric series. Null weights count as zero.
+    /// @param n Number of randomly chosen indices.
+    /// @param replace Whether an index can be chosen more than once.
+    /// @return an error, if a weight is negative, infinite or NaN, if all weights are zero or
+    ///         if there are less than n positive weights to sample without replacement.
+    pub fn sample_weighted_indices(&mut self,
+                                   weights: &Series,
+                                   n: usize,
+                                   replace: bool)
+                                   -> PolarsResult<IdxCa> {
+        let weights = weights.cast(&DataType::Float64)?;
+        let weights = weights.f64()?;
+
+        // a single chunk without nulls can be sampled without copying
+        let indices = match weights.cont_slice() {
+            Ok(values) => self.choose_rows(values, n, replace)?,
+            Err(_) => {
+                let values: Vec<f64> = weights.iter().map(|w| w.unwrap_or(0.0)).collect();
+                self.choose_rows(&values, n, replace)?
+            }
+        };
+
+        Ok(IdxCa::from_vec(PlSmallStr::EMPTY,
+                           indices.into_iter().map(|i| i as IdxSize).collect()))
+    }
+
+    fn choose_rows(&mut self, weights: &[f64], n: usize, replace: bool) -> PolarsResult<Vec<usize>> {
+        polars_ensure!(weights.iter().all(|&w| w >= 0.0 && w.is_finite()),
+                       ComputeError: "weights must not be negative, infinite or NaN");
+
+        let positive = weights.iter().filter(|&&w| w > 0.0).count();
+
+        if n == 0 {
+            return Ok(Vec::new());
+        }
+
+        if replace {
+            polars_ensure!(positive > 0, ComputeError: "cannot sample by weights, which are all zero");
+            Ok(self.random_choice_indices_f64(weights, n))
+        } else {
+            polars_ensure!(n <= positive,
+                           ShapeMismatch: "cannot take a larger sample than the {} rows with a \
+                                           positive weight when `replace=false`",
+                           positive);
+            Ok(self.random_choice_indices_without_replacement_f64(weights, n))
+        }
+    }
+}
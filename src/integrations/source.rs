@@ -0,0 +1,229 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # External Weight Sources
+//! Weights, which live in a key-value store or a database, change while they are sampled and
+//! are too many to be fetched for every draw. A `TwoPhaseSampler` pages through the keys of a
+//! `WeightSource` once and builds an alias table over an upper bound of every weight, e.g. a
+//! cached value plus a margin or a cap of the application. Only the keys and their bounds are
+//! kept locally.
+//!
+//! A draw chooses a candidate key from this index and re-checks its current weight in the
+//! source: the candidate is accepted with the probability weight / bound, otherwise the draw is
+//! repeated. Accepted keys are therefore chosen exactly by their current weights, as long as no
+//! weight exceeds its bound. The expected number of lookups per draw is the sum of the bounds
+//! divided by the sum of the current weights.
+//!
+//! Implementations can use `async fn` for the methods of `WeightSource`.
+//!
+//! ```rust
+//! extern crate futures;
+//! extern crate rand;
+//! extern crate random_choice;
+//! use std::collections::HashMap;
+//! use futures::executor::block_on;
+//! use random_choice::integrations::source::{Page, TwoPhaseSampler, WeightSource};
+//!
+//! struct Store {
+//!     weights: HashMap<&'static str, f64>,
+//! }
+//!
+//! impl WeightSource for Store {
+//!     type Key = &'static str;
+//!     type Cursor = ();
+//!
+//!     async fn weight(&self, key: &&'static str) -> f64 {
+//!         self.weights[key]
+//!     }
+//!
+//!     async fn keys_page(&self, _cursor: Option<()>) -> Page<&'static str, ()> {
+//!         // every weight may grow by 10 % until the next rebuild
+//!         let entries = self.weights.iter().map(|(&key, &w)| (key, w * 1.1)).collect();
+//!         Page { entries, next: None }
+//!     }
+//! }
+//!
+//! # fn main() {
+//! let weights = vec![("hi", 5.6), ("this", 7.8), ("is", 9.7), ("a", 1.1), ("test!", 2.0)];
+//! let store = Store { weights: weights.into_iter().collect() };
+//!
+//! let sampler = block_on(TwoPhaseSampler::build(store));
+//! let mut rng = rand::thread_rng();
+//!
+//! for key in block_on(sampler.sample_n(&mut rng, 100)) {
+//!     print!("{}, ", key);
+//! }
+//! # }
+//! ```
+
+use std::future::Future;
+
+use rand::Rng;
+
+use crate::prepared::alias::AliasTable;
+
+/// The default number of candidates, which are re-checked by a single draw.
+pub const DEFAULT_MAX_ATTEMPTS: usize = 64;
+
+/// One page of keys of a `WeightSource`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page<K, C> {
+    /// The keys of this page with an upper bound of their weights.
+    pub entries: Vec<(K, f64)>,
+    /// The cursor of the next page or None, if this is the last page.
+    pub next: Option<C>,
+}
+
+/// Weights, which are stored outside of the process and are looked up asynchronously.
+pub trait WeightSource {
+    /// Identifies a weight in the source.
+    type Key: Clone;
+    /// Identifies a page of keys, e.g. the cursor of a Redis `SCAN` or an offset of a query.
+    type Cursor;
+
+    /// Looks up the current weight of a key.
+    /// @return the weight or zero, if the key no longer exists.
+    fn weight(&self, key: &Self::Key) -> impl Future<Output = f64>;
+
+    /// Lists the keys of the source page by page.
+    /// @param cursor None for the first page, otherwise the cursor of the previous page.
+    fn keys_page(&self,
+                 cursor: Option<Self::Cursor>)
+                 -> impl Future<Output = Page<Self::Key, Self::Cursor>>;
+}
+
+/// Chooses keys of a `WeightSource` by an index over upper bounds of their weights and
+/// re-checks every candidate against its current weight.
+#[derive(Debug)]
+pub struct TwoPhaseSampler<S: WeightSource> {
+    source: S,
+    keys: Vec<S::Key>,
+    bounds: Vec<f64>,
+    index: AliasTable,
+    total_bound: f64,
+    max_attempts: usize,
+}
+
+impl<S: WeightSource> TwoPhaseSampler<S> {
+    /// Pages through all keys of the source and builds the index in O(n).
+    ///
+    /// @invariant bounds must not be negative and their sum must not overflow.
+    pub async fn build(source: S) -> Self {
+        let mut sampler = TwoPhaseSampler {
+            source,
+            keys: Vec::new(),
+            bounds: Vec::new(),
+            index: AliasTable::new(&[]),
+            total_bound: 0.0,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        };
+
+        sampler.rebuild().await;
+        sampler
+    }
+
+    /// Limits the number of candidates, which are re-checked by a single draw.
+    /// @param max_attempts It must be greater than zero.
+    pub fn with_max_attempts(mut self, max_attempts: usize) -> Self {
+        assert!(max_attempts > 0, "max attempts must be greater than zero");
+
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Pages through the keys of the source again, e.g. after keys were added or bounds
+    /// were exceeded.
+    pub async fn rebuild(&mut self) {
+        self.keys.clear();
+        self.bounds.clear();
+
+        let mut cursor = None;
+
+        loop {
+            let page = self.source.keys_page(cursor).await;
+
+            for (key, bound) in page.entries {
+                self.keys.push(key);
+                self.bounds.push(bound);
+            }
+
+            match page.next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        self.index = AliasTable::new(&self.bounds);
+        self.total_bound = self.bounds.iter().fold(0.0, |acc, &b| acc + b);
+    }
+
+    /// Returns the source of the weights.
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+
+    /// Returns the number of keys in the index.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns true, if the source had no keys.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the keys of the index.
+    pub fn keys(&self) -> &[S::Key] {
+        &self.keys
+    }
+
+    /// Returns the upper bounds of the weights of the keys.
+    pub fn bounds(&self) -> &[f64] {
+        &self.bounds
+    }
+
+    /// Returns the maximum number of candidates, which are re-checked by a single draw.
+    pub fn max_attempts(&self) -> usize {
+        self.max_attempts
+    }
+
+    /// Chooses one key by its current weight.
+    /// @return None, if no bound is positive or all candidates of the draw were rejected.
+    pub async fn sample<R: Rng>(&self, rng: &mut R) -> Option<S::Key> {
+        if self.total_bound <= 0.0 {
+            return None;
+        }
+
+        for _ in 0..self.max_attempts {
+            let candidate = self.index.sample(rng)?;
+            let bound = self.bounds[candidate];
+
+            if bound <= 0.0 {
+                continue;
+            }
+
+            let weight = self.source.weight(&self.keys[candidate]).await;
+
+            // next_f64() ∈ [0.0, 1.0), so a weight of zero is never accepted
+            if rng.next_f64() * bound < weight {
+                return Some(self.keys[candidate].clone());
+            }
+        }
+
+        None
+    }
+
+    /// Chooses n keys independently by their current weights.
+    /// @return less than n keys, if draws were rejected. See `sample`.
+    pub async fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<S::Key> {
+        let mut keys = Vec::with_capacity(n);
+
+        for _ in 0..n {
+            if let Some(key) = self.sample(rng).await {
+                keys.push(key);
+            }
+        }
+
+        keys
+    }
+}
@@ -0,0 +1,185 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Distributed Draws
+//! With the `wire` feature, a coordinator prepares the weights once, wraps them with a seed and
+//! the size of the whole draw into a `DrawPlan` and sends its compact `postcard` encoding to the
+//! workers. Every worker decodes the plan and chooses a disjoint portion of the spokes: the
+//! portions of all workers are exactly the indices of the whole draw, no matter how many workers
+//! there are, and every portion is reproducible from the plan alone.
+//!
+//! The spin of the wheel is the first draw of the counter-based generator `CounterRng` with the
+//! seed, so no random state is shared. The encoding starts with `FORMAT_VERSION`, which only
+//! changes with a new major version of this crate.
+//!
+//! ```rust
+//! # #[cfg(feature = "wire")]
+//! # fn main() {
+//! use random_choice::prepared::PreparedChoice;
+//! use random_choice::integrations::wire::DrawPlan;
+//!
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//! let plan = DrawPlan::new(PreparedChoice::new(&weights), 5000, 1_000_000);
+//! let bytes = plan.to_bytes().unwrap();
+//!
+//! // on every worker
+//! let plan = DrawPlan::from_bytes(&bytes).unwrap();
+//! let portion = plan.sample_portion(2, 8);
+//!
+//! assert_eq!(portion.len(), 125_000);
+//! # }
+//! # #[cfg(not(feature = "wire"))]
+//! # fn main() {}
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::ops::Range;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::core::counter::CounterRng;
+use crate::prepared::PreparedChoice;
+
+/// The version of the encoding of a `DrawPlan`.
+pub const FORMAT_VERSION: u16 = 1;
+
+/// Errors of the encoding and the decoding of a `DrawPlan`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WireError {
+    /// The bytes aren't a valid encoding.
+    Encoding(postcard::Error),
+    /// The plan was encoded with another `FORMAT_VERSION`.
+    UnsupportedVersion(u16),
+}
+
+impl fmt::Display for WireError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            WireError::Encoding(ref error) => write!(f, "invalid encoding: {}", error),
+            WireError::UnsupportedVersion(version) => {
+                write!(f,
+                       "format version {} is not supported, expected {}",
+                       version,
+                       FORMAT_VERSION)
+            }
+        }
+    }
+}
+
+impl Error for WireError {}
+
+impl From<postcard::Error> for WireError {
+    fn from(error: postcard::Error) -> Self {
+        WireError::Encoding(error)
+    }
+}
+
+/// A prepared sampler with the seed and the size of a draw, which is split among workers.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DrawPlan {
+    version: u16,
+    seed: u64,
+    n: u64,
+    prepared: PreparedChoice,
+}
+
+impl DrawPlan {
+    /// @param prepared the prefix sums of the weights.
+    /// @param seed the seed of the spin of the wheel.
+    /// @param n the number of indices of the whole draw.
+    pub fn new(mut prepared: PreparedChoice, seed: u64, n: u64) -> Self {
+        // the workers place the spokes on the prefix sums
+        prepared.flush();
+
+        DrawPlan {
+            version: FORMAT_VERSION,
+            seed,
+            n,
+            prepared,
+        }
+    }
+
+    /// Encodes the plan with `postcard`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, WireError> {
+        Ok(postcard::to_allocvec(self)?)
+    }
+
+    /// Decodes a plan, which was encoded by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, WireError> {
+        // the version is decoded first, so that a newer layout isn't misread
+        let version: u16 = postcard::take_from_bytes(bytes)?.0;
+
+        if version != FORMAT_VERSION {
+            return Err(WireError::UnsupportedVersion(version));
+        }
+
+        Ok(postcard::from_bytes(bytes)?)
+    }
+
+    /// Returns the seed.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the number of indices of the whole draw.
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Returns true, if the whole draw has no indices.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Returns the prefix sums of the weights.
+    pub fn prepared(&self) -> &PreparedChoice {
+        &self.prepared
+    }
+
+    /// Returns the spokes of a worker, so that the portions of all workers are disjoint and
+    /// cover the whole draw.
+    /// @param worker the index of the worker ∈ 0..workers.
+    /// @param workers the number of workers.
+    pub fn portion(&self, worker: u64, workers: u64) -> Range<u64> {
+        assert!(worker < workers, "worker {} is out of 0..{}", worker, workers);
+
+        let bound = |w: u64| (self.n as u128 * w as u128 / workers as u128) as u64;
+        bound(worker)..bound(worker + 1)
+    }
+
+    /// Chooses the indices of the portion of a worker, see `portion`.
+    pub fn sample_portion(&self, worker: u64, workers: u64) -> Vec<usize> {
+        self.sample_range(self.portion(worker, workers))
+    }
+
+    /// Chooses the indices of a range of spokes of the whole draw.
+    /// @return the indices of the spokes in ascending order or nothing, if no weight is positive.
+    pub fn sample_range(&self, spokes: Range<u64>) -> Vec<usize> {
+        let sum = self.prepared.total_weight();
+        let end = spokes.end.min(self.n);
+
+        if self.prepared.is_empty() || sum <= 0.0 || spokes.start >= end {
+            return Vec::new();
+        }
+
+        let prefix_sums = self.prepared.prefix_sums();
+        let last = prefix_sums.len() - 1;
+        let spoke_gap = sum / self.n as f64;
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = CounterRng::<crate::core::entropy::Philox4x32>::new(self.seed, 0).next_f64();
+
+        let mut i: usize = 0;
+        let mut choices: Vec<usize> = Vec::with_capacity((end - spokes.start) as usize);
+
+        for j in spokes.start..end {
+            // every spoke is computed from its index, so that all workers agree on it
+            let spoke = (spin + j as f64) * spoke_gap;
+            i = (i + prefix_sums[i..].partition_point(|&s| s < spoke)).min(last);
+            choices.push(i);
+        }
+
+        choices
+    }
+}
@@ -0,0 +1,40 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Legacy API
+//! The sampling methods of the releases before 1.0, which trust their input. They are thin
+//! wrappers, which choose the same samples as before, and are available with the `legacy`
+//! feature, which is on by default. So they don't rescale tiny weights like `Sampler` does,
+//! see `rescale`. See the migration guide in the README.
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n samples by their weights. The greater their weights the more likely they get chosen.
+    /// Replaced by `Sampler::sample`, which checks the input.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param samples The to be selected samples
+    /// @param weights Weights that get chosen by their weight/probability. One weight can be greater 1.
+    /// @param n Number of randomly chosen samples by weight.
+    /// @return randomly selected samples by their weights
+    pub fn random_choice_f64<'a, T>(&mut self,
+                                    samples: &'a [T],
+                                    weights: &[f64],
+                                    n: usize)
+                                    -> Vec<&'a T> {
+        self.choose_f64(samples, weights, n)
+    }
+
+    /// Chooses n samples by their f32 weights, see `random_choice_f64`.
+    /// Replaced by `Sampler::sample`, which checks the input.
+    pub fn random_choice_f32<'a, T>(&mut self,
+                                    samples: &'a [T],
+                                    weights: &[f32],
+                                    n: usize)
+                                    -> Vec<&'a T> {
+        self.choose_f32(samples, weights, n)
+    }
+}
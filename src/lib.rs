@@ -15,20 +15,39 @@
 //! - **Evolutionary algorithms**: Choose the _n_ fittest populations by their fitness **_fi_**
 //! - **Monte Carlo Localization**: Resampling of _n_ particles by their weight **_w_**
 //!
+//! ## Layout
+//! - `core`: `Sampler`, `RandomChoice` and their foundations, without any feature
+//! - `prepared`: samplers, which prepare the weights once for many draws
+//! - `streaming`: samplers with a memory, which doesn't grow with the input or the output
+//! - `strategies`: sampling designs and applications built on the samplers
+//! - `integrations`: one module per integration, each behind its own feature
+//!
+//! ## MSRV
+//! The minimum supported Rust version of the core, i.e. without features, is 1.82.
+//! It is only raised in a minor release and checked by CI. The integrations follow the MSRV of
+//! the crates, which they integrate.
+//!
+//! ## 1.0 and the Legacy API
+//! `Sampler` is the entry point of 1.0: it owns the injected random number generator, draws by
+//! a `Strategy` and returns a `SampleError` for invalid input. The pre-1.0 methods
+//! `RandomChoice::random_choice_f64` and `random_choice_f32` are kept as thin wrappers behind
+//! the `legacy` feature, which is on by default, so code can migrate one call at a time.
+//!
 //! # Examples
 //! ## Default Way
 //! ```rust
+//! extern crate rand;
 //! extern crate random_choice;
-//! use self::random_choice::random_choice;
+//! use random_choice::Sampler;
 //!
 //! # fn main() {
-//! let mut samples = vec!["hi", "this", "is", "a", "test!"];
+//! let samples = vec!["hi", "this", "is", "a", "test!"];
 //! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
 //!
 //! let number_choices = 100;
-//! let choices = random_choice().random_choice_f64(&samples, &weights, number_choices);
+//! let choices = Sampler::new(rand::thread_rng()).sample(&samples, &weights, number_choices);
 //!
-//! for choice in choices {
+//! for choice in choices.unwrap() {
 //!     print!("{}, ", choice);
 //! }
 //! # }
@@ -37,126 +56,81 @@
 //! ```rust
 //! extern crate rand;
 //! extern crate random_choice;
-//! use random_choice::RandomChoice;
+//! use random_choice::{Sampler, Strategy};
 //! use rand::SeedableRng;
 //!
 //! fn main() {
-//!     let mut samples = vec!["hi", "this", "is", "a", "test!"];
-//!     let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!     let samples = vec!["hi", "this", "is", "a", "test!"];
+//!     let weights: Vec<f32> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
 //!
 //!     let rng = rand::StdRng::from_seed(&[5000, 44, 55, 199]);
 //!
-//!     let mut random_choice = RandomChoice::new(rng);
+//!     let mut sampler = Sampler::new(rng).with_strategy(Strategy::Independent);
 //!     let number_choices = 100;
-//!     let choices = random_choice.random_choice_f64(&mut samples, &weights, number_choices);
+//!     let choices = sampler.sample(&samples, &weights, number_choices).unwrap();
 //!
 //!     for choice in choices {
 //!         print!("{}, ", choice);
 //!     }
 //! }
 //! ```
+//! ## Legacy
+//! ```rust
+//! extern crate random_choice;
+//! use self::random_choice::random_choice;
+//!
+//! # #[cfg(feature = "legacy")]
+//! # fn main() {
+//! let samples = vec!["hi", "this", "is", "a", "test!"];
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! let choices = random_choice().random_choice_f64(&samples, &weights, 100);
+//!
+//! assert_eq!(choices.len(), 100);
+//! # }
+//! # #[cfg(not(feature = "legacy"))]
+//! # fn main() {}
+//! ```
 
 extern crate rand;
+#[cfg(feature = "fair-draw")]
+extern crate sha2;
+#[cfg(feature = "mmap")]
+extern crate memmap2;
+#[cfg(feature = "arrow")]
+extern crate arrow_array;
+#[cfg(feature = "polars")]
+extern crate polars_core;
+#[cfg(feature = "async")]
+extern crate futures;
+#[cfg(feature = "rand-compat")]
+extern crate rand_compat;
+#[cfg(feature = "slotmap")]
+extern crate slotmap;
+#[cfg(feature = "generational-arena")]
+extern crate generational_arena;
+#[cfg(feature = "bevy")]
+extern crate bevy_ecs;
+#[cfg(any(feature = "wire", feature = "config"))]
+extern crate serde;
+#[cfg(feature = "wire")]
+extern crate postcard;
+#[cfg(feature = "linalg")]
+extern crate nalgebra;
+#[cfg(feature = "config")]
+extern crate toml;
+#[cfg(feature = "config")]
+extern crate serde_yaml;
+
+pub mod core;
+pub mod integrations;
+#[cfg(feature = "legacy")]
+mod legacy;
+pub mod prepared;
+pub mod strategies;
+pub mod streaming;
+
+pub use crate::core::{random_choice, RandomChoice};
+pub use crate::core::sampler::{SampleError, Sampler, Strategy};
+pub(crate) use crate::core::sus_indices;
 
-use self::rand::{thread_rng, ThreadRng, Rng};
-
-pub struct RandomChoice<RNG: Rng> {
-    rng: RNG,
-}
-
-/// Creates a new RandomChoice struct using the ThreadRng
-pub fn random_choice() -> RandomChoice<ThreadRng> {
-    RandomChoice::new(thread_rng())
-}
-
-
-impl<RNG: Rng> RandomChoice<RNG> {
-    /// Creates a new RandomChoice struct.
-    /// @param rng the random number generator to use with this stuct.
-    pub fn new(rng: RNG) -> Self {
-        RandomChoice { rng: rng }
-    }
-
-    /// Chooses n samples by their weights. The greater their weights the more likely they get chosen.
-    ///
-    /// @invariant sum of weights must not overflow.
-    /// @param samples The to be selected samples
-    /// @param weights Weights that get chosen by their weight/probability. One weight can be greater 1.
-    /// @param n Number of randomly chosen samples by weight.
-    /// @return randomly selected samples by their weights
-    pub fn random_choice_f64<'a, T>(&mut self,
-                                    samples: &'a [T],
-                                    weights: &[f64],
-                                    n: usize)
-                                    -> Vec<&'a T> {
-        if weights.len() == 0 || samples.len() == 0 || n == 0 {
-            return Vec::new();
-        }
-
-        let sum: f64 = weights.iter().fold(0.0, |acc, &i| acc + i);
-        let spoke_gap: f64 = sum / n as f64;
-
-        // next_f64() ∈ [0.0, 1.0)
-        let spin = self.rng.next_f64() * spoke_gap;
-
-        let mut i: usize = 0;
-        let mut accumulated_weights = weights[0];
-        let mut choices: Vec<&T> = Vec::with_capacity(n);
-        let mut current_spoke: f64 = spin;
-
-        while current_spoke < sum {
-            while accumulated_weights < current_spoke {
-                i += 1;
-                accumulated_weights += weights[i];
-            }
-            choices.push(&samples[i]);
-            current_spoke += spoke_gap;
-        }
-
-        // add this condition, because float leads to inaccurate
-        // calculations which can miss some samples
-        while choices.len() < n {
-            choices.push(&samples[i]);
-        }
-
-        choices
-    }
-
-    pub fn random_choice_f32<'a, T>(&mut self,
-                                    samples: &'a [T],
-                                    weights: &[f32],
-                                    n: usize)
-                                    -> Vec<&'a T> {
-        if weights.len() == 0 || samples.len() == 0 || n == 0 {
-            return Vec::new();
-        }
-
-        let sum: f64 = weights.iter().fold(0.0, |acc, &i| acc + i as f64);
-        let spoke_gap: f64 = sum / n as f64;
-
-        // next_f64() ∈ [0.0, 1.0)
-        let spin = self.rng.next_f64() * spoke_gap;
-
-        let mut i: usize = 0;
-        let mut accumulated_weights = weights[0] as f64;
-        let mut choices: Vec<&T> = Vec::with_capacity(n);
-        let mut current_spoke: f64 = spin;
-
-        while current_spoke < sum {
-            while accumulated_weights < current_spoke {
-                i += 1;
-                accumulated_weights += weights[i] as f64;
-            }
-            choices.push(&samples[i]);
-            current_spoke += spoke_gap;
-        }
-
-        // add this condition, because float leads to inaccurate
-        // calculations which can miss some samples
-        while choices.len() < n {
-            choices.push(&samples[i]);
-        }
-
-        choices
-    }
-}
@@ -0,0 +1,505 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Alias Tables
+//! Walker's alias method with Vose's construction: after an O(n) construction,
+//! every single sample costs O(1), independent of the number of weights.
+//!
+//! An `AliasTable` can be written to disk with `write_to`. With the `mmap` feature,
+//! a `SharedAliasTable` maps such a file read-only, so that several worker processes
+//! share one table instead of each of them building its own copy.
+//!
+//! An `AliasTable32` stores the probabilities as `f32` and the aliases as `u32`, i.e. 8 instead
+//! of 16 bytes per weight, e.g. 800 MB instead of 1.6 GB for 10^8 weights. It is constructed in
+//! `f64` like an `AliasTable` and rounded afterwards. Every construction verifies, that the
+//! total variation distance between the distribution of the table and the weights is at most
+//! `F32_ERROR_BOUND`. Rounding to `f32` contributes at most 2^-25, the rest of the bound is
+//! left for the rounding errors of the construction in `f64`.
+//!
+//! ## On-Disk Format
+//! All numbers are little endian.
+//!
+//! | Offset         | Size    | Content                          |
+//! |----------------|---------|----------------------------------|
+//! | 0              | 8       | magic bytes `RCALIAS\0`          |
+//! | 8              | 4       | format version (`FORMAT_VERSION`)|
+//! | 12             | 4       | reserved, zero                   |
+//! | 16             | 8       | number of entries `n`            |
+//! | 24             | 8 * n   | probabilities as `f64`           |
+//! | 24 + 8 * n     | 8 * n   | aliases as `u64`                 |
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::prepared::alias::AliasTable;
+//!
+//! # fn main() {
+//! let samples = vec!["hi", "this", "is", "a", "test!"];
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! let table = AliasTable::new(&weights);
+//! let mut rng = rand::thread_rng();
+//!
+//! for index in table.sample_n(&mut rng, 100) {
+//!     print!("{}, ", samples[index]);
+//! }
+//! # }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::ControlFlow;
+
+use rand::Rng;
+
+use crate::core::progress::{Aborted, Phase, Progress, Reporter};
+
+/// Magic bytes at the beginning of an alias table file.
+pub const MAGIC: &[u8; 8] = b"RCALIAS\0";
+
+/// Version of the on-disk format. It is increased on every incompatible change.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Size of the header in bytes.
+pub const HEADER_SIZE: usize = 24;
+
+/// The greatest total variation distance between the distribution of an `AliasTable32` and its
+/// weights.
+pub const F32_ERROR_BOUND: f64 = 1e-6;
+
+/// Samples indices in O(1) by Walker's alias method.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasTable {
+    probabilities: Vec<f64>,
+    aliases: Vec<usize>,
+}
+
+impl AliasTable {
+    /// Builds the alias table in O(n).
+    /// If all weights are zero, every index is equally likely.
+    ///
+    /// @invariant sum of weights must not overflow and weights must not be negative.
+    /// @param weights the weights of the indices. They don't have to sum up to 1.0.
+    pub fn new(weights: &[f64]) -> Self {
+        let (probabilities, aliases) = build_vose(weights, &mut Reporter::none())
+            .expect("construction without progress hook can't be aborted");
+
+        AliasTable {
+            probabilities,
+            aliases,
+        }
+    }
+
+    /// Builds the alias table in O(n) and reports the progress.
+    /// See the `progress` module.
+    ///
+    /// @param weights the weights of the indices. They don't have to sum up to 1.0.
+    /// @param every the hook is called every `every` elements and at the end of every phase.
+    /// @param progress the progress hook. If it breaks, the construction is aborted.
+    pub fn with_progress<F>(weights: &[f64], every: usize, mut progress: F) -> Result<Self, Aborted>
+        where F: FnMut(&Progress) -> ControlFlow<()>
+    {
+        let (probabilities, aliases) = build_vose(weights, &mut Reporter::new(every, &mut progress))?;
+
+        Ok(AliasTable {
+            probabilities,
+            aliases,
+        })
+    }
+
+    /// Returns the number of indices in this table.
+    pub fn len(&self) -> usize {
+        self.probabilities.len()
+    }
+
+    /// Returns true, if the table was built from no weights.
+    pub fn is_empty(&self) -> bool {
+        self.probabilities.is_empty()
+    }
+
+    /// Returns the probability of keeping the index of a slot instead of taking its alias.
+    pub fn probabilities(&self) -> &[f64] {
+        &self.probabilities
+    }
+
+    /// Returns the alias of each slot.
+    pub fn aliases(&self) -> &[usize] {
+        &self.aliases
+    }
+
+    /// Chooses one index by its weight.
+    /// @return None, if the table is empty.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let slot = rng.gen_range(0, self.len());
+
+        if rng.next_f64() < self.probabilities[slot] {
+            Some(slot)
+        } else {
+            Some(self.aliases[slot])
+        }
+    }
+
+    /// Chooses n indices independently by their weights.
+    pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        (0..n).filter_map(|_| self.sample(rng)).collect()
+    }
+
+    /// Writes the table in the on-disk format described in the module documentation.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let mut writer = io::BufWriter::new(writer);
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(&0u32.to_le_bytes())?;
+        writer.write_all(&(self.len() as u64).to_le_bytes())?;
+
+        for probability in &self.probabilities {
+            writer.write_all(&probability.to_le_bytes())?;
+        }
+
+        for &alias in &self.aliases {
+            writer.write_all(&(alias as u64).to_le_bytes())?;
+        }
+
+        writer.flush()
+    }
+}
+
+/// The error of `AliasTable32::new`: the rounded table is too far from the weights.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrecisionError {
+    /// The total variation distance between the distribution of the table and the weights.
+    pub total_error: f64,
+}
+
+impl fmt::Display for PrecisionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f,
+               "total probability error {} exceeds the bound {}",
+               self.total_error,
+               F32_ERROR_BOUND)
+    }
+}
+
+impl Error for PrecisionError {}
+
+/// Samples indices in O(1) by Walker's alias method with half of the memory of an `AliasTable`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasTable32 {
+    probabilities: Vec<f32>,
+    aliases: Vec<u32>,
+    total_error: f64,
+}
+
+impl AliasTable32 {
+    /// Builds the alias table in O(n) with `f64` and rounds it to `f32`.
+    /// If all weights are zero, every index is equally likely.
+    ///
+    /// @invariant sum of weights must not overflow and weights must not be negative.
+    ///            There must be at most 2^32 weights.
+    /// @param weights the weights of the indices. They don't have to sum up to 1.0.
+    /// @return the table or the error, if its distribution is further than `F32_ERROR_BOUND`
+    ///         from the weights.
+    pub fn new(weights: &[f64]) -> Result<Self, PrecisionError> {
+        assert!(weights.len() as u64 <= 1 << 32, "an AliasTable32 has at most 2^32 weights");
+
+        let (mut implied, aliases) = build_vose(weights, &mut Reporter::none())
+            .expect("construction without progress hook can't be aborted");
+
+        let probabilities: Vec<f32> = implied.iter().map(|&p| p as f32).collect();
+        let aliases: Vec<u32> = aliases.into_iter().map(|alias| alias as u32).collect();
+
+        // the buffer of the f64 probabilities is reused for the probabilities of the indices,
+        // which the rounded table implies, scaled by n
+        for (slot, &p) in probabilities.iter().enumerate() {
+            implied[slot] = p as f64;
+        }
+
+        for (slot, &p) in probabilities.iter().enumerate() {
+            if aliases[slot] as usize != slot {
+                implied[aliases[slot] as usize] += 1.0 - p as f64;
+            }
+        }
+
+        let len = weights.len() as f64;
+        let sum: f64 = weights.iter().fold(0.0, |acc, &w| acc + w);
+        let exact = |i: usize| if sum > 0.0 { weights[i] / sum } else { 1.0 / len };
+
+        let total_error = implied.iter()
+            .enumerate()
+            .fold(0.0, |acc, (i, &q)| acc + (q / len - exact(i)).abs()) / 2.0;
+
+        if total_error.is_nan() || total_error > F32_ERROR_BOUND {
+            return Err(PrecisionError { total_error });
+        }
+
+        Ok(AliasTable32 {
+            probabilities,
+            aliases,
+            total_error,
+        })
+    }
+
+    /// Returns the number of indices in this table.
+    pub fn len(&self) -> usize {
+        self.probabilities.len()
+    }
+
+    /// Returns true, if the table was built from no weights.
+    pub fn is_empty(&self) -> bool {
+        self.probabilities.is_empty()
+    }
+
+    /// Returns the probability of keeping the index of a slot instead of taking its alias.
+    pub fn probabilities(&self) -> &[f32] {
+        &self.probabilities
+    }
+
+    /// Returns the alias of each slot.
+    pub fn aliases(&self) -> &[u32] {
+        &self.aliases
+    }
+
+    /// Returns the total variation distance between the distribution of the table and the
+    /// weights, which was verified by the construction.
+    pub fn total_error(&self) -> f64 {
+        self.total_error
+    }
+
+    /// Chooses one index by its weight.
+    /// @return None, if the table is empty.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let slot = rng.gen_range(0, self.len());
+
+        if rng.next_f64() < self.probabilities[slot] as f64 {
+            Some(slot)
+        } else {
+            Some(self.aliases[slot] as usize)
+        }
+    }
+
+    /// Chooses n indices independently by their weights.
+    pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        (0..n).filter_map(|_| self.sample(rng)).collect()
+    }
+}
+
+/// Vose's construction of the probability and alias columns.
+fn build_vose(weights: &[f64], reporter: &mut Reporter) -> Result<(Vec<f64>, Vec<usize>), Aborted> {
+    let len = weights.len();
+    let mut sum: f64 = 0.0;
+
+    for (i, &weight) in weights.iter().enumerate() {
+        sum += weight;
+        reporter.report(Phase::Summing, i + 1, len)?;
+    }
+
+    let mut aliases: Vec<usize> = (0..len).collect();
+
+    if len == 0 || sum <= 0.0 {
+        return Ok((vec![1.0; len], aliases));
+    }
+
+    let scale = len as f64 / sum;
+    let mut probabilities: Vec<f64> = Vec::with_capacity(len);
+
+    let mut small: Vec<usize> = Vec::new();
+    let mut large: Vec<usize> = Vec::new();
+
+    for (i, &weight) in weights.iter().enumerate() {
+        let p = weight * scale;
+        probabilities.push(p);
+
+        if p < 1.0 {
+            small.push(i);
+        } else {
+            large.push(i);
+        }
+
+        reporter.report(Phase::Partitioning, i + 1, len)?;
+    }
+
+    let mut paired: usize = 0;
+
+    while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+        small.pop();
+        aliases[s] = l;
+        probabilities[l] = (probabilities[l] + probabilities[s]) - 1.0;
+
+        if probabilities[l] < 1.0 {
+            large.pop();
+            small.push(l);
+        }
+
+        paired += 1;
+        reporter.report(Phase::Pairing, paired, len)?;
+    }
+
+    // the remaining slots are full up to inaccurate float calculations
+    for i in small.into_iter().chain(large) {
+        probabilities[i] = 1.0;
+
+        paired += 1;
+        reporter.report(Phase::Pairing, paired, len)?;
+    }
+
+    Ok((probabilities, aliases))
+}
+
+#[cfg(feature = "mmap")]
+pub use self::shared::SharedAliasTable;
+
+#[cfg(feature = "mmap")]
+mod shared {
+    use std::convert::TryInto;
+    use std::fs::File;
+    use std::io::{self, Write};
+    use std::path::Path;
+
+    use memmap2::Mmap;
+    use rand::Rng;
+
+    use super::{AliasTable, FORMAT_VERSION, HEADER_SIZE, MAGIC};
+
+    /// An alias table, which is mapped read-only from a file.
+    ///
+    /// The operating system shares the mapped pages between all processes which open the same
+    /// file, so a table has to be built only once, e.g. before forking the workers.
+    /// The file must not be modified while it is mapped.
+    #[derive(Debug)]
+    pub struct SharedAliasTable {
+        map: Mmap,
+        len: usize,
+    }
+
+    impl SharedAliasTable {
+        /// Builds an alias table from the weights, writes it to path and maps it.
+        pub fn create<P: AsRef<Path>>(path: P, weights: &[f64]) -> io::Result<Self> {
+            let mut file = File::create(path.as_ref())?;
+            AliasTable::new(weights).write_to(&mut file)?;
+            file.flush()?;
+
+            SharedAliasTable::open(path)
+        }
+
+        /// Maps a table file, which was written by `AliasTable::write_to`.
+        /// Only the header and the size of the file are checked, so that opening a large table
+        /// doesn't read all of its pages. `validate` checks the entries.
+        /// @return an error of kind `InvalidData`, if the file has an unknown format or version,
+        ///         a reserved field, which isn't zero, or the wrong size.
+        pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+            let file = File::open(path)?;
+
+            // Safety: the file is mapped read-only and the documentation forbids modifying it
+            // while it is mapped.
+            let map = unsafe { Mmap::map(&file)? };
+
+            if map.len() < HEADER_SIZE || &map[0..8] != MAGIC {
+                return Err(invalid_data("not an alias table file"));
+            }
+
+            let version = u32::from_le_bytes(map[8..12].try_into().unwrap());
+
+            if version != FORMAT_VERSION {
+                return Err(invalid_data(&format!("unsupported alias table format version {}, \
+                                                  expected {}",
+                                                 version,
+                                                 FORMAT_VERSION)));
+            }
+
+            // a later format may use the reserved field, so it is not ignored
+            if map[12..16] != [0; 4] {
+                return Err(invalid_data("reserved field of the alias table header is not zero"));
+            }
+
+            let len = u64::from_le_bytes(map[16..24].try_into().unwrap()) as usize;
+
+            let size = len.checked_mul(16).and_then(|size| size.checked_add(HEADER_SIZE));
+
+            if size != Some(map.len()) {
+                return Err(invalid_data("alias table file has the wrong size"));
+            }
+
+            Ok(SharedAliasTable { map, len })
+        }
+
+        /// Checks, that every alias is an index of the table. It reads the whole file, i.e.
+        /// O(n) pages, so it is meant for files, which aren't trusted, e.g. once after a copy.
+        /// @return an error of kind `InvalidData` for the first alias out of range.
+        pub fn validate(&self) -> io::Result<()> {
+            match (0..self.len).find(|&slot| self.alias(slot) >= self.len) {
+                Some(slot) => Err(invalid_data(&format!("alias of slot {} is out of range", slot))),
+                None => Ok(()),
+            }
+        }
+
+        /// Returns the number of indices in this table.
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Returns true, if the table was built from no weights.
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        /// Returns the probability of keeping the index of a slot instead of taking its alias.
+        pub fn probability(&self, slot: usize) -> f64 {
+            let offset = HEADER_SIZE + 8 * slot;
+            f64::from_le_bytes(self.map[offset..offset + 8].try_into().unwrap())
+        }
+
+        /// Returns the alias of a slot.
+        pub fn alias(&self, slot: usize) -> usize {
+            let offset = HEADER_SIZE + 8 * (self.len + slot);
+            u64::from_le_bytes(self.map[offset..offset + 8].try_into().unwrap()) as usize
+        }
+
+        /// Chooses one index by its weight. The index is out of range, if the file has an alias
+        /// out of range, see `validate`.
+        /// @return None, if the table is empty.
+        pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+            if self.is_empty() {
+                return None;
+            }
+
+            let slot = rng.gen_range(0, self.len);
+
+            if rng.next_f64() < self.probability(slot) {
+                Some(slot)
+            } else {
+                Some(self.alias(slot))
+            }
+        }
+
+        /// Chooses n indices independently by their weights.
+        pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+            if self.is_empty() {
+                return Vec::new();
+            }
+
+            (0..n).filter_map(|_| self.sample(rng)).collect()
+        }
+    }
+
+    fn invalid_data(message: &str) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, message)
+    }
+}
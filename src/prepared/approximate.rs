@@ -0,0 +1,239 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Approximate Sampling
+//! For candidate generation over millions of items, where exactness doesn't matter,
+//! `ApproximateSampler` trades a bounded error for a cheaper construction and smaller tables.
+//!
+//! Every weight is rounded down to the lower bound of its band: the bands split each binade
+//! [2^e, 2^(e+1)) into 2^k parts of equal width, which is done by clearing the lower mantissa
+//! bits of the weight. So every weight is rounded by less than the factor 1 + 2^-k. A band is
+//! chosen by an alias table over the rounded weights of all bands and a member of the band
+//! uniformly. Only the bands need a table, and there are far fewer bands than weights.
+//! Subnormal weights have no implicit leading bit, so clearing their bits could round them
+//! down to zero. They are kept exact instead, every distinct subnormal weight is its own band.
+//!
+//! ## Error Bound
+//! If every weight w_i is rounded to q_i with w_i / q_i ∈ [1, 1 + δ), the total variation
+//! distance between the distributions of w and q is at most δ / 2. With δ = 2^-k, the distance is at most
+//! 2^-(k + 1), e.g. 0.2 % for k = 8. See `total_variation_bound`.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::prepared::approximate::ApproximateSampler;
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = (1..100000).map(|i| 1.0 / i as f64).collect();
+//!
+//! let sampler = ApproximateSampler::new(&weights, 8);
+//! let mut rng = rand::thread_rng();
+//!
+//! assert!(sampler.total_variation_bound() <= 0.002);
+//! assert_eq!(sampler.sample_n(&mut rng, 100).len(), 100);
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+use rand::Rng;
+
+use crate::prepared::alias::AliasTable;
+
+/// The maximum number of mantissa bits of a band key. With 52 bits, no weight is rounded.
+pub const MAX_PRECISION_BITS: u32 = 52;
+
+/// Returns the bound of the total variation distance between the exact distribution and the one
+/// of an `ApproximateSampler` with the given precision: 2^-(precision_bits + 1).
+pub fn total_variation_bound(precision_bits: u32) -> f64 {
+    if precision_bits >= MAX_PRECISION_BITS {
+        0.0
+    } else {
+        0.5 * (-(precision_bits as f64)).exp2()
+    }
+}
+
+/// Samples indices from weights, which are rounded to 2^k bands per power of two.
+#[derive(Debug, Clone)]
+pub struct ApproximateSampler {
+    precision_bits: u32,
+    len: usize,
+    /// The rounded weight of the members of every band.
+    representatives: Vec<f64>,
+    /// The members of band b are members[band_starts[b]..band_starts[b + 1]].
+    band_starts: Vec<usize>,
+    members: Vec<usize>,
+    table: AliasTable,
+}
+
+impl ApproximateSampler {
+    /// Builds the sampler in O(n). Weights, which are not positive and finite, are never chosen.
+    /// If no weight is positive, every index is equally likely like in `AliasTable`.
+    ///
+    /// @param weights the weights of the indices. They don't have to sum up to 1.0.
+    /// @param precision_bits the number k of mantissa bits, which are kept. Up to
+    ///        `MAX_PRECISION_BITS`, greater values are treated like it.
+    pub fn new(weights: &[f64], precision_bits: u32) -> Self {
+        let precision_bits = precision_bits.min(MAX_PRECISION_BITS);
+        let mask: u64 = !((1u64 << (MAX_PRECISION_BITS - precision_bits)) - 1);
+
+        // the key of a band is the weight with the lower mantissa bits cleared, or the whole
+        // weight, if it is subnormal
+        let mut bands: HashMap<u64, usize, BuildHasherDefault<KeyHasher>> = HashMap::default();
+        let mut representatives: Vec<f64> = Vec::new();
+        let mut counts: Vec<usize> = Vec::new();
+        let mut band_of: Vec<usize> = Vec::with_capacity(weights.len());
+
+        // neighbouring weights often share their band, which saves the lookup
+        let mut last: Option<(u64, usize)> = None;
+
+        for &weight in weights {
+            if weight > 0.0 && weight.is_finite() {
+                let key = if weight.is_normal() {
+                    weight.to_bits() & mask
+                } else {
+                    weight.to_bits()
+                };
+                let band = match last {
+                    Some((last_key, band)) if last_key == key => band,
+                    _ => {
+                        *bands.entry(key).or_insert_with(|| {
+                            representatives.push(f64::from_bits(key));
+                            counts.push(0);
+                            counts.len() - 1
+                        })
+                    }
+                };
+
+                last = Some((key, band));
+
+                counts[band] += 1;
+                band_of.push(band);
+            } else {
+                band_of.push(usize::MAX);
+            }
+        }
+
+        if representatives.is_empty() && !weights.is_empty() {
+            representatives.push(1.0);
+            counts.push(weights.len());
+            band_of.iter_mut().for_each(|band| *band = 0);
+        }
+
+        let mut band_starts: Vec<usize> = Vec::with_capacity(counts.len() + 1);
+        band_starts.push(0);
+
+        for &count in &counts {
+            let end = band_starts[band_starts.len() - 1] + count;
+            band_starts.push(end);
+        }
+
+        let mut next = band_starts.clone();
+        let mut members: Vec<usize> = vec![0; band_starts[band_starts.len() - 1]];
+
+        for (i, &band) in band_of.iter().enumerate() {
+            if band != usize::MAX {
+                members[next[band]] = i;
+                next[band] += 1;
+            }
+        }
+
+        let mut band_weights: Vec<f64> = representatives.iter()
+            .zip(&counts)
+            .map(|(&representative, &count)| representative * count as f64)
+            .collect();
+
+        // the alias table divides by the sum, which overflows for subnormal sums, so these are
+        // scaled up by a power of two, which is exact
+        let max = band_weights.iter().cloned().fold(0.0, f64::max);
+
+        if max > 0.0 && max < f64::MIN_POSITIVE {
+            let scale = 2f64.powi(600);
+            band_weights.iter_mut().for_each(|weight| *weight *= scale);
+        }
+
+        ApproximateSampler {
+            precision_bits,
+            len: weights.len(),
+            representatives,
+            band_starts,
+            members,
+            table: AliasTable::new(&band_weights),
+        }
+    }
+
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true, if the sampler was built from no weights.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number k of kept mantissa bits.
+    pub fn precision_bits(&self) -> u32 {
+        self.precision_bits
+    }
+
+    /// Returns the bound of the total variation distance to the exact distribution.
+    pub fn total_variation_bound(&self) -> f64 {
+        total_variation_bound(self.precision_bits)
+    }
+
+    /// Returns the number of bands.
+    pub fn bands(&self) -> usize {
+        self.representatives.len()
+    }
+
+    /// Returns the rounded weight and the members of a band.
+    pub fn band(&self, band: usize) -> (f64, &[usize]) {
+        let members = &self.members[self.band_starts[band]..self.band_starts[band + 1]];
+        (self.representatives[band], members)
+    }
+
+    /// Chooses one index by its rounded weight.
+    /// @return None, if the sampler is empty.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        self.table.sample(rng).map(|band| {
+            let start = self.band_starts[band];
+            let end = self.band_starts[band + 1];
+            self.members[rng.gen_range(start, end)]
+        })
+    }
+
+    /// Chooses n indices independently by their rounded weights.
+    pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+
+        (0..n).filter_map(|_| self.sample(rng)).collect()
+    }
+}
+
+/// The finalizer of SplitMix64 as hash for band keys, which are no input of an adversary.
+/// Far cheaper than SipHash, which dominates the construction otherwise.
+#[derive(Default)]
+struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = self.0.rotate_left(8) ^ u64::from(byte);
+        }
+    }
+
+    fn write_u64(&mut self, key: u64) {
+        self.0 ^= key;
+    }
+}
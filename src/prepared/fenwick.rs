@@ -0,0 +1,199 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Dynamic Weights
+//! `FenwickSampler` keeps the weights in a Fenwick tree (binary indexed tree), so that a single
+//! weight can be changed in O(log n) instead of rebuilding a table in O(n), and an index is still
+//! chosen in O(log n). It is the backend of the samplers, whose weights change between draws.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::prepared::fenwick::FenwickSampler;
+//!
+//! # fn main() {
+//! let mut sampler = FenwickSampler::new(&[5.6, 7.8, 9.7, 1.1, 2.0]);
+//! let mut rng = rand::thread_rng();
+//!
+//! sampler.set_weight(2, 0.0);
+//!
+//! for index in sampler.sample_n(&mut rng, 100) {
+//!     assert!(index != 2);
+//! }
+//! # }
+//! ```
+
+use rand::Rng;
+
+/// Chooses indices by weights, which can be changed in O(log n).
+#[derive(Debug, Clone, PartialEq)]
+pub struct FenwickSampler {
+    /// tree[i - 1] is the sum of the weights (i - lowbit(i))..i.
+    tree: Vec<f64>,
+    weights: Vec<f64>,
+    /// The number of positive weights. Rounding errors of the updates may leave a tiny total
+    /// weight, after all weights have been set to zero.
+    positive: usize,
+}
+
+impl FenwickSampler {
+    /// Builds the tree in O(n).
+    /// @invariant sum of weights must not overflow and weights must not be negative.
+    pub fn new(weights: &[f64]) -> Self {
+        let mut tree = weights.to_vec();
+
+        for i in 1..=tree.len() {
+            let parent = i + lowbit(i);
+
+            if parent <= tree.len() {
+                tree[parent - 1] += tree[i - 1];
+            }
+        }
+
+        FenwickSampler {
+            tree,
+            weights: weights.to_vec(),
+            positive: weights.iter().filter(|&&w| w > 0.0).count(),
+        }
+    }
+
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Returns true, if there are no weights.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Returns the weight of an index.
+    pub fn weight(&self, i: usize) -> f64 {
+        self.weights[i]
+    }
+
+    /// Returns all weights.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the sum of all weights in O(log n).
+    pub fn total_weight(&self) -> f64 {
+        self.prefix_sum(self.len())
+    }
+
+    /// Returns the sum of the weights 0..end in O(log n).
+    pub fn prefix_sum(&self, end: usize) -> f64 {
+        let mut sum: f64 = 0.0;
+        let mut i = end;
+
+        while i > 0 {
+            sum += self.tree[i - 1];
+            i -= lowbit(i);
+        }
+
+        sum
+    }
+
+    /// Changes the weight of an index in O(log n).
+    /// @invariant the weight must not be negative.
+    pub fn set_weight(&mut self, i: usize, weight: f64) {
+        let delta = weight - self.weights[i];
+
+        if self.weights[i] > 0.0 {
+            self.positive -= 1;
+        }
+
+        if weight > 0.0 {
+            self.positive += 1;
+        }
+
+        self.weights[i] = weight;
+
+        // setting a weight to zero twice must not accumulate rounding errors
+        if delta == 0.0 {
+            return;
+        }
+
+        let mut j = i + 1;
+
+        while j <= self.tree.len() {
+            self.tree[j - 1] += delta;
+            j += lowbit(j);
+        }
+    }
+
+    /// Rebuilds the tree in O(n), which removes the rounding errors of many updates.
+    pub fn rebuild(&mut self) {
+        *self = FenwickSampler::new(&self.weights);
+    }
+
+    /// Returns true, if at least one weight is positive.
+    pub fn has_positive_weight(&self) -> bool {
+        self.positive > 0
+    }
+
+    /// Chooses one index by its weight in O(log n).
+    /// @return None, if no weight is positive.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        let total = self.total_weight();
+
+        if self.positive == 0 || total <= 0.0 {
+            return None;
+        }
+
+        // next_f64() ∈ [0.0, 1.0)
+        Some(self.find(rng.next_f64() * total))
+    }
+
+    /// Chooses n indices independently by their weights.
+    /// @return nothing, if no weight is positive.
+    pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        let total = self.total_weight();
+
+        if self.positive == 0 || total <= 0.0 {
+            return Vec::new();
+        }
+
+        (0..n).map(|_| self.find(rng.next_f64() * total)).collect()
+    }
+
+    /// Finds the index, whose weight covers the target on the wheel, by descending the tree.
+    /// Indices with a weight of zero are skipped.
+    /// @invariant at least one weight is positive.
+    pub(crate) fn find(&self, target: f64) -> usize {
+        let len = self.tree.len();
+        let mut position: usize = 0;
+        let mut remaining = target;
+        let mut step = if len == 0 { 0 } else { 1 << (usize::BITS - 1 - len.leading_zeros()) };
+
+        while step > 0 {
+            let next = position + step;
+
+            if next <= len && self.tree[next - 1] <= remaining {
+                position = next;
+                remaining -= self.tree[next - 1];
+            }
+
+            step >>= 1;
+        }
+
+        // rounding errors may point to a weight of zero,
+        // the nearest positive weight is taken instead
+        let i = position.min(len - 1);
+
+        if self.weights[i] > 0.0 {
+            return i;
+        }
+
+        (0..i)
+            .rev()
+            .find(|&j| self.weights[j] > 0.0)
+            .or_else(|| (i + 1..len).find(|&j| self.weights[j] > 0.0))
+            .unwrap_or(i)
+    }
+}
+
+fn lowbit(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
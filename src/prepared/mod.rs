@@ -0,0 +1,644 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Prepared Choices
+//! `PreparedChoice` computes the prefix sums of the weights once, so that repeated draws from the
+//! same weights don't have to sum them up again. Instead of walking the wheel one weight at a
+//! time, every spoke gallops ahead over the prefix sums: if one weight dominates, e.g. 1e9 against
+//! millions of weights of 1e-9, a spoke skips all of the tiny weights in O(log n) steps.
+//!
+//! How the spokes are placed on the prefix sums is a `Placement`. All of them choose the same
+//! indices as `RandomChoice::random_choice_indices_f64` with the same random number generator,
+//! they only differ in their costs for k spokes on n weights. Tiny and subnormal weights are
+//! rescaled before the prefix sums are computed, the same way as by `random_choice_indices_f64`.
+//!
+//! If an upstream system already maintains the prefix sums, `PreparedChoice::from_cumulative`
+//! takes them over instead of accumulating the weights again.
+//!
+//! `PreparedChoice::update_many` changes k weights in O(k * log n) instead of rebuilding the
+//! prefix sums in O(n): the first update moves the weights into a `FenwickSampler`, on which the
+//! spokes are then placed, until `flush` computes the prefix sums again.
+//!
+//! With the `mmap` feature, a `MappedPreparedChoice` samples from a weight file, which is larger
+//! than the memory, with a prefix index of one sum per block of weights.
+//!
+//! The submodules are the other samplers, which prepare the weights once for many draws:
+//! alias tables, approximate tables and Fenwick trees.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::prepared::PreparedChoice;
+//!
+//! # fn main() {
+//! let mut weights: Vec<f64> = vec![1e-9; 100000];
+//! weights[50000] = 1e9;
+//!
+//! let prepared = PreparedChoice::new(&weights);
+//! let mut rng = rand::thread_rng();
+//!
+//! let choices = prepared.sample_n(&mut rng, 100);
+//! assert_eq!(choices.len(), 100);
+//! # }
+//! ```
+
+pub mod alias;
+pub mod approximate;
+pub mod fenwick;
+
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::ops::ControlFlow;
+
+use rand::Rng;
+
+use crate::prepared::fenwick::FenwickSampler;
+use crate::core::progress::{Aborted, Phase, Progress, Reporter};
+use crate::core::rescale::{self, Rescaling};
+
+/// The search of the index of a spoke on the prefix sums.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Placement {
+    /// Walks the prefix sums one index at a time like `random_choice_indices_f64`: O(n + k).
+    Linear,
+    /// Searches exponentially from the index of the previous spoke: O(k * log(n / k)).
+    Gallop,
+    /// Searches binary between the index of the previous spoke and the end: O(k * log n).
+    BinarySearch,
+    /// `BinarySearch` for k * log n < n, `Linear` for k >= n and `Gallop` otherwise.
+    #[default]
+    Auto,
+}
+
+/// The error of `PreparedChoice::from_cumulative`: the values aren't the prefix sums of weights,
+/// which are not negative.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotCumulative {
+    /// The first index, whose value is negative, not finite or less than the value before.
+    pub index: usize,
+}
+
+impl fmt::Display for NotCumulative {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cumulative weights decrease or are invalid at index {}", self.index)
+    }
+}
+
+impl Error for NotCumulative {}
+
+/// The prefix sums of weights for repeated stochastic universal sampling.
+/// It is encoded as its prefix sums.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "wire", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wire", serde(into = "Vec<f64>", try_from = "Vec<f64>"))]
+pub struct PreparedChoice {
+    /// prefix_sums[i] is the sum of the weights 0..=i. It is stale, while `updated` is some.
+    prefix_sums: Vec<f64>,
+    /// The weights since the first `update_many` until the next `flush`.
+    updated: Option<FenwickSampler>,
+}
+
+impl PartialEq for PreparedChoice {
+    fn eq(&self, other: &Self) -> bool {
+        self.prefix_sums() == other.prefix_sums()
+    }
+}
+
+impl From<PreparedChoice> for Vec<f64> {
+    /// Returns the prefix sums.
+    fn from(mut prepared: PreparedChoice) -> Self {
+        prepared.flush();
+        prepared.prefix_sums
+    }
+}
+
+impl TryFrom<Vec<f64>> for PreparedChoice {
+    type Error = NotCumulative;
+
+    /// See `PreparedChoice::from_cumulative`.
+    fn try_from(cumulative: Vec<f64>) -> Result<Self, NotCumulative> {
+        PreparedChoice::from_cumulative(cumulative)
+    }
+}
+
+impl PreparedChoice {
+    /// Computes the prefix sums in O(n). Tiny and subnormal weights are rescaled first, see
+    /// `rescale`, so the prefix sums and `weight` are those of the rescaled weights.
+    ///
+    /// @invariant sum of weights must not overflow and weights must not be negative.
+    /// @param weights the weights of the indices. They don't have to sum up to 1.0.
+    pub fn new(weights: &[f64]) -> Self {
+        PreparedChoice::build(weights, &mut Reporter::none())
+            .expect("construction without progress hook can't be aborted")
+    }
+
+    /// Computes the prefix sums in O(n) and reports the progress in the phase `Summing`.
+    /// See the `progress` module.
+    ///
+    /// @param weights the weights of the indices. They don't have to sum up to 1.0.
+    /// @param every the hook is called every `every` elements and at the end of the phase.
+    /// @param progress the progress hook. If it breaks, the construction is aborted.
+    pub fn with_progress<F>(weights: &[f64], every: usize, mut progress: F) -> Result<Self, Aborted>
+        where F: FnMut(&Progress) -> ControlFlow<()>
+    {
+        PreparedChoice::build(weights, &mut Reporter::new(every, &mut progress))
+    }
+
+    /// Takes the prefix sums, which an upstream system already maintains, instead of computing
+    /// them. A `Vec` is taken over without copying it, the values are only checked in O(n).
+    ///
+    /// @param cumulative the cumulative weights, i.e. the sum of the weights 0..=i at position i.
+    ///        They must be finite, not negative and not decrease. They don't have to end at 1.0.
+    /// @return the prepared choice or the first index, which violates these conditions.
+    pub fn from_cumulative<C: Into<Vec<f64>>>(cumulative: C) -> Result<Self, NotCumulative> {
+        let prefix_sums = cumulative.into();
+        let mut previous = 0.0;
+
+        for (index, &sum) in prefix_sums.iter().enumerate() {
+            // an infinite total has no finite spoke gap
+            if !sum.is_finite() || sum < previous {
+                return Err(NotCumulative { index });
+            }
+
+            previous = sum;
+        }
+
+        Ok(PreparedChoice {
+            prefix_sums,
+            updated: None,
+        })
+    }
+
+    fn build(weights: &[f64], reporter: &mut Reporter) -> Result<Self, Aborted> {
+        let len = weights.len();
+        let rescaling = Rescaling::detect(weights);
+        let mut prefix_sums: Vec<f64> = Vec::with_capacity(len);
+        let mut sum: f64 = 0.0;
+
+        for (i, &weight) in weights.iter().enumerate() {
+            sum += rescale::rescaled(rescaling, weight);
+            prefix_sums.push(sum);
+            reporter.report(Phase::Summing, i + 1, len)?;
+        }
+
+        Ok(PreparedChoice {
+            prefix_sums,
+            updated: None,
+        })
+    }
+
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        self.prefix_sums.len()
+    }
+
+    /// Returns true, if there are no weights.
+    pub fn is_empty(&self) -> bool {
+        self.prefix_sums.is_empty()
+    }
+
+    /// Returns the current sum of all weights, in O(log n) after `update_many`.
+    pub fn total_weight(&self) -> f64 {
+        match self.updated {
+            Some(ref tree) => tree.total_weight(),
+            None => self.prefix_sums.last().cloned().unwrap_or(0.0),
+        }
+    }
+
+    /// Returns the prefix sums, i.e. the sum of the weights 0..=i at position i.
+    /// After `update_many`, they are computed in O(n) until the next `flush`.
+    pub fn prefix_sums(&self) -> Cow<'_, [f64]> {
+        match self.updated {
+            Some(ref tree) => {
+                let mut sum: f64 = 0.0;
+                Cow::Owned(tree.weights().iter().map(|&w| { sum += w; sum }).collect())
+            }
+            None => Cow::Borrowed(&self.prefix_sums),
+        }
+    }
+
+    /// Returns the weight of an index, up to rounding errors of the prefix sums.
+    pub fn weight(&self, i: usize) -> f64 {
+        match self.updated {
+            Some(ref tree) => tree.weight(i),
+            None if i == 0 => self.prefix_sums[0],
+            None => self.prefix_sums[i] - self.prefix_sums[i - 1],
+        }
+    }
+
+    /// Changes the weights of k indices in O(k * log n). The first update after the
+    /// construction or a `flush` moves the weights into a Fenwick tree in O(n).
+    ///
+    /// @invariant the weights must not be negative.
+    /// @param updates the indices with their new weights.
+    pub fn update_many(&mut self, updates: &[(usize, f64)]) {
+        if self.updated.is_none() {
+            let weights: Vec<f64> = (0..self.len()).map(|i| self.weight(i)).collect();
+            self.updated = Some(FenwickSampler::new(&weights));
+        }
+
+        if let Some(ref mut tree) = self.updated {
+            for &(i, weight) in updates {
+                tree.set_weight(i, weight);
+            }
+        }
+    }
+
+    /// Computes the prefix sums of the updated weights in O(n), so that the spokes are placed
+    /// on them again instead of the Fenwick tree.
+    pub fn flush(&mut self) {
+        if let Some(tree) = self.updated.take() {
+            self.prefix_sums = PreparedChoice::new(tree.weights()).prefix_sums;
+        }
+    }
+
+    /// Chooses one index by its weight.
+    /// @return None, if there are no weights.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        self.sample_n(rng, 1).pop()
+    }
+
+    /// Chooses n indices by their weights with stochastic universal sampling.
+    /// The spokes are placed by `Placement::Auto`.
+    /// @return n indices in ascending order or nothing, if there are no weights.
+    pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        self.sample_n_with(rng, n, Placement::Auto)
+    }
+
+    /// Chooses n indices by their weights with stochastic universal sampling.
+    /// @param placement the search of the index of every spoke.
+    /// @return n indices in ascending order or nothing, if there are no weights.
+    pub fn sample_n_with<R: Rng>(&self, rng: &mut R, n: usize, placement: Placement) -> Vec<usize> {
+        if self.is_empty() || n == 0 {
+            return Vec::new();
+        }
+
+        let placement = self.resolve(placement, n);
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = rng.next_f64();
+        let sum = self.total_weight();
+        let spoke_gap: f64 = sum / n as f64;
+
+        let mut i: usize = 0;
+        let mut choices: Vec<usize> = Vec::with_capacity(n);
+        let mut current_spoke: f64 = spin * spoke_gap;
+
+        while current_spoke < sum && choices.len() < n {
+            i = match (&self.updated, placement) {
+                (Some(tree), _) => tree.find(current_spoke),
+                (None, Placement::Linear) => self.walk(i, current_spoke),
+                (None, Placement::BinarySearch) => self.binary_search(i, current_spoke),
+                (None, _) => self.gallop(i, current_spoke),
+            };
+            choices.push(i);
+            current_spoke += spoke_gap;
+        }
+
+        // add this condition, because float leads to inaccurate
+        // calculations which can miss some samples
+        while choices.len() < n {
+            choices.push(i);
+        }
+
+        choices
+    }
+
+    /// Chooses n indices by their weights with stochastic universal sampling, as if the weights
+    /// of the excluded indices were zero, e.g. to skip the items, which a user has already seen.
+    /// The prefix sums aren't modified: the spokes are placed on a wheel without the excluded
+    /// weights and shifted past them, which costs O(m * log m + n * log n) for m exclusions.
+    ///
+    /// @param excluded indices, which are never chosen. Duplicates and indices out of bounds
+    ///                 are ignored.
+    /// @return n indices in ascending order or nothing, if no other index has a positive weight.
+    pub fn sample_excluding<R: Rng>(&self,
+                                    rng: &mut R,
+                                    n: usize,
+                                    excluded: &[usize])
+                                    -> Vec<usize> {
+        if self.is_empty() || n == 0 {
+            return Vec::new();
+        }
+
+        let mut excluded: Vec<usize> = excluded.iter()
+            .cloned()
+            .filter(|&e| e < self.len())
+            .collect();
+        excluded.sort_unstable();
+        excluded.dedup();
+
+        // the weights between the excluded indices
+        let mut sum: f64 = 0.0;
+        let mut start = 0.0;
+
+        for &e in &excluded {
+            sum += self.start_of(e) - start;
+            start = self.start_of(e + 1);
+        }
+
+        sum += self.total_weight() - start;
+
+        if sum <= 0.0 {
+            return Vec::new();
+        }
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = rng.next_f64();
+        let spoke_gap: f64 = sum / n as f64;
+
+        let mut i: usize = 0;
+        let mut j: usize = 0;
+        let mut shift: f64 = 0.0;
+        let mut choices: Vec<usize> = Vec::with_capacity(n);
+        let mut current_spoke: f64 = spin * spoke_gap;
+
+        while current_spoke < sum && choices.len() < n {
+            // the weight of index e covers the spokes in (start_of(e), prefix_sums[e]]
+            while j < excluded.len() && self.start_of(excluded[j]) < current_spoke + shift {
+                shift += self.weight(excluded[j]);
+                j += 1;
+            }
+
+            i = match self.updated {
+                Some(ref tree) => tree.find(current_spoke + shift),
+                None => self.binary_search(i, current_spoke + shift),
+            };
+
+            // rounding errors may point to an excluded index
+            if excluded.binary_search(&i).is_ok() {
+                i = (i + 1..self.len())
+                    .chain((0..i).rev())
+                    .find(|k| excluded.binary_search(k).is_err() && self.weight(*k) > 0.0)
+                    .unwrap_or(i);
+            }
+
+            choices.push(i);
+            current_spoke += spoke_gap;
+        }
+
+        // add this condition, because float leads to inaccurate
+        // calculations which can miss some samples
+        while choices.len() < n {
+            choices.push(i);
+        }
+
+        choices
+    }
+
+    /// Returns the sum of the weights 0..i.
+    fn start_of(&self, i: usize) -> f64 {
+        match self.updated {
+            Some(ref tree) => tree.prefix_sum(i),
+            None if i == 0 => 0.0,
+            None => self.prefix_sums[i - 1],
+        }
+    }
+
+    /// Replaces `Placement::Auto` by the cheapest placement for n spokes.
+    fn resolve(&self, placement: Placement, n: usize) -> Placement {
+        if placement != Placement::Auto {
+            return placement;
+        }
+
+        let len = self.len();
+        let log_len = (usize::BITS - len.leading_zeros()) as usize;
+
+        if n.saturating_mul(log_len) < len {
+            Placement::BinarySearch
+        } else if n >= len {
+            Placement::Linear
+        } else {
+            Placement::Gallop
+        }
+    }
+
+    /// Finds the first index from `start` on, whose prefix sum reaches the spoke,
+    /// by walking one index at a time.
+    fn walk(&self, start: usize, spoke: f64) -> usize {
+        let last = self.prefix_sums.len() - 1;
+        let mut i = start;
+
+        while i < last && self.prefix_sums[i] < spoke {
+            i += 1;
+        }
+
+        i
+    }
+
+    /// Finds the first index from `start` on, whose prefix sum reaches the spoke,
+    /// by binary search over all remaining indices.
+    fn binary_search(&self, start: usize, spoke: f64) -> usize {
+        let last = self.prefix_sums.len() - 1;
+
+        start + self.prefix_sums[start..last].partition_point(|&s| s < spoke)
+    }
+
+    /// Finds the first index from `start` on, whose prefix sum reaches the spoke,
+    /// by exponential search followed by binary search.
+    fn gallop(&self, start: usize, spoke: f64) -> usize {
+        let prefix_sums = &self.prefix_sums;
+        let last = prefix_sums.len() - 1;
+
+        if prefix_sums[start] >= spoke {
+            return start;
+        }
+
+        // invariant: prefix_sums[low] < spoke
+        let mut low = start;
+        let mut step: usize = 1;
+
+        while low + step < last && prefix_sums[low + step] < spoke {
+            low += step;
+            step *= 2;
+        }
+
+        let high = (low + step).min(last);
+
+        // the first index in (low, high], whose prefix sum reaches the spoke,
+        // or the last index, if rounding errors let all of them fall short
+        low + 1 + prefix_sums[low + 1..high].partition_point(|&s| s < spoke)
+    }
+}
+
+#[cfg(feature = "mmap")]
+pub use self::mapped::{MappedPreparedChoice, DEFAULT_BLOCK_SIZE};
+
+#[cfg(feature = "mmap")]
+mod mapped {
+    use std::convert::TryInto;
+    use std::fs::File;
+    use std::io::{self, BufWriter, Write};
+    use std::path::Path;
+
+    use memmap2::Mmap;
+    use rand::Rng;
+
+    use crate::core::rescale::{self, Rescaling};
+
+    /// The number of weights per block of the prefix index.
+    pub const DEFAULT_BLOCK_SIZE: usize = 4096;
+
+    /// A prepared choice over a weight file, which is mapped read-only, for weights that don't
+    /// fit into memory. The file contains the weights as `f64` in little endian without a header.
+    ///
+    /// Only the prefix sum at the end of every block of weights is kept in memory. A spoke is
+    /// placed by binary search over the blocks and a walk within its block, so a draw reads only
+    /// the blocks, which it hits. The same indices as by `PreparedChoice` are chosen, because
+    /// `create` writes the weights after rescaling them like it.
+    /// The file must not be modified while it is mapped.
+    #[derive(Debug)]
+    pub struct MappedPreparedChoice {
+        map: Mmap,
+        len: usize,
+        block_size: usize,
+        /// block_ends[b] is the sum of all weights up to the end of block b.
+        block_ends: Vec<f64>,
+    }
+
+    impl MappedPreparedChoice {
+        /// Writes the weights to path and maps the file. Tiny and subnormal weights are
+        /// rescaled before they are written, see `rescale`. Files, which are opened, are taken
+        /// as given.
+        pub fn create<P: AsRef<Path>>(path: P, weights: &[f64]) -> io::Result<Self> {
+            let rescaling = Rescaling::detect(weights);
+            let mut writer = BufWriter::new(File::create(path.as_ref())?);
+
+            for &weight in weights {
+                writer.write_all(&rescale::rescaled(rescaling, weight).to_le_bytes())?;
+            }
+
+            writer.flush()?;
+            drop(writer);
+
+            MappedPreparedChoice::open(path)
+        }
+
+        /// Maps a weight file and builds the prefix index with `DEFAULT_BLOCK_SIZE`.
+        pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+            MappedPreparedChoice::open_with_block_size(path, DEFAULT_BLOCK_SIZE)
+        }
+
+        /// Maps a weight file and builds the prefix index in one pass over the file.
+        /// @param block_size the number of weights per block. Smaller blocks need more memory
+        ///        for the index, but less reading per spoke. It must be greater than zero.
+        /// @return an error of kind `InvalidData`, if the file size isn't a multiple of 8.
+        pub fn open_with_block_size<P>(path: P, block_size: usize) -> io::Result<Self>
+            where P: AsRef<Path>
+        {
+            assert!(block_size > 0, "block size must be greater than zero");
+
+            let file = File::open(path)?;
+
+            // Safety: the file is mapped read-only and the documentation forbids modifying it
+            // while it is mapped.
+            let map = unsafe { Mmap::map(&file)? };
+
+            if map.len() % 8 != 0 {
+                return Err(io::Error::new(io::ErrorKind::InvalidData,
+                                          "weight file size must be a multiple of 8 bytes"));
+            }
+
+            let len = map.len() / 8;
+            let mut block_ends: Vec<f64> = Vec::with_capacity(len.div_ceil(block_size));
+            let mut sum: f64 = 0.0;
+
+            for block in map.chunks(8 * block_size) {
+                for bytes in block.chunks(8) {
+                    sum += f64::from_le_bytes(bytes.try_into().unwrap());
+                }
+                block_ends.push(sum);
+            }
+
+            Ok(MappedPreparedChoice {
+                map,
+                len,
+                block_size,
+                block_ends,
+            })
+        }
+
+        /// Returns the number of weights.
+        pub fn len(&self) -> usize {
+            self.len
+        }
+
+        /// Returns true, if the file contains no weights.
+        pub fn is_empty(&self) -> bool {
+            self.len == 0
+        }
+
+        /// Returns the number of weights per block of the prefix index.
+        pub fn block_size(&self) -> usize {
+            self.block_size
+        }
+
+        /// Returns the sum of all weights.
+        pub fn total_weight(&self) -> f64 {
+            self.block_ends.last().cloned().unwrap_or(0.0)
+        }
+
+        /// Reads the weight of an index from the file.
+        pub fn weight(&self, i: usize) -> f64 {
+            f64::from_le_bytes(self.map[8 * i..8 * i + 8].try_into().unwrap())
+        }
+
+        /// Chooses one index by its weight.
+        /// @return None, if there are no weights.
+        pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+            self.sample_n(rng, 1).pop()
+        }
+
+        /// Chooses n indices by their weights with stochastic universal sampling.
+        /// @return n indices in ascending order or nothing, if there are no weights.
+        pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+            if self.is_empty() || n == 0 {
+                return Vec::new();
+            }
+
+            // next_f64() ∈ [0.0, 1.0)
+            let spin = rng.next_f64();
+            let sum = self.total_weight();
+            let spoke_gap: f64 = sum / n as f64;
+            let last = self.len - 1;
+            let last_block = self.block_ends.len() - 1;
+
+            let mut block: usize = 0;
+            let mut i: usize = 0;
+            let mut accumulated_weights = self.weight(0);
+            let mut choices: Vec<usize> = Vec::with_capacity(n);
+            let mut current_spoke: f64 = spin * spoke_gap;
+
+            while current_spoke < sum && choices.len() < n {
+                let spoke = current_spoke;
+
+                if self.block_ends[block] < spoke {
+                    // jump to the first block, which reaches the spoke, and continue the running
+                    // sum from the end of the previous block, which keeps the sums identical
+                    block += 1 + self.block_ends[block + 1..last_block]
+                        .partition_point(|&end| end < spoke);
+                    i = block * self.block_size;
+                    accumulated_weights = self.block_ends[block - 1] + self.weight(i);
+                }
+
+                while accumulated_weights < spoke && i < last {
+                    i += 1;
+                    accumulated_weights += self.weight(i);
+                }
+
+                choices.push(i);
+                current_spoke += spoke_gap;
+            }
+
+            // add this condition, because float leads to inaccurate
+            // calculations which can miss some samples
+            while choices.len() < n {
+                choices.push(i);
+            }
+
+            choices
+        }
+    }
+}
@@ -0,0 +1,204 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Bandits
+//! A/B-style optimizations choose between arms, e.g. variants of a page, and learn from their
+//! rewards which arm is best. A `BetaBandit` keeps a Beta(α, β) posterior of the success rate of
+//! every arm. `select_arm` draws one success rate from every posterior and chooses the arm with
+//! the greatest one, so that every arm is chosen with the probability, that it is the best one
+//! (Thompson, "On the likelihood that one unknown probability exceeds another in view of the
+//! evidence of two samples", 1933). `update` adds the reward of the arm to its posterior.
+//!
+//! If the rewards aren't drawn from fixed rates, but chosen by an adversary, `Exp3` (Auer,
+//! Cesa-Bianchi, Freund and Schapire, "The Nonstochastic Multiarmed Bandit Problem", 2002) keeps
+//! multiplicative weights of the arms and draws them mixed with the uniform distribution like
+//! `RandomChoice::with_exploration`. `update` lowers the weight of an arm by its estimated loss.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::strategies::bandit::BetaBandit;
+//! use rand::Rng;
+//!
+//! # fn main() {
+//! let conversion_rates = [0.04, 0.05, 0.12];
+//! let mut bandit = BetaBandit::new(conversion_rates.len());
+//! let mut rng = rand::thread_rng();
+//!
+//! for _ in 0..2000 {
+//!     let arm = bandit.select_arm(&mut rng).unwrap();
+//!     let converted = rng.next_f64() < conversion_rates[arm];
+//!     bandit.update(arm, if converted { 1.0 } else { 0.0 });
+//! }
+//!
+//! println!("estimated conversion rates: {:?}", bandit.means());
+//! # }
+//! ```
+
+use rand::distributions::{Gamma, IndependentSample};
+use rand::Rng;
+
+use crate::strategies::exploration::explored_probabilities;
+
+/// The Beta posteriors of the success rates of the arms of a bandit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BetaBandit {
+    /// α and β of every arm.
+    posteriors: Vec<(f64, f64)>,
+}
+
+impl BetaBandit {
+    /// Creates a bandit with the uniform prior Beta(1, 1) for every arm.
+    pub fn new(arms: usize) -> Self {
+        BetaBandit::with_prior(arms, 1.0, 1.0)
+    }
+
+    /// Creates a bandit with the same prior Beta(alpha, beta) for every arm.
+    /// @invariant alpha and beta must be positive.
+    pub fn with_prior(arms: usize, alpha: f64, beta: f64) -> Self {
+        assert!(alpha > 0.0 && beta > 0.0, "alpha and beta must be positive");
+
+        BetaBandit { posteriors: vec![(alpha, beta); arms] }
+    }
+
+    /// Returns the number of arms.
+    pub fn len(&self) -> usize {
+        self.posteriors.len()
+    }
+
+    /// Returns true, if the bandit has no arms.
+    pub fn is_empty(&self) -> bool {
+        self.posteriors.is_empty()
+    }
+
+    /// Returns α and β of the posterior of an arm.
+    pub fn posterior(&self, arm: usize) -> (f64, f64) {
+        self.posteriors[arm]
+    }
+
+    /// Returns the expected success rate α / (α + β) of every arm.
+    pub fn means(&self) -> Vec<f64> {
+        self.posteriors.iter().map(|&(alpha, beta)| alpha / (alpha + beta)).collect()
+    }
+
+    /// Adds an arm with the prior Beta(1, 1).
+    /// @return the index of the new arm.
+    pub fn add_arm(&mut self) -> usize {
+        self.posteriors.push((1.0, 1.0));
+        self.posteriors.len() - 1
+    }
+
+    /// Chooses an arm by Thompson sampling.
+    /// @return the arm, whose drawn success rate is the greatest, or None, if there are no arms.
+    pub fn select_arm<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        let mut best: Option<(usize, f64)> = None;
+
+        for (arm, &(alpha, beta)) in self.posteriors.iter().enumerate() {
+            let rate = sample_beta(rng, alpha, beta);
+
+            if best.is_none_or(|(_, best_rate)| rate > best_rate) {
+                best = Some((arm, rate));
+            }
+        }
+
+        best.map(|(arm, _)| arm)
+    }
+
+    /// Adds the reward of an arm to its posterior: α += reward and β += 1 - reward.
+    /// @param reward 1.0 for a success, 0.0 for a failure or a fractional reward ∈ [0.0, 1.0].
+    pub fn update(&mut self, arm: usize, reward: f64) {
+        assert!((0.0..=1.0).contains(&reward), "reward must be in [0.0, 1.0]");
+
+        let posterior = &mut self.posteriors[arm];
+        posterior.0 += reward;
+        posterior.1 += 1.0 - reward;
+    }
+}
+
+/// Draws from Beta(alpha, beta) as X / (X + Y) with X ~ Gamma(alpha, 1) and Y ~ Gamma(beta, 1).
+fn sample_beta<R: Rng>(rng: &mut R, alpha: f64, beta: f64) -> f64 {
+    let x = Gamma::new(alpha, 1.0).ind_sample(rng);
+    let y = Gamma::new(beta, 1.0).ind_sample(rng);
+
+    if x + y > 0.0 {
+        x / (x + y)
+    } else {
+        // both draws underflowed for tiny shapes, the mean is the best guess
+        alpha / (alpha + beta)
+    }
+}
+
+/// The adversarial bandit Exp3 with losses ∈ [0.0, 1.0].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exp3 {
+    weights: Vec<f64>,
+    gamma: f64,
+}
+
+impl Exp3 {
+    /// Creates a bandit, whose arms have the same weight.
+    /// @param gamma the share of the uniform distribution ∈ (0.0, 1.0], which is also the
+    ///              learning rate. (2 / 3 * ln K / T)^(1/2) is a good choice for K arms and
+    ///              T rounds.
+    pub fn new(arms: usize, gamma: f64) -> Self {
+        assert!(gamma > 0.0 && gamma <= 1.0, "gamma must be in (0.0, 1.0]");
+
+        Exp3 {
+            weights: vec![1.0; arms],
+            gamma,
+        }
+    }
+
+    /// Returns the number of arms.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Returns true, if the bandit has no arms.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Returns gamma, see `new`.
+    pub fn gamma(&self) -> f64 {
+        self.gamma
+    }
+
+    /// Returns the weights of the arms, scaled so that the greatest one is 1.0.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the probability of every arm: (1 - γ) * w_i / Σ_k w_k + γ / K.
+    pub fn probabilities(&self) -> Vec<f64> {
+        explored_probabilities(&self.weights, self.gamma)
+    }
+
+    /// Chooses an arm by its probability.
+    /// @return the arm or None, if there are no arms.
+    pub fn draw<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        let probabilities = self.probabilities();
+        let sum: f64 = probabilities.iter().fold(0.0, |acc, &p| acc + p);
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = rng.next_f64();
+        crate::sus_indices(probabilities.len(), 1, spin, sum, |i| probabilities[i]).pop()
+    }
+
+    /// Lowers the weight of the drawn arm by its loss divided by its probability, i.e. the
+    /// unbiased estimate of the loss over all arms: w_arm *= exp(-γ / K * loss / p_arm).
+    /// @param loss the loss of the arm in this round ∈ [0.0, 1.0].
+    pub fn update(&mut self, arm: usize, loss: f64) {
+        assert!((0.0..=1.0).contains(&loss), "loss must be in [0.0, 1.0]");
+
+        let probability = self.probabilities()[arm];
+        let rate = self.gamma / self.weights.len() as f64;
+        self.weights[arm] *= (-rate * loss / probability).exp();
+
+        // the weights only shrink, so they are rescaled before they underflow
+        let max = self.weights.iter().cloned().fold(0.0, f64::max);
+        for weight in self.weights.iter_mut() {
+            *weight /= max;
+        }
+    }
+}
@@ -0,0 +1,86 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Best-of-n Sampling
+//! Retrieval pipelines often sample n candidates by their weights and then keep the one,
+//! which a more expensive scoring function ranks highest. `choose_best_of` does both in one pass
+//! over the lazily chosen spokes without collecting the candidates. Since the spokes are chosen
+//! in ascending order, a candidate, which is chosen several times, is scored only once.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//!
+//! # fn main() {
+//! let documents = vec!["hi", "this", "is", "a", "test!"];
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! // the longest of 3 candidates
+//! let best = random_choice().choose_best_of(&documents, &weights, 3, |d| d.len() as f64);
+//!
+//! assert!(best.is_some());
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n candidates by their weights and returns the one with the greatest score.
+    /// See `choose_best_index_of`.
+    pub fn choose_best_of<'a, T, F>(&mut self,
+                                    samples: &'a [T],
+                                    weights: &[f64],
+                                    n: usize,
+                                    mut score: F)
+                                    -> Option<&'a T>
+        where F: FnMut(&T) -> f64
+    {
+        if samples.is_empty() {
+            return None;
+        }
+
+        self.choose_best_index_of(weights, n, |i| score(&samples[i]))
+            .map(|i| &samples[i])
+    }
+
+    /// Chooses n candidate indices by their weights and returns the one with the greatest score.
+    /// The candidates are the indices, which `random_choice_indices_f64` chooses.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param n Number of candidates.
+    /// @param score scores a candidate index. It is called once per distinct candidate.
+    /// @return the candidate with the greatest score, the first one on ties, or nothing,
+    ///         if there are no candidates or all scores are NaN.
+    pub fn choose_best_index_of<F>(&mut self,
+                                   weights: &[f64],
+                                   n: usize,
+                                   mut score: F)
+                                   -> Option<usize>
+        where F: FnMut(usize) -> f64
+    {
+        let mut best: Option<(usize, f64)> = None;
+        let mut previous: Option<usize> = None;
+
+        for i in self.spokes_f64(weights, n) {
+            if previous == Some(i) {
+                continue;
+            }
+
+            previous = Some(i);
+            let value = score(i);
+
+            if value.is_nan() {
+                continue;
+            }
+
+            if best.is_none_or(|(_, best_value)| value > best_value) {
+                best = Some((i, value));
+            }
+        }
+
+        best.map(|(i, _)| i)
+    }
+}
@@ -0,0 +1,74 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Bitmask Output
+//! Some consumers only need to know which indices were chosen, not how often, e.g. which shards
+//! to probe in this round. `random_choice_bitmask` marks every index, which was chosen at least
+//! once, in a bitmask of `u64` words: index i is bit `i % 64` of word `i / 64`.
+//! The chosen indices are never collected, so the memory usage is one bit per weight,
+//! independent of n.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//! use random_choice::strategies::bitmask;
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! let mask = random_choice().random_choice_bitmask(&weights, 3);
+//!
+//! for shard in bitmask::selected_indices(&mask) {
+//!     assert!(bitmask::is_selected(&mask, shard));
+//!     print!("{}, ", shard);
+//! }
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+/// The number of indices per word of a bitmask.
+pub const WORD_BITS: usize = 64;
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n indices by their weights and marks them in a bitmask.
+    /// It marks the indices, which `random_choice_indices_f64` chooses.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param n Number of randomly chosen indices by weight.
+    /// @return ceil(weights.len() / 64) words, in which the chosen indices are set.
+    pub fn random_choice_bitmask(&mut self, weights: &[f64], n: usize) -> Vec<u64> {
+        let mut mask = vec![0u64; weights.len().div_ceil(WORD_BITS)];
+
+        for index in self.spokes_f64(weights, n) {
+            mask[index / WORD_BITS] |= 1 << (index % WORD_BITS);
+        }
+
+        mask
+    }
+}
+
+/// Returns true, if the index is set in the bitmask.
+pub fn is_selected(mask: &[u64], index: usize) -> bool {
+    mask.get(index / WORD_BITS)
+        .is_some_and(|word| word & (1 << (index % WORD_BITS)) != 0)
+}
+
+/// Returns the indices, which are set in the bitmask, in ascending order.
+pub fn selected_indices(mask: &[u64]) -> Vec<usize> {
+    let mut indices = Vec::new();
+
+    for (w, &word) in mask.iter().enumerate() {
+        let mut bits = word;
+
+        while bits != 0 {
+            indices.push(w * WORD_BITS + bits.trailing_zeros() as usize);
+            bits &= bits - 1;
+        }
+    }
+
+    indices
+}
@@ -0,0 +1,124 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Budgeted Sampling
+//! Crawl schedulers and query samplers don't know how many items they can afford, they know
+//! how much they can spend. With budgeted sampling, every item has a cost, and items are drawn
+//! one after another by their weights, until the next drawn item costs more than the budget,
+//! which is left. The picks and the remaining budget are returned.
+//!
+//! `random_choice_budgeted_f64` draws independently, i.e. an item can be picked again, and
+//! `random_choice_budgeted_without_replacement_f64` picks every item at most once in the order
+//! of Efraimidis and Spirakis, see `random_choice_indices_without_replacement_f64`.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//!
+//! # fn main() {
+//! let priorities: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//! let fetch_costs: Vec<f64> = vec![3.0, 1.0, 4.0, 1.0, 5.0];
+//!
+//! let crawl = random_choice()
+//!     .random_choice_budgeted_without_replacement_f64(&priorities, &fetch_costs, 8.0);
+//!
+//! let spent: f64 = crawl.indices.iter().map(|&i| fetch_costs[i]).sum();
+//! assert_eq!(spent + crawl.remaining, 8.0);
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::prepared::fenwick::FenwickSampler;
+use crate::RandomChoice;
+
+/// The picks of a budgeted draw.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BudgetedSample {
+    /// The picked indices in the order of the draw.
+    pub indices: Vec<usize>,
+    /// The budget minus the costs of the picks.
+    pub remaining: f64,
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Draws indices independently by their weights, until the next index costs more than the
+    /// budget, which is left, or its cost is too small to reduce the budget in f64.
+    ///
+    /// @invariant weights must not be negative and costs must be positive.
+    /// @param weights Weights of the indices 0..weights.len().
+    /// @param costs the cost of every index.
+    /// @param budget the sum of the costs, which may be spent.
+    /// @return the picks and the remaining budget. Nothing is picked, if no weight is positive.
+    pub fn random_choice_budgeted_f64(&mut self,
+                                      weights: &[f64],
+                                      costs: &[f64],
+                                      budget: f64)
+                                      -> BudgetedSample {
+        check_costs(weights, costs);
+
+        let sampler = FenwickSampler::new(weights);
+        let mut sample = BudgetedSample {
+            indices: Vec::new(),
+            remaining: budget,
+        };
+
+        while let Some(i) = sampler.sample(&mut self.rng) {
+            // a cost below the precision of the remaining budget would never exhaust it
+            if sample.remaining - costs[i] == sample.remaining || !sample.pick(i, costs[i]) {
+                break;
+            }
+        }
+
+        sample
+    }
+
+    /// Draws distinct indices by their weights, until the next index costs more than the
+    /// budget, which is left, or all indices with a positive weight are picked.
+    ///
+    /// @invariant weights must not be negative and costs must be positive.
+    /// @param weights Weights of the indices 0..weights.len().
+    /// @param costs the cost of every index.
+    /// @param budget the sum of the costs, which may be spent.
+    /// @return the picks and the remaining budget.
+    pub fn random_choice_budgeted_without_replacement_f64(&mut self,
+                                                          weights: &[f64],
+                                                          costs: &[f64],
+                                                          budget: f64)
+                                                          -> BudgetedSample {
+        check_costs(weights, costs);
+
+        let order = self.random_choice_indices_without_replacement_f64(weights, weights.len());
+        let mut sample = BudgetedSample {
+            indices: Vec::new(),
+            remaining: budget,
+        };
+
+        for i in order {
+            if !sample.pick(i, costs[i]) {
+                break;
+            }
+        }
+
+        sample
+    }
+}
+
+impl BudgetedSample {
+    /// Picks the index, if the remaining budget covers its cost.
+    fn pick(&mut self, i: usize, cost: f64) -> bool {
+        if cost > self.remaining {
+            return false;
+        }
+
+        self.indices.push(i);
+        self.remaining -= cost;
+        true
+    }
+}
+
+fn check_costs(weights: &[f64], costs: &[f64]) {
+    assert_eq!(weights.len(), costs.len(), "every weight needs a cost");
+    // a free item would be picked forever
+    assert!(costs.iter().all(|&cost| cost > 0.0), "costs must be positive");
+}
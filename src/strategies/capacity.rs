@@ -0,0 +1,145 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Sampling with Capacities
+//! For ticket allocation or inventories, every index has a weight and a remaining capacity, e.g.
+//! the free seats of a time slot. A selection decrements the capacity of the chosen index
+//! atomically, and exhausted indices are skipped, until their capacity is restocked.
+//! While it has capacity, an index is chosen proportionally to its weight.
+//!
+//! A `CapacitySampler` can be shared between threads: the capacities are atomic counters and the
+//! weights of exhausted indices are removed from a `FenwickSampler` in O(log n).
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::strategies::capacity::CapacitySampler;
+//!
+//! # fn main() {
+//! let slots = vec!["9:00", "10:00", "11:00"];
+//! let sampler = CapacitySampler::new(&[3.0, 2.0, 1.0], &[2, 1, 1]);
+//! let mut rng = rand::thread_rng();
+//!
+//! let mut booked = Vec::new();
+//!
+//! while let Some(slot) = sampler.sample(&mut rng) {
+//!     booked.push(slots[slot]);
+//! }
+//!
+//! assert_eq!(booked.len(), 4);
+//! # }
+//! ```
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use rand::Rng;
+
+use crate::prepared::fenwick::FenwickSampler;
+
+/// Chooses indices by their weights and decrements their remaining capacities.
+#[derive(Debug)]
+pub struct CapacitySampler {
+    weights: Vec<f64>,
+    capacities: Vec<AtomicUsize>,
+    /// The weights of the indices, which have capacity left.
+    available: Mutex<FenwickSampler>,
+}
+
+impl CapacitySampler {
+    /// @param weights the weights of the indices. They must not be negative.
+    /// @param capacities the number of times every index can be chosen.
+    ///        It must have the same length as weights.
+    pub fn new(weights: &[f64], capacities: &[usize]) -> Self {
+        assert_eq!(weights.len(),
+                   capacities.len(),
+                   "every weight needs a capacity");
+
+        let available: Vec<f64> = weights.iter()
+            .zip(capacities)
+            .map(|(&weight, &capacity)| if capacity > 0 { weight } else { 0.0 })
+            .collect();
+
+        CapacitySampler {
+            weights: weights.to_vec(),
+            capacities: capacities.iter().map(|&capacity| AtomicUsize::new(capacity)).collect(),
+            available: Mutex::new(FenwickSampler::new(&available)),
+        }
+    }
+
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Returns true, if there are no indices.
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Returns the remaining capacity of an index.
+    pub fn remaining(&self, i: usize) -> usize {
+        self.capacities[i].load(Ordering::SeqCst)
+    }
+
+    /// Returns the sum of the remaining capacities.
+    pub fn total_remaining(&self) -> usize {
+        self.capacities.iter().map(|capacity| capacity.load(Ordering::SeqCst)).sum()
+    }
+
+    /// Chooses one index with remaining capacity by its weight and decrements its capacity.
+    /// @return None, if every index with a positive weight is exhausted.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        loop {
+            let i = self.lock().sample(rng)?;
+
+            let decremented = self.capacities[i]
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |c| c.checked_sub(1));
+
+            match decremented {
+                Ok(1) => {
+                    self.sync(i);
+                    return Some(i);
+                }
+                Ok(_) => return Some(i),
+                // another thread took the last unit in the meantime
+                Err(_) => self.sync(i),
+            }
+        }
+    }
+
+    /// Chooses up to n indices one after another, see `sample`.
+    /// @return less than n indices, if the capacities are exhausted before.
+    pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<usize> {
+        let mut choices: Vec<usize> = Vec::with_capacity(n);
+
+        while choices.len() < n {
+            match self.sample(rng) {
+                Some(i) => choices.push(i),
+                None => break,
+            }
+        }
+
+        choices
+    }
+
+    /// Increases the remaining capacity of an index, e.g. after a cancellation.
+    pub fn restock(&self, i: usize, amount: usize) {
+        if self.capacities[i].fetch_add(amount, Ordering::SeqCst) == 0 && amount > 0 {
+            self.sync(i);
+        }
+    }
+
+    /// Sets the weight of an index in the tree according to its current capacity.
+    /// It runs under the lock, so the last synchronization sees the latest capacity.
+    fn sync(&self, i: usize) {
+        let mut available = self.lock();
+        let weight = if self.remaining(i) > 0 { self.weights[i] } else { 0.0 };
+        available.set_weight(i, weight);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, FenwickSampler> {
+        // the tree is consistent after every update, so a poisoned lock can be used further
+        self.available.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
@@ -0,0 +1,161 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Categorical Distributions
+//! Configuration files often describe a categorical distribution as labels with weights, e.g.
+//! `{ sunny: 6, cloudy: 3, rainy: 1 }` in YAML or TOML. A `Categorical` keeps the labels and
+//! their weights together instead of in parallel slices: it samples labels and looks up their
+//! probabilities by label.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::strategies::categorical::Categorical;
+//!
+//! # fn main() {
+//! let weather = Categorical::new(&[("sunny", 6.0), ("cloudy", 3.0), ("rainy", 1.0)]).unwrap();
+//! let mut rng = rand::thread_rng();
+//!
+//! let today = weather.sample(&mut rng).unwrap();
+//! assert!(weather.log_prob(today).unwrap() <= 0.0);
+//! assert_eq!(weather.probability("rainy"), Some(0.1));
+//! assert_eq!(weather.log_prob("snowy"), None);
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use rand::Rng;
+
+use crate::prepared::PreparedChoice;
+
+/// The errors of the construction of a `Categorical`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CategoricalError {
+    /// The label appears more than once.
+    DuplicateLabel(String),
+    /// The weight of the label is negative, infinite or NaN.
+    InvalidWeight(String, f64),
+}
+
+impl fmt::Display for CategoricalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CategoricalError::DuplicateLabel(ref label) => {
+                write!(f, "label {:?} appears more than once", label)
+            }
+            CategoricalError::InvalidWeight(ref label, weight) => {
+                write!(f,
+                       "weight {} of label {:?} is not a finite, non-negative number",
+                       weight,
+                       label)
+            }
+        }
+    }
+}
+
+impl Error for CategoricalError {}
+
+/// A distribution over labels, which are chosen by their weights.
+#[derive(Debug, Clone)]
+pub struct Categorical {
+    labels: Vec<String>,
+    weights: Vec<f64>,
+    /// The position of every label in `labels`.
+    positions: HashMap<String, usize>,
+    prepared: PreparedChoice,
+}
+
+impl Categorical {
+    /// @param pairs the labels with their weights. They don't have to sum up to 1.0.
+    /// @return the distribution or the first label, which is duplicate or has an invalid weight.
+    pub fn new<S: AsRef<str>>(pairs: &[(S, f64)]) -> Result<Self, CategoricalError> {
+        let mut labels: Vec<String> = Vec::with_capacity(pairs.len());
+        let mut weights: Vec<f64> = Vec::with_capacity(pairs.len());
+        let mut positions: HashMap<String, usize> = HashMap::with_capacity(pairs.len());
+
+        for (label, weight) in pairs {
+            let label = label.as_ref();
+
+            if !weight.is_finite() || *weight < 0.0 {
+                return Err(CategoricalError::InvalidWeight(label.to_string(), *weight));
+            }
+
+            if positions.insert(label.to_string(), labels.len()).is_some() {
+                return Err(CategoricalError::DuplicateLabel(label.to_string()));
+            }
+
+            labels.push(label.to_string());
+            weights.push(*weight);
+        }
+
+        let prepared = PreparedChoice::new(&weights);
+
+        Ok(Categorical {
+            labels,
+            weights,
+            positions,
+            prepared,
+        })
+    }
+
+    /// Returns the number of labels.
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    /// Returns true, if there are no labels.
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Returns the labels in the order of their construction.
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    /// Returns the weights in the order of the labels.
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Returns the sum of the weights.
+    pub fn total_weight(&self) -> f64 {
+        self.prepared.total_weight()
+    }
+
+    /// Returns the weight of a label or None, if the label is unknown.
+    pub fn weight(&self, label: &str) -> Option<f64> {
+        self.positions.get(label).map(|&i| self.weights[i])
+    }
+
+    /// Returns the probability of a label or None, if the label is unknown.
+    /// If all weights are zero, every probability is NaN.
+    pub fn probability(&self, label: &str) -> Option<f64> {
+        self.weight(label).map(|weight| weight / self.total_weight())
+    }
+
+    /// Returns the natural logarithm of the probability of a label, e.g. for the likelihood of
+    /// observations, or None, if the label is unknown. A weight of zero gives -∞.
+    pub fn log_prob(&self, label: &str) -> Option<f64> {
+        self.weight(label).map(|weight| weight.ln() - self.total_weight().ln())
+    }
+
+    /// Chooses one label by its weight.
+    /// @return None, if there are no labels.
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> Option<&str> {
+        self.prepared.sample(rng).map(|i| self.labels[i].as_str())
+    }
+
+    /// Chooses n labels by their weights with stochastic universal sampling.
+    /// @return n labels in the order of the labels or nothing, if there are no labels.
+    pub fn sample_n<R: Rng>(&self, rng: &mut R, n: usize) -> Vec<&str> {
+        self.prepared
+            .sample_n(rng, n)
+            .into_iter()
+            .map(|i| self.labels[i].as_str())
+            .collect()
+    }
+}
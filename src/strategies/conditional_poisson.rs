@@ -0,0 +1,390 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Conditional Poisson Sampling
+//! A fixed-size sampling design without replacement with unequal probabilities:
+//! every index i is included independently with its working probability p_i,
+//! conditioned on the sample having exactly k members. Equivalently, a subset S of size k
+//! is chosen with a probability proportional to the product of the odds p_i / (1 - p_i)
+//! of its members, see `SubsetWeighting::Product`.
+//!
+//! The inclusion probabilities of the design differ from the working probabilities.
+//! `calibrate` finds the working probabilities for given inclusion probabilities
+//! by the fixed-point iteration of Chen, Dempster and Liu (1994), which is applied to the logits.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//! use random_choice::strategies::conditional_poisson::ConditionalPoisson;
+//!
+//! # fn main() {
+//! // e.g. proportional to the size of the units, summing up to the sample size 2
+//! let inclusion_probabilities: Vec<f64> = vec![0.2, 0.3, 0.5, 0.4, 0.6];
+//!
+//! let design = ConditionalPoisson::calibrated(&inclusion_probabilities, 1e-10, 1000).unwrap();
+//! let sample = design.sample(&mut random_choice());
+//!
+//! assert_eq!(sample.len(), 2);
+//! # }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use rand::Rng;
+
+use crate::strategies::subsets::SuffixPolynomials;
+use crate::RandomChoice;
+
+/// Errors of the construction and the calibration of a conditional Poisson design.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionalPoissonError {
+    /// The probability at this index is not within [0, 1].
+    InvalidProbability(usize),
+    /// The inclusion probabilities don't sum up to an integer sample size.
+    NonIntegerSampleSize(f64),
+    /// The sample size is greater than the number of indices with a positive probability.
+    InfeasibleSampleSize(usize),
+    /// The calibration didn't reach the tolerance within the maximum number of iterations.
+    NotConverged {
+        /// The number of iterations, which were run.
+        iterations: usize,
+        /// The greatest absolute difference to a target inclusion probability.
+        max_error: f64,
+    },
+}
+
+impl fmt::Display for ConditionalPoissonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ConditionalPoissonError::InvalidProbability(index) => {
+                write!(f, "probability at index {} is not within [0, 1]", index)
+            }
+            ConditionalPoissonError::NonIntegerSampleSize(sum) => {
+                write!(f, "inclusion probabilities sum up to {}, which is no integer", sum)
+            }
+            ConditionalPoissonError::InfeasibleSampleSize(k) => {
+                write!(f, "less than {} indices have a positive probability", k)
+            }
+            ConditionalPoissonError::NotConverged { iterations, max_error } => {
+                write!(f,
+                       "calibration didn't converge within {} iterations, the error is {}",
+                       iterations,
+                       max_error)
+            }
+        }
+    }
+}
+
+impl Error for ConditionalPoissonError {}
+
+/// A conditional Poisson sampling design of fixed size k.
+#[derive(Debug, Clone)]
+pub struct ConditionalPoisson {
+    len: usize,
+    k: usize,
+    /// Indices with a working probability of one, which are always included.
+    certain: Vec<usize>,
+    /// Indices with a working probability within (0, 1).
+    uncertain: Vec<usize>,
+    /// The odds p / (1 - p) of the uncertain indices.
+    odds: Vec<f64>,
+    polynomials: SuffixPolynomials,
+}
+
+impl ConditionalPoisson {
+    /// Creates the design from working probabilities.
+    ///
+    /// @param working_probabilities the probabilities p_i ∈ [0, 1] of the independent draws.
+    ///        Indices with p_i = 1 are always included, indices with p_i = 0 never.
+    /// @param k the sample size
+    pub fn new(working_probabilities: &[f64], k: usize) -> Result<Self, ConditionalPoissonError> {
+        let mut certain: Vec<usize> = Vec::new();
+        let mut uncertain: Vec<usize> = Vec::new();
+        let mut odds: Vec<f64> = Vec::new();
+
+        for (i, &p) in working_probabilities.iter().enumerate() {
+            if !(0.0..=1.0).contains(&p) {
+                return Err(ConditionalPoissonError::InvalidProbability(i));
+            }
+
+            if p == 1.0 {
+                certain.push(i);
+            } else if p > 0.0 {
+                uncertain.push(i);
+                odds.push(p / (1.0 - p));
+            }
+        }
+
+        if certain.len() > k || certain.len() + uncertain.len() < k {
+            return Err(ConditionalPoissonError::InfeasibleSampleSize(k));
+        }
+
+        let polynomials = SuffixPolynomials::new(&odds, k - certain.len());
+
+        Ok(ConditionalPoisson {
+            len: working_probabilities.len(),
+            k,
+            certain,
+            uncertain,
+            odds,
+            polynomials,
+        })
+    }
+
+    /// Calibrates the working probabilities for the inclusion probabilities
+    /// and creates the design from them. See `calibrate`.
+    pub fn calibrated(inclusion_probabilities: &[f64],
+                      tolerance: f64,
+                      max_iterations: usize)
+                      -> Result<Self, ConditionalPoissonError> {
+        let working_probabilities = calibrate(inclusion_probabilities, tolerance, max_iterations)?;
+        let k = sample_size(inclusion_probabilities)?;
+
+        ConditionalPoisson::new(&working_probabilities, k)
+    }
+
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true, if the design has no indices.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the sample size.
+    pub fn sample_size(&self) -> usize {
+        self.k
+    }
+
+    /// Computes the exact inclusion probabilities of the design in O(n * k).
+    pub fn inclusion_probabilities(&self) -> Vec<f64> {
+        let mut probabilities = vec![0.0; self.len];
+
+        for &i in &self.certain {
+            probabilities[i] = 1.0;
+        }
+
+        let uncertain = product_inclusion_probabilities(&self.odds, self.k - self.certain.len());
+
+        for (&i, p) in self.uncertain.iter().zip(uncertain) {
+            probabilities[i] = p;
+        }
+
+        probabilities
+    }
+
+    /// Draws a sample of k distinct indices.
+    /// @return the indices in ascending order
+    pub fn sample<RNG: Rng>(&self, random_choice: &mut RandomChoice<RNG>) -> Vec<usize> {
+        let mut sample = self.certain.clone();
+
+        sample.extend(self.polynomials
+            .sample(&mut random_choice.rng, &self.odds)
+            .into_iter()
+            .map(|i| self.uncertain[i]));
+
+        sample.sort();
+        sample
+    }
+}
+
+/// Finds the working probabilities of a conditional Poisson design with the given inclusion
+/// probabilities. The logits of the working probabilities are corrected by the difference of the
+/// logits of the target and the current inclusion probabilities until all inclusion probabilities
+/// are within the tolerance.
+///
+/// @param inclusion_probabilities the targets π_i ∈ [0, 1], which sum up to the sample size.
+/// @param tolerance the maximum absolute difference of an inclusion probability to its target.
+/// @param max_iterations the maximum number of iterations, each of them costs O(n * k).
+/// @return the working probabilities, which sum up to the sample size as well.
+pub fn calibrate(inclusion_probabilities: &[f64],
+                 tolerance: f64,
+                 max_iterations: usize)
+                 -> Result<Vec<f64>, ConditionalPoissonError> {
+    let k = sample_size(inclusion_probabilities)?;
+
+    let certain = inclusion_probabilities.iter().filter(|&&p| p == 1.0).count();
+    let uncertain: Vec<usize> = (0..inclusion_probabilities.len())
+        .filter(|&i| inclusion_probabilities[i] > 0.0 && inclusion_probabilities[i] < 1.0)
+        .collect();
+
+    let targets: Vec<f64> = uncertain.iter().map(|&i| inclusion_probabilities[i]).collect();
+    let mut log_odds: Vec<f64> = targets.iter().map(|&p| logit(p)).collect();
+
+    let mut iterations = 0;
+
+    loop {
+        let odds: Vec<f64> = log_odds.iter().map(|&l| l.exp()).collect();
+        let current = product_inclusion_probabilities(&odds, k - certain);
+
+        let max_error = current.iter()
+            .zip(&targets)
+            .map(|(c, t)| (c - t).abs())
+            .fold(0.0, f64::max);
+
+        if max_error <= tolerance {
+            break;
+        }
+
+        if iterations == max_iterations {
+            return Err(ConditionalPoissonError::NotConverged {
+                iterations,
+                max_error,
+            });
+        }
+
+        for ((l, &c), &t) in log_odds.iter_mut().zip(&current).zip(&targets) {
+            *l += logit(t) - logit(c);
+        }
+
+        iterations += 1;
+    }
+
+    // the design only depends on the odds up to a common factor,
+    // which is chosen so that the working probabilities sum up to k as well
+    let shift = normalizing_shift(&log_odds, (k - certain) as f64);
+    let mut working_probabilities = vec![0.0; inclusion_probabilities.len()];
+
+    for (i, &p) in inclusion_probabilities.iter().enumerate() {
+        if p == 1.0 {
+            working_probabilities[i] = 1.0;
+        }
+    }
+
+    for (&i, &l) in uncertain.iter().zip(&log_odds) {
+        working_probabilities[i] = logistic(l + shift);
+    }
+
+    Ok(working_probabilities)
+}
+
+/// The sample size given by the sum of the inclusion probabilities.
+fn sample_size(inclusion_probabilities: &[f64]) -> Result<usize, ConditionalPoissonError> {
+    let mut sum: f64 = 0.0;
+
+    for (i, &p) in inclusion_probabilities.iter().enumerate() {
+        if !(0.0..=1.0).contains(&p) {
+            return Err(ConditionalPoissonError::InvalidProbability(i));
+        }
+        sum += p;
+    }
+
+    let k = sum.round();
+
+    if (sum - k).abs() > 1e-6 * k.max(1.0) {
+        return Err(ConditionalPoissonError::NonIntegerSampleSize(sum));
+    }
+
+    Ok(k as usize)
+}
+
+/// The inclusion probabilities of the product design over the odds,
+/// π_i = w_i * e_k-1(w without w_i) / e_k(w).
+fn product_inclusion_probabilities(odds: &[f64], k: usize) -> Vec<f64> {
+    let n = odds.len();
+
+    if k == 0 {
+        return vec![0.0; n];
+    }
+
+    if k == n {
+        return vec![1.0; n];
+    }
+
+    let (prefix, prefix_scales) = polynomial_rows(odds.iter().cloned(), k);
+    let (suffix, suffix_scales) = polynomial_rows(odds.iter().rev().cloned(), k);
+    let width = k + 1;
+
+    // the suffix rows are in reverse order: row r covers the last r weights
+    let total = suffix[n * width + k];
+    let total_scale = suffix_scales[n];
+
+    (0..n)
+        .map(|i| {
+            let before = &prefix[i * width..(i + 1) * width];
+            let after = &suffix[(n - i - 1) * width..(n - i) * width];
+
+            let others: f64 = (0..k).map(|j| before[j] * after[k - 1 - j]).sum();
+            let scale = (prefix_scales[i] + suffix_scales[n - i - 1] - total_scale).exp();
+
+            odds[i] * others * scale / total
+        })
+        .collect()
+}
+
+/// The elementary symmetric polynomials e_0..e_k of the first r weights for r = 0..n.
+/// Every row is normalized by its maximum, the natural logarithms of these factors are returned.
+fn polynomial_rows<I>(weights: I, k: usize) -> (Vec<f64>, Vec<f64>)
+    where I: ExactSizeIterator<Item = f64>
+{
+    let n = weights.len();
+    let width = k + 1;
+
+    let mut rows = vec![0.0; (n + 1) * width];
+    let mut log_scales = vec![0.0; n + 1];
+    rows[0] = 1.0;
+
+    for (r, weight) in weights.enumerate() {
+        let (head, tail) = rows.split_at_mut((r + 1) * width);
+        let previous = &head[r * width..];
+        let row = &mut tail[..width];
+
+        let mut row_max: f64 = 0.0;
+
+        for j in 0..width {
+            row[j] = if j > 0 {
+                previous[j] + weight * previous[j - 1]
+            } else {
+                previous[j]
+            };
+            row_max = row_max.max(row[j]);
+        }
+
+        for value in row.iter_mut() {
+            *value /= row_max;
+        }
+
+        log_scales[r + 1] = log_scales[r] + row_max.ln();
+    }
+
+    (rows, log_scales)
+}
+
+/// Finds the shift of the logits, for which the logistic values sum up to the target.
+fn normalizing_shift(log_odds: &[f64], target: f64) -> f64 {
+    let sum = |shift: f64| log_odds.iter().map(|&l| logistic(l + shift)).sum::<f64>();
+
+    let mut low = -1.0;
+    let mut high = 1.0;
+
+    while sum(low) > target {
+        low *= 2.0;
+    }
+
+    while sum(high) < target {
+        high *= 2.0;
+    }
+
+    for _ in 0..100 {
+        let middle = 0.5 * (low + high);
+
+        if sum(middle) < target {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    0.5 * (low + high)
+}
+
+fn logit(p: f64) -> f64 {
+    (p / (1.0 - p)).ln()
+}
+
+fn logistic(l: f64) -> f64 {
+    1.0 / (1.0 + (-l).exp())
+}
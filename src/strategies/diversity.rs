@@ -0,0 +1,121 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Duplicate Caps
+//! A recommendation feed is drawn with replacement, but one dominant weight must not flood it.
+//! `random_choice_capped_f64` caps the share of the n choices, which any single index occupies,
+//! at max_ratio, i.e. every index is chosen at most max(1, ⌊max_ratio · n⌋) times.
+//!
+//! An index, whose expected count n · w_i / Σ_k w_k reaches the cap, gets exactly the cap, and
+//! its overflow mass is distributed over the other indices in proportion to their weights.
+//! This is repeated, until no expected count reaches the cap, and the spokes, which are left,
+//! are placed by stochastic universal sampling over the uncapped indices.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//!
+//! # fn main() {
+//! let items = vec!["viral", "a", "b", "c", "d"];
+//! let weights: Vec<f64> = vec![100.0, 1.0, 1.0, 1.0, 1.0];
+//!
+//! let feed = random_choice().random_choice_capped_f64(&items, &weights, 10, 0.3);
+//!
+//! assert_eq!(feed.len(), 10);
+//! assert_eq!(feed.iter().filter(|&&&item| item == "viral").count(), 3);
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::sus_indices;
+use crate::RandomChoice;
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n samples by their weights, but no sample more than max_ratio · n times.
+    ///
+    /// @invariant sum of weights must not overflow and weights must not be negative.
+    /// @param samples The to be selected samples
+    /// @param weights Weights of the samples. One weight can be greater 1.
+    /// @param n Number of randomly chosen samples by weight.
+    /// @param max_ratio the greatest share of the choices per sample ∈ (0.0, 1.0].
+    /// @return the chosen samples in the order of the samples. These are less than n, if the
+    ///         samples with a positive weight can't fill n choices under the cap.
+    pub fn random_choice_capped_f64<'a, T>(&mut self,
+                                           samples: &'a [T],
+                                           weights: &[f64],
+                                           n: usize,
+                                           max_ratio: f64)
+                                           -> Vec<&'a T> {
+        self.random_choice_indices_capped_f64(weights, n, max_ratio)
+            .into_iter()
+            .map(|i| &samples[i])
+            .collect()
+    }
+
+    /// Chooses n indices by their weights, but no index more than max_ratio · n times.
+    /// See `random_choice_capped_f64`.
+    /// @return the chosen indices in ascending order.
+    pub fn random_choice_indices_capped_f64(&mut self,
+                                            weights: &[f64],
+                                            n: usize,
+                                            max_ratio: f64)
+                                            -> Vec<usize> {
+        assert!(max_ratio > 0.0 && max_ratio <= 1.0,
+                "max_ratio must be within (0.0, 1.0]");
+
+        let cap = ((max_ratio * n as f64).floor() as usize).max(1);
+        let mut capped = vec![false; weights.len()];
+        let mut remaining = n;
+        let mut sum: f64;
+
+        // water-filling: cap the indices, whose expected count reaches the cap,
+        // and give their overflow to the others
+        loop {
+            sum = weights.iter()
+                .zip(capped.iter())
+                .filter(|&(_, &capped)| !capped)
+                .fold(0.0, |acc, (&w, _)| acc + w);
+
+            if sum <= 0.0 || remaining == 0 {
+                break;
+            }
+
+            let mut newly_capped = 0;
+
+            for (i, &w) in weights.iter().enumerate() {
+                if !capped[i] && w > 0.0 && remaining as f64 * w / sum >= cap as f64 {
+                    capped[i] = true;
+                    newly_capped += 1;
+                }
+            }
+
+            if newly_capped == 0 {
+                break;
+            }
+
+            remaining = remaining.saturating_sub(newly_capped * cap);
+        }
+
+        let mut counts = vec![0; weights.len()];
+
+        for (count, _) in counts.iter_mut().zip(capped.iter()).filter(|&(_, &capped)| capped) {
+            *count = cap;
+        }
+
+        if sum > 0.0 {
+            // next_f64() ∈ [0.0, 1.0)
+            let spin = self.rng.next_f64();
+            let uncapped = |i: usize| if capped[i] { 0.0 } else { weights[i] };
+
+            for i in sus_indices(weights.len(), remaining, spin, sum, uncapped) {
+                counts[i] += 1;
+            }
+        }
+
+        counts.iter()
+            .enumerate()
+            .flat_map(|(i, &count)| std::iter::repeat_n(i, count))
+            .collect()
+    }
+}
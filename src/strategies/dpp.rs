@@ -0,0 +1,229 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Determinantal Point Processes
+//! A k-DPP chooses a subset of exactly k distinct indices, which are both of high weight and
+//! diverse: a subset S is chosen with a probability proportional to det(L_S), where
+//! L_ij = q_i · S_ij · q_j combines the quality q_i = √w_i of the indices with their
+//! similarity S_ij. Similar indices span a small volume and rarely appear together.
+//! With k = 1 and a unit diagonal of S, an index is chosen proportionally to its weight.
+//!
+//! `KDpp` decomposes L once in O(n³) with the `linalg` feature. Every draw then selects k
+//! eigenvectors by the elementary symmetric polynomials of the eigenvalues and samples the
+//! projection DPP of these eigenvectors in O(n · k²), see Kulesza and Taskar (2011),
+//! "k-DPPs: Fixed-Size Determinantal Point Processes".
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::strategies::dpp::KDpp;
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![1.0, 1.0, 0.5];
+//! // 0 and 1 are duplicates, 2 is different from both
+//! let similarity: Vec<f64> = vec![1.0, 1.0, 0.0,
+//!                                 1.0, 1.0, 0.0,
+//!                                 0.0, 0.0, 1.0];
+//!
+//! let dpp = KDpp::new(&weights, &similarity);
+//! let subset = dpp.sample(&mut rand::thread_rng(), 2);
+//!
+//! assert!(subset == vec![0, 2] || subset == vec![1, 2]);
+//! # }
+//! ```
+
+use nalgebra::DMatrix;
+use rand::Rng;
+
+use crate::strategies::subsets::SuffixPolynomials;
+use crate::sus_indices;
+use crate::RandomChoice;
+
+/// The eigendecomposition of the kernel of a DPP, from which subsets of any size are drawn.
+#[derive(Debug, Clone)]
+pub struct KDpp {
+    /// The eigenvalues, those within the rounding error of zero are set to zero.
+    eigenvalues: Vec<f64>,
+    /// The orthonormal eigenvectors as columns.
+    eigenvectors: DMatrix<f64>,
+}
+
+impl KDpp {
+    /// Decomposes the kernel L_ij = √w_i · S_ij · √w_j in O(n³).
+    ///
+    /// @invariant the weights must not be negative and the similarity must be symmetric and
+    ///            positive semidefinite, e.g. the Gram matrix of normalized feature vectors.
+    /// @param weights the quality of the indices 0..weights.len().
+    /// @param similarity the n × n similarities S_ij in row-major order.
+    pub fn new(weights: &[f64], similarity: &[f64]) -> Self {
+        let n = weights.len();
+
+        assert_eq!(similarity.len(),
+                   n * n,
+                   "similarity must be a square matrix of the size of the weights");
+
+        if n == 0 {
+            return KDpp {
+                eigenvalues: Vec::new(),
+                eigenvectors: DMatrix::zeros(0, 0),
+            };
+        }
+
+        let quality: Vec<f64> = weights.iter().map(|&w| w.sqrt()).collect();
+        let kernel = DMatrix::from_fn(n, n, |i, j| quality[i] * similarity[i * n + j] * quality[j]);
+        let decomposition = kernel.symmetric_eigen();
+
+        // eigenvalues of a rank deficient kernel are only zero up to the rounding error
+        let max = decomposition.eigenvalues.iter().cloned().fold(0.0, f64::max);
+        let threshold = max * n as f64 * f64::EPSILON;
+
+        KDpp {
+            eigenvalues: decomposition.eigenvalues
+                .iter()
+                .map(|&l| if l > threshold { l } else { 0.0 })
+                .collect(),
+            eigenvectors: decomposition.eigenvectors,
+        }
+    }
+
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        self.eigenvalues.len()
+    }
+
+    /// Returns true, if there are no indices.
+    pub fn is_empty(&self) -> bool {
+        self.eigenvalues.is_empty()
+    }
+
+    /// Returns the eigenvalues of the kernel in the order of the eigenvectors.
+    pub fn eigenvalues(&self) -> &[f64] {
+        &self.eigenvalues
+    }
+
+    /// Chooses a subset of k distinct indices with a probability proportional to det(L_S).
+    /// @return the members in ascending order or nothing, if k is zero or greater than the
+    ///         number of positive eigenvalues, i.e. no subset of size k has a positive weight.
+    pub fn sample<R: Rng>(&self, rng: &mut R, k: usize) -> Vec<usize> {
+        if k == 0 || k > self.len() {
+            return Vec::new();
+        }
+
+        let polynomials = SuffixPolynomials::new(&self.eigenvalues, k);
+
+        if !polynomials.is_feasible() {
+            return Vec::new();
+        }
+
+        let columns: Vec<Vec<f64>> = polynomials.sample(rng, &self.eigenvalues)
+            .into_iter()
+            .map(|j| self.eigenvectors.column(j).iter().cloned().collect())
+            .collect();
+
+        sample_projection(rng, columns)
+    }
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses a subset of k samples, which are both of high weight and diverse.
+    /// See `random_dpp_subset_indices_f64`.
+    pub fn random_dpp_subset_f64<'a, T>(&mut self,
+                                        samples: &'a [T],
+                                        weights: &[f64],
+                                        similarity: &[f64],
+                                        k: usize)
+                                        -> Vec<&'a T> {
+        self.random_dpp_subset_indices_f64(weights, similarity, k)
+            .into_iter()
+            .map(|i| &samples[i])
+            .collect()
+    }
+
+    /// Chooses a subset of k distinct indices by a k-DPP, see `KDpp`.
+    /// Build a `KDpp` once to draw several subsets from the same kernel.
+    ///
+    /// @param weights the quality of the indices 0..weights.len().
+    /// @param similarity the n × n similarities S_ij in row-major order.
+    /// @param k Size of the subset.
+    /// @return the members in ascending order or nothing, if no subset of size k has a
+    ///         positive probability or k is zero.
+    pub fn random_dpp_subset_indices_f64(&mut self,
+                                         weights: &[f64],
+                                         similarity: &[f64],
+                                         k: usize)
+                                         -> Vec<usize> {
+        KDpp::new(weights, similarity).sample(&mut self.rng, k)
+    }
+}
+
+/// Samples the projection DPP, which is spanned by orthonormal columns, one index at a time:
+/// an index is chosen by its squared norm in the span, which is then reduced to the subspace
+/// orthogonal to the index.
+fn sample_projection<R: Rng>(rng: &mut R, mut columns: Vec<Vec<f64>>) -> Vec<usize> {
+    let n = columns.first().map_or(0, |column| column.len());
+    let mut subset: Vec<usize> = Vec::with_capacity(columns.len());
+
+    while !columns.is_empty() {
+        let mut norms: Vec<f64> = (0..n)
+            .map(|i| columns.iter().map(|column| column[i] * column[i]).sum())
+            .collect();
+
+        for &i in &subset {
+            norms[i] = 0.0;
+        }
+
+        let sum: f64 = norms.iter().sum();
+
+        if sum <= 0.0 {
+            break;
+        }
+
+        // next_f64() ∈ [0.0, 1.0)
+        let i = sus_indices(n, 1, rng.next_f64(), sum, |i| norms[i])[0];
+        subset.push(i);
+
+        // the column with the greatest entry at i is eliminated from the others, which leaves
+        // a basis of the vectors in the span, which are zero at i
+        let pivot = (0..columns.len())
+            .max_by(|&a, &b| columns[a][i].abs().partial_cmp(&columns[b][i].abs()).unwrap())
+            .unwrap();
+        let pivot_column = columns.swap_remove(pivot);
+
+        for column in columns.iter_mut() {
+            let factor = column[i] / pivot_column[i];
+
+            for (value, &p) in column.iter_mut().zip(pivot_column.iter()) {
+                *value -= factor * p;
+            }
+        }
+
+        orthonormalize(&mut columns);
+    }
+
+    subset.sort();
+    subset
+}
+
+/// Modified Gram-Schmidt.
+fn orthonormalize(columns: &mut [Vec<f64>]) {
+    for c in 0..columns.len() {
+        let (done, rest) = columns.split_at_mut(c);
+        let column = &mut rest[0];
+
+        for basis in done.iter() {
+            let projection: f64 = column.iter().zip(basis.iter()).map(|(a, b)| a * b).sum();
+
+            for (value, &b) in column.iter_mut().zip(basis.iter()) {
+                *value -= projection * b;
+            }
+        }
+
+        let norm = column.iter().map(|v| v * v).sum::<f64>().sqrt();
+
+        if norm > 0.0 {
+            for value in column.iter_mut() {
+                *value /= norm;
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Exploration
+//! Epsilon-greedy style selection mixes the weighted distribution with the uniform distribution:
+//! P(i) = (1 - ε) * w_i / Σ_k w_k + ε / n.
+//! Every one of the n items is chosen with a probability of at least ε / n, even if its weight
+//! is 0.0, so that a bandit keeps exploring arms, which currently look bad.
+//! If no weight is positive, the distribution is uniform.
+//!
+//! `RandomChoice::with_exploration` returns an `Exploring` sampler, which draws from the mixed
+//! distribution without copying the weights.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//! use random_choice::strategies::exploration::explored_probabilities;
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![9.0, 1.0, 0.0, 0.0];
+//!
+//! // every arm keeps at least 0.2 / 4 = 0.05
+//! assert_eq!(explored_probabilities(&weights, 0.2), vec![0.77, 0.13, 0.05, 0.05]);
+//!
+//! let arms = random_choice().with_exploration(0.2).random_choice_indices_f64(&weights, 100);
+//! assert_eq!(arms.len(), 100);
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+/// A sampler of the weighted distribution mixed with the uniform distribution.
+pub struct Exploring<'a, RNG: Rng + 'a> {
+    random_choice: &'a mut RandomChoice<RNG>,
+    epsilon: f64,
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Mixes every following draw of the returned sampler with the uniform distribution.
+    /// @param epsilon the share of the uniform distribution ∈ [0.0, 1.0].
+    ///                Every item is chosen with a probability of at least epsilon / n.
+    pub fn with_exploration(&mut self, epsilon: f64) -> Exploring<'_, RNG> {
+        assert!((0.0..=1.0).contains(&epsilon), "epsilon must be in [0.0, 1.0]");
+
+        Exploring {
+            random_choice: self,
+            epsilon,
+        }
+    }
+}
+
+impl<'a, RNG: Rng> Exploring<'a, RNG> {
+    /// Returns epsilon, see `RandomChoice::with_exploration`.
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    /// Chooses n samples by the mixed distribution of their weights.
+    ///
+    /// @invariant sum of weights must not overflow and weights must not be negative.
+    /// @param samples The to be selected samples
+    /// @param weights Weights of the samples. They don't have to sum up to 1.0.
+    /// @param n Number of randomly chosen samples.
+    /// @return n samples in the order of the samples or nothing, if there are no weights.
+    pub fn random_choice_f64<'b, T>(&mut self,
+                                    samples: &'b [T],
+                                    weights: &[f64],
+                                    n: usize)
+                                    -> Vec<&'b T> {
+        self.random_choice_indices_f64(weights, n).into_iter().map(|i| &samples[i]).collect()
+    }
+
+    /// Chooses n indices by the mixed distribution of their weights.
+    ///
+    /// @invariant sum of weights must not overflow and weights must not be negative.
+    /// @param weights Weights of the indices 0..weights.len().
+    /// @param n Number of randomly chosen indices.
+    /// @return n indices in ascending order or nothing, if there are no weights.
+    pub fn random_choice_indices_f64(&mut self, weights: &[f64], n: usize) -> Vec<usize> {
+        if weights.is_empty() || n == 0 {
+            return Vec::new();
+        }
+
+        let probability = mixture(weights, self.epsilon);
+        let sum: f64 = (0..weights.len()).fold(0.0, |acc, i| acc + probability(i));
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = self.random_choice.rng.next_f64();
+        crate::sus_indices(weights.len(), n, spin, sum, probability)
+    }
+}
+
+/// Returns the probabilities of the weights mixed with the uniform distribution.
+/// @param epsilon the share of the uniform distribution ∈ [0.0, 1.0].
+pub fn explored_probabilities(weights: &[f64], epsilon: f64) -> Vec<f64> {
+    let probability = mixture(weights, epsilon);
+    (0..weights.len()).map(probability).collect()
+}
+
+/// P(i) = (1 - ε) * w_i / Σ_k w_k + ε / n, or 1 / n if no weight is positive.
+fn mixture(weights: &[f64], epsilon: f64) -> impl Fn(usize) -> f64 + '_ {
+    let sum: f64 = weights.iter().fold(0.0, |acc, &w| acc + w);
+    let uniform = 1.0 / weights.len() as f64;
+
+    move |i| {
+        if sum > 0.0 {
+            (1.0 - epsilon) * weights[i] / sum + epsilon * uniform
+        } else {
+            uniform
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Distribution Export
+//! Dashboards, which show why the selection behaves as it does, need the wheel itself: the
+//! normalized probability of every index, the cumulative probabilities, i.e. where the segments
+//! of the wheel end, and how often a draw actually chose every index. `export_distribution`
+//! emits them as JSON or CSV.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//! use random_choice::strategies::export::{export_distribution, export_distribution_with_draw,
+//!                                          ExportFormat};
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![1.0, 3.0];
+//!
+//! assert_eq!(export_distribution(&weights, ExportFormat::Csv),
+//!            "index,weight,probability,cumulative\n0,1,0.25,0.25\n1,3,0.75,1\n");
+//!
+//! let draw = random_choice().random_choice_indices_f64(&weights, 4);
+//! let json = export_distribution_with_draw(&weights, &draw, ExportFormat::Json);
+//!
+//! assert!(json.starts_with("[{\"index\":0,\"weight\":1,\"probability\":0.25"));
+//! # }
+//! ```
+
+use std::fmt::Write;
+
+/// The encoding of the exported distribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// An array with one object per index.
+    Json,
+    /// A header line followed by one line per index.
+    Csv,
+}
+
+/// Exports the weight, the probability and the cumulative probability of every index.
+///
+/// @param weights Weights of the indices 0..weights.len().
+/// @param format the encoding.
+/// @return the distribution. All probabilities are 0.0, if no weight is positive.
+pub fn export_distribution(weights: &[f64], format: ExportFormat) -> String {
+    export(weights, None, format)
+}
+
+/// Exports the distribution like `export_distribution` and the realized frequency of every
+/// index, i.e. the share of a draw, which chose it.
+///
+/// @param weights Weights of the indices 0..weights.len().
+/// @param chosen the chosen indices of a draw, e.g. of `random_choice_indices_f64`.
+/// @param format the encoding.
+pub fn export_distribution_with_draw(weights: &[f64],
+                                     chosen: &[usize],
+                                     format: ExportFormat)
+                                     -> String {
+    let mut counts = vec![0usize; weights.len()];
+
+    for &i in chosen {
+        counts[i] += 1;
+    }
+
+    let frequencies: Vec<f64> = counts.iter()
+        .map(|&count| if chosen.is_empty() { 0.0 } else { count as f64 / chosen.len() as f64 })
+        .collect();
+
+    export(weights, Some(&frequencies), format)
+}
+
+fn export(weights: &[f64], frequencies: Option<&[f64]>, format: ExportFormat) -> String {
+    let total: f64 = weights.iter().sum();
+    let normalize = |value: f64| if total > 0.0 { value / total } else { 0.0 };
+
+    let mut out = String::new();
+    let mut cumulative = 0.0;
+
+    match format {
+        ExportFormat::Json => out.push('['),
+        ExportFormat::Csv => {
+            out.push_str("index,weight,probability,cumulative");
+            if frequencies.is_some() {
+                out.push_str(",frequency");
+            }
+            out.push('\n');
+        }
+    }
+
+    for (i, &weight) in weights.iter().enumerate() {
+        cumulative += weight;
+        let mut values = vec![("weight", weight),
+                              ("probability", normalize(weight)),
+                              ("cumulative", normalize(cumulative))];
+
+        if let Some(frequencies) = frequencies {
+            values.push(("frequency", frequencies[i]));
+        }
+
+        // writing to a String can't fail
+        match format {
+            ExportFormat::Json => {
+                if i > 0 {
+                    out.push(',');
+                }
+                write!(out, "{{\"index\":{}", i).unwrap();
+                for (name, value) in values {
+                    write!(out, ",\"{}\":{}", name, json_number(value)).unwrap();
+                }
+                out.push('}');
+            }
+            ExportFormat::Csv => {
+                write!(out, "{}", i).unwrap();
+                for (_, value) in values {
+                    write!(out, ",{}", value).unwrap();
+                }
+                out.push('\n');
+            }
+        }
+    }
+
+    if format == ExportFormat::Json {
+        out.push(']');
+    }
+
+    out
+}
+
+/// JSON has no representation of infinite numbers and NaN.
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
@@ -0,0 +1,285 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Commit-Reveal Fair Draw
+//! A two-phase helper for draws that participants can verify without trusting the operator.
+//!
+//! 1. The operator picks a secret seed and publishes its `Commitment`.
+//! 2. Participants contribute their own entropy to the `FairDraw`.
+//! 3. The operator reveals the seed. It is checked against the commitment and combined with
+//!    all contributions. The stochastic universal sampling draw is derived from the combined value.
+//!
+//! The operator cannot adapt the seed to the contributions, because the commitment is published
+//! first, and the participants cannot predict the outcome, because the seed is still secret while
+//! they contribute. The order of the contributions doesn't matter.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::strategies::fair_draw::{Commitment, FairDraw};
+//!
+//! # fn main() {
+//! let samples = vec!["alice", "bob", "carol"];
+//! let weights: Vec<f64> = vec![1.0, 2.0, 3.0];
+//!
+//! // phase 1: the operator publishes the commitment and keeps the seed secret
+//! let seed = b"operator secret";
+//! let mut draw = FairDraw::new(Commitment::new(seed));
+//!
+//! draw.contribute(b"entropy of alice");
+//! draw.contribute(b"entropy of bob");
+//!
+//! // phase 2: the operator reveals the seed
+//! let revealed = draw.reveal(seed).unwrap();
+//! let winners = revealed.random_choice_f64(&samples, &weights, 2);
+//!
+//! // everybody can check the published result
+//! assert!(revealed.verify_f64(&samples, &weights, 2, winners));
+//! # }
+//! ```
+
+use std::borrow::Borrow;
+use std::error::Error;
+use std::fmt;
+
+use rand::{ChaChaRng, SeedableRng};
+use sha2::{Digest, Sha256};
+
+use crate::RandomChoice;
+
+/// Domain separation prefix, so that the combined seed can't collide with hashes of other protocols.
+const DOMAIN: &[u8] = b"random_choice fair draw v1";
+
+/// Errors of the commit-reveal protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FairDrawError {
+    /// The revealed seed doesn't match the published commitment.
+    CommitmentMismatch,
+    /// A commitment couldn't be parsed from its hexadecimal representation.
+    InvalidHex,
+}
+
+impl fmt::Display for FairDrawError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FairDrawError::CommitmentMismatch => {
+                write!(f, "revealed seed doesn't match the commitment")
+            }
+            FairDrawError::InvalidHex => write!(f, "commitment must be 64 hexadecimal digits"),
+        }
+    }
+}
+
+impl Error for FairDrawError {}
+
+/// The SHA-256 hash of the operator's secret seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Commitment([u8; 32]);
+
+impl Commitment {
+    /// Commits to a secret seed.
+    /// @param seed the secret seed of the operator. It should have at least 32 bytes of entropy.
+    pub fn new(seed: &[u8]) -> Self {
+        Commitment(sha256(&[seed]))
+    }
+
+    /// Creates a commitment from the raw hash bytes.
+    pub fn from_bytes(bytes: [u8; 32]) -> Self {
+        Commitment(bytes)
+    }
+
+    /// Parses a commitment from the hexadecimal representation given by `Display`.
+    pub fn from_hex(hex: &str) -> Result<Self, FairDrawError> {
+        let hex = hex.as_bytes();
+
+        if hex.len() != 64 {
+            return Err(FairDrawError::InvalidHex);
+        }
+
+        let mut bytes = [0u8; 32];
+
+        for (byte, digits) in bytes.iter_mut().zip(hex.chunks(2)) {
+            *byte = (hex_value(digits[0])? << 4) | hex_value(digits[1])?;
+        }
+
+        Ok(Commitment(bytes))
+    }
+
+    /// Returns the raw hash bytes.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Checks, whether the revealed seed is the one this commitment was made for.
+    pub fn verify(&self, seed: &[u8]) -> bool {
+        // the commitment is public, so a constant time comparison is not needed
+        Commitment::new(seed) == *self
+    }
+}
+
+impl fmt::Display for Commitment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects the participants' entropy for a committed draw.
+#[derive(Debug, Clone)]
+pub struct FairDraw {
+    commitment: Commitment,
+    contributions: Vec<[u8; 32]>,
+}
+
+impl FairDraw {
+    /// Starts a draw for the published commitment of the operator.
+    pub fn new(commitment: Commitment) -> Self {
+        FairDraw {
+            commitment,
+            contributions: Vec::new(),
+        }
+    }
+
+    /// Returns the commitment this draw was started with.
+    pub fn commitment(&self) -> &Commitment {
+        &self.commitment
+    }
+
+    /// Adds the entropy of one participant. Only its hash is kept.
+    pub fn contribute(&mut self, entropy: &[u8]) {
+        self.contributions.push(sha256(&[entropy]));
+    }
+
+    /// Returns the number of contributions so far.
+    pub fn contributions(&self) -> usize {
+        self.contributions.len()
+    }
+
+    /// Reveals the seed of the operator and derives the combined seed of the draw.
+    /// @return an error, if the seed doesn't match the commitment.
+    pub fn reveal(&self, seed: &[u8]) -> Result<RevealedDraw, FairDrawError> {
+        if !self.commitment.verify(seed) {
+            return Err(FairDrawError::CommitmentMismatch);
+        }
+
+        // sorting makes the combined seed independent of the order of the contributions
+        let mut contributions = self.contributions.clone();
+        contributions.sort();
+
+        // the raw seed is hashed, because its hash is the public commitment. The length prefix
+        // separates the seed from the contributions.
+        let seed_length = (seed.len() as u64).to_le_bytes();
+        let count = (contributions.len() as u64).to_le_bytes();
+        let mut parts: Vec<&[u8]> = vec![DOMAIN, &seed_length, seed, &count];
+        parts.extend(contributions.iter().map(|c| &c[..]));
+
+        Ok(RevealedDraw { combined_seed: sha256(&parts) })
+    }
+}
+
+/// A draw, whose seed has been revealed and verified.
+/// Every party can reproduce and verify its results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RevealedDraw {
+    combined_seed: [u8; 32],
+}
+
+impl RevealedDraw {
+    /// Returns the combined seed of the operator's seed and all contributions.
+    pub fn combined_seed(&self) -> &[u8; 32] {
+        &self.combined_seed
+    }
+
+    /// Creates the random number generator of this draw.
+    /// ChaCha is used, because its output doesn't depend on the platform.
+    pub fn rng(&self) -> ChaChaRng {
+        let mut key = [0u32; 8];
+
+        for (word, bytes) in key.iter_mut().zip(self.combined_seed.chunks(4)) {
+            *word = u32::from(bytes[0]) | u32::from(bytes[1]) << 8 | u32::from(bytes[2]) << 16 |
+                    u32::from(bytes[3]) << 24;
+        }
+
+        ChaChaRng::from_seed(&key)
+    }
+
+    /// Chooses n samples by their weights with the random number generator of this draw.
+    /// They are the samples of the indices of `RandomChoice::random_choice_indices_f64` with
+    /// rescaling switched off, so that published draws don't depend on it.
+    pub fn random_choice_f64<'a, T>(&self,
+                                    samples: &'a [T],
+                                    weights: &[f64],
+                                    n: usize)
+                                    -> Vec<&'a T> {
+        RandomChoice::new(self.rng()).choose_f64(samples, weights, n)
+    }
+
+    /// Chooses n samples by their f32 weights with the random number generator of this draw.
+    /// See `random_choice_f64`.
+    pub fn random_choice_f32<'a, T>(&self,
+                                    samples: &'a [T],
+                                    weights: &[f32],
+                                    n: usize)
+                                    -> Vec<&'a T> {
+        RandomChoice::new(self.rng()).choose_f32(samples, weights, n)
+    }
+
+    /// Checks a published result against the result of `random_choice_f64`.
+    /// @param claimed the published chosen samples in the order they were drawn.
+    pub fn verify_f64<T, I>(&self, samples: &[T], weights: &[f64], n: usize, claimed: I) -> bool
+        where T: PartialEq,
+              I: IntoIterator,
+              I::Item: Borrow<T>
+    {
+        same_choices(self.random_choice_f64(samples, weights, n), claimed)
+    }
+
+    /// Checks a published result against the result of `random_choice_f32`.
+    /// @param claimed the published chosen samples in the order they were drawn.
+    pub fn verify_f32<T, I>(&self, samples: &[T], weights: &[f32], n: usize, claimed: I) -> bool
+        where T: PartialEq,
+              I: IntoIterator,
+              I::Item: Borrow<T>
+    {
+        same_choices(self.random_choice_f32(samples, weights, n), claimed)
+    }
+}
+
+fn same_choices<T, I>(choices: Vec<&T>, claimed: I) -> bool
+    where T: PartialEq,
+          I: IntoIterator,
+          I::Item: Borrow<T>
+{
+    let mut claimed = claimed.into_iter();
+
+    for choice in choices {
+        match claimed.next() {
+            Some(ref c) if c.borrow() == choice => {}
+            _ => return false,
+        }
+    }
+
+    claimed.next().is_none()
+}
+
+fn sha256(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+
+    for part in parts {
+        hasher.update(part);
+    }
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&hasher.finalize());
+    hash
+}
+
+fn hex_value(digit: u8) -> Result<u8, FairDrawError> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(FairDrawError::InvalidHex),
+    }
+}
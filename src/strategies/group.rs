@@ -0,0 +1,149 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Sampling Per Group
+//! Weights are often stored in one column with a group id per row, e.g. events of many users.
+//! `sample_per_group` chooses n indices of every group by stochastic universal sampling, like
+//! `random_choice_indices_f64` within each group, without splitting the weights per group.
+//! Every group gets its own wheel and all wheels are spun in one pass over the weights.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//!
+//! # fn main() {
+//! let users = ["alice", "bob", "alice", "carol", "bob"];
+//! let weights = [1.0, 2.0, 3.0, 0.0, 4.0];
+//!
+//! let groups = random_choice().sample_per_group(&users, &weights, 2);
+//!
+//! assert_eq!(groups[0].0, "alice");
+//! assert_eq!(groups[0].1.len(), 2);
+//! assert_eq!(groups[2], ("carol", vec![]));
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use rand::Rng;
+
+use crate::core::rescale::{self, Detector, Rescaling};
+use crate::RandomChoice;
+
+/// The wheel of one group for the stochastic universal sampling.
+struct GroupWheel {
+    rescaling: Option<Rescaling>,
+    sum: f64,
+    spoke_gap: f64,
+    current_spoke: f64,
+    accumulated_weights: f64,
+    last_index: usize,
+    chosen: Vec<usize>,
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n indices per group by their weights, e.g. "5 weighted samples per user",
+    /// without splitting the weights into one slice per group.
+    /// Every group gets its own wheel, all wheels are spun in the same pass over the weights.
+    ///
+    /// @invariant sum of weights of each group must not overflow.
+    /// @invariant group_ids and weights have the same length.
+    /// @param group_ids The group of each index. The groups may be interleaved.
+    /// @param weights Weights of the indices. One weight can be greater 1.
+    /// @param n_per_group Number of randomly chosen indices by weight per group.
+    /// @return every group in the order of its first occurrence
+    ///         with its chosen indices in ascending order.
+    ///         Groups, whose weights sum up to zero, get no indices.
+    pub fn sample_per_group<G>(&mut self,
+                               group_ids: &[G],
+                               weights: &[f64],
+                               n_per_group: usize)
+                               -> Vec<(G, Vec<usize>)>
+        where G: Hash + Eq + Clone
+    {
+        assert_eq!(group_ids.len(), weights.len(), "every weight needs a group id");
+
+        let mut slots: HashMap<&G, usize> = HashMap::new();
+        let mut groups: Vec<&G> = Vec::new();
+        let mut detectors: Vec<Detector> = Vec::new();
+        let mut slot_of_index: Vec<usize> = Vec::with_capacity(weights.len());
+
+        for (group_id, &weight) in group_ids.iter().zip(weights) {
+            let slot = *slots.entry(group_id).or_insert_with(|| {
+                groups.push(group_id);
+                detectors.push(Detector::new());
+                groups.len() - 1
+            });
+
+            detectors[slot].add(weight);
+            slot_of_index.push(slot);
+        }
+
+        if n_per_group == 0 {
+            return groups.into_iter().map(|group_id| (group_id.clone(), Vec::new())).collect();
+        }
+
+        // every group is rescaled on its own like the weights of `random_choice_indices_f64`
+        let rescalings: Vec<Option<Rescaling>> = detectors.iter()
+            .map(|detector| if self.rescale { detector.rescaling() } else { None })
+            .collect();
+        let mut sums: Vec<f64> = detectors.iter().map(|detector| detector.sum()).collect();
+
+        if rescalings.iter().any(Option::is_some) {
+            for (sum, _) in sums.iter_mut().zip(&rescalings).filter(|(_, r)| r.is_some()) {
+                *sum = 0.0;
+            }
+
+            for (&slot, &weight) in slot_of_index.iter().zip(weights) {
+                if let Some(rescaling) = rescalings[slot] {
+                    sums[slot] += rescaling.apply(weight);
+                }
+            }
+        }
+
+        let mut wheels: Vec<GroupWheel> = Vec::with_capacity(sums.len());
+
+        for (sum, rescaling) in sums.into_iter().zip(rescalings) {
+            let spoke_gap = sum / n_per_group as f64;
+
+            wheels.push(GroupWheel {
+                rescaling,
+                sum,
+                spoke_gap,
+                // next_f64() ∈ [0.0, 1.0)
+                current_spoke: self.rng.next_f64() * spoke_gap,
+                accumulated_weights: 0.0,
+                last_index: 0,
+                chosen: Vec::with_capacity(n_per_group),
+            });
+        }
+
+        for (i, (&slot, &weight)) in slot_of_index.iter().zip(weights).enumerate() {
+            let wheel = &mut wheels[slot];
+
+            wheel.accumulated_weights += rescale::rescaled(wheel.rescaling, weight);
+            wheel.last_index = i;
+
+            while wheel.current_spoke <= wheel.accumulated_weights &&
+                  wheel.current_spoke < wheel.sum &&
+                  wheel.chosen.len() < n_per_group {
+                wheel.chosen.push(i);
+                wheel.current_spoke += wheel.spoke_gap;
+            }
+        }
+
+        groups.into_iter()
+            .zip(wheels)
+            .map(|(group_id, mut wheel)| {
+                // add this condition, because float leads to inaccurate
+                // calculations which can miss some samples
+                while wheel.sum > 0.0 && wheel.chosen.len() < n_per_group {
+                    wheel.chosen.push(wheel.last_index);
+                }
+
+                (group_id.clone(), wheel.chosen)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,87 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Importance Sampling Weights
+//! Prioritized experience replay (Schaul, Quan, Antonoglou and Silver, "Prioritized Experience
+//! Replay", 2016) draws the transitions of a replay buffer with the probabilities
+//! P(i) = p_i^α / Σ_k p_k^α and corrects the bias of the updates by the importance sampling
+//! weights w_i = (N * P(i))^-β. The weights are divided by the greatest weight of the buffer,
+//! i.e. the weight of the least likely transition, so that they only ever scale updates down.
+//!
+//! The batch is drawn by stochastic universal sampling, which stratifies it like the segments
+//! of the original paper.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//!
+//! # fn main() {
+//! let priorities: Vec<f64> = vec![0.5, 2.0, 0.1, 1.2, 0.7];
+//!
+//! let batch = random_choice().prioritized_sample_f64(&priorities, 3, 0.6, 0.4);
+//!
+//! for (&index, &weight) in batch.indices.iter().zip(batch.weights.iter()) {
+//!     assert!(weight > 0.0 && weight <= 1.0);
+//!     print!("{}: {}, ", index, weight);
+//! }
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+/// A batch of indices with their importance sampling weights.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportanceSample {
+    /// The chosen indices in ascending order.
+    pub indices: Vec<usize>,
+    /// The importance sampling weight of every chosen index ∈ (0.0, 1.0].
+    pub weights: Vec<f64>,
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n indices by their priorities to the power of alpha and computes their importance
+    /// sampling weights.
+    ///
+    /// @invariant priorities must not be negative.
+    /// @param priorities Priorities of the indices 0..priorities.len().
+    /// @param n Number of randomly chosen indices.
+    /// @param alpha How much the priorities count. With 0.0, all indices are equally likely.
+    /// @param beta How much the bias is corrected. With 1.0, it is corrected fully.
+    /// @return n indices with their weights or nothing, if no priority is positive.
+    pub fn prioritized_sample_f64(&mut self,
+                                  priorities: &[f64],
+                                  n: usize,
+                                  alpha: f64,
+                                  beta: f64)
+                                  -> ImportanceSample {
+        let scaled: Vec<f64> = priorities.iter().map(|&p| p.powf(alpha)).collect();
+        let sum: f64 = scaled.iter().fold(0.0, |acc, &s| acc + s);
+        let min = scaled.iter().cloned().filter(|&s| s > 0.0).fold(f64::INFINITY, f64::min);
+
+        if sum <= 0.0 || n == 0 {
+            return ImportanceSample {
+                indices: Vec::new(),
+                weights: Vec::new(),
+            };
+        }
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = self.rng.next_f64();
+        let indices = crate::sus_indices(scaled.len(), n, spin, sum, |i| scaled[i]);
+
+        let len = priorities.len();
+        let max_weight = correction(min / sum, len, beta);
+        let weights = indices.iter()
+            .map(|&i| correction(scaled[i] / sum, len, beta) / max_weight)
+            .collect();
+
+        ImportanceSample { indices, weights }
+    }
+}
+
+/// The unnormalized importance sampling weight (N * P(i))^-β.
+pub(crate) fn correction(probability: f64, len: usize, beta: f64) -> f64 {
+    (len as f64 * probability).powf(-beta)
+}
@@ -0,0 +1,78 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Weighted K-Fold Splits
+//! Cross validation splits a dataset into k folds. If the items have weights, e.g. the number
+//! of samples of a patient or the size of a document, the folds should carry about the same
+//! total weight instead of the same number of items. `weighted_kfold` assigns the items in
+//! descending order of their weights to the fold with the least weight so far (longest
+//! processing time first). Equal weights are visited in a random order and equally loaded
+//! folds are chosen randomly, so that every seed gives another balanced split.
+//!
+//! The weights of two folds differ by at most the greatest weight.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::strategies::kfold::weighted_kfold;
+//!
+//! # fn main() {
+//! let sizes: Vec<f64> = vec![5.0, 3.0, 3.0, 2.0, 2.0, 2.0, 1.0];
+//! let folds = weighted_kfold(&sizes, 3, &mut rand::thread_rng());
+//!
+//! let mut totals = [0.0; 3];
+//! for (&fold, &size) in folds.iter().zip(sizes.iter()) {
+//!     totals[fold] += size;
+//! }
+//!
+//! let max = totals.iter().cloned().fold(0.0, f64::max);
+//! let min = totals.iter().cloned().fold(f64::INFINITY, f64::min);
+//! assert!(max - min <= 5.0);
+//! # }
+//! ```
+
+use std::cmp::Ordering;
+
+use rand::Rng;
+
+/// Assigns every item to one of k folds, so that the folds carry about the same total weight.
+/// It costs O(n * log n + n * k).
+///
+/// @invariant weights must not be negative.
+/// @param weights the weights of the items.
+/// @param k the number of folds. It must be greater than zero.
+/// @param rng the random number generator, which breaks the ties.
+/// @return the fold ∈ 0..k of every item.
+pub fn weighted_kfold<R: Rng>(weights: &[f64], k: usize, rng: &mut R) -> Vec<usize> {
+    assert!(k > 0, "k must be greater than zero");
+
+    // the random keys shuffle the items of equal weight
+    let mut order: Vec<(f64, u64, usize)> = weights.iter()
+        .enumerate()
+        .map(|(i, &w)| (w, rng.next_u64(), i))
+        .collect();
+
+    order.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal).then(a.1.cmp(&b.1)));
+
+    let mut loads = vec![0.0; k];
+    let mut counts = vec![0usize; k];
+    let mut folds = vec![0; weights.len()];
+
+    for (weight, _, i) in order {
+        // the scan starts at a random fold, so that ties are broken randomly
+        let offset = rng.gen_range(0, k);
+        let mut lightest = offset;
+
+        for fold in (offset + 1..k).chain(0..offset) {
+            if (loads[fold], counts[fold]) < (loads[lightest], counts[lightest]) {
+                lightest = fold;
+            }
+        }
+
+        folds[i] = lightest;
+        loads[lightest] += weight;
+        counts[lightest] += 1;
+    }
+
+    folds
+}
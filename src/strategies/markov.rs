@@ -0,0 +1,187 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Markov Chains
+//! Procedural generation of text, music or terrain often learns which state follows which
+//! from examples and replays the chain. A `MarkovSampler` counts the observed transitions per
+//! state and samples the next state by these counts.
+//!
+//! The transitions of every state are kept in a `PreparedChoice`, which is only rebuilt on the
+//! first draw after an observation of that state. A trained chain therefore draws the next state
+//! in O(log k) for k successors, and observing one state doesn't invalidate the others.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::strategies::markov::MarkovSampler;
+//!
+//! # fn main() {
+//! let mut chain = MarkovSampler::new();
+//!
+//! for word in ["banana", "bandana", "cabana"].iter() {
+//!     chain.observe_sequence(word.chars());
+//! }
+//!
+//! let mut rng = rand::thread_rng();
+//! let name: String = chain.walk(&mut rng, 'b', 6).into_iter().collect();
+//!
+//! assert!(name.starts_with("ba"));
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use rand::Rng;
+
+use crate::prepared::PreparedChoice;
+
+/// The observed successors of one state.
+#[derive(Debug, Clone)]
+struct Transitions<S> {
+    successors: Vec<S>,
+    weights: Vec<f64>,
+    /// The position of every successor in `successors`.
+    positions: HashMap<S, usize>,
+    /// The prefix sums of `weights`, None after an observation.
+    prepared: Option<PreparedChoice>,
+}
+
+impl<S: Eq + Hash + Clone> Transitions<S> {
+    fn new() -> Self {
+        Transitions {
+            successors: Vec::new(),
+            weights: Vec::new(),
+            positions: HashMap::new(),
+            prepared: None,
+        }
+    }
+
+    fn add(&mut self, to: S, weight: f64) {
+        match self.positions.get(&to) {
+            Some(&i) => self.weights[i] += weight,
+            None => {
+                self.positions.insert(to.clone(), self.successors.len());
+                self.successors.push(to);
+                self.weights.push(weight);
+            }
+        }
+
+        self.prepared = None;
+    }
+
+    fn prepared(&mut self) -> &PreparedChoice {
+        let weights = &self.weights;
+        self.prepared.get_or_insert_with(|| PreparedChoice::new(weights))
+    }
+}
+
+/// A Markov chain, whose transition weights are learned from observations.
+#[derive(Debug, Clone)]
+pub struct MarkovSampler<S: Eq + Hash> {
+    transitions: HashMap<S, Transitions<S>>,
+}
+
+impl<S: Eq + Hash + Clone> Default for MarkovSampler<S> {
+    fn default() -> Self {
+        MarkovSampler::new()
+    }
+}
+
+impl<S: Eq + Hash + Clone> MarkovSampler<S> {
+    /// Creates a chain without transitions.
+    pub fn new() -> Self {
+        MarkovSampler { transitions: HashMap::new() }
+    }
+
+    /// Returns the number of states with at least one observed successor.
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Returns true, if no transition has been observed.
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Counts one transition.
+    pub fn observe(&mut self, from: S, to: S) {
+        self.observe_weighted(from, to, 1.0);
+    }
+
+    /// Adds a weight to a transition.
+    /// @invariant the weight must not be negative.
+    pub fn observe_weighted(&mut self, from: S, to: S, weight: f64) {
+        self.transitions.entry(from).or_insert_with(Transitions::new).add(to, weight);
+    }
+
+    /// Counts the transitions between all consecutive states of a sequence.
+    pub fn observe_sequence<I: IntoIterator<Item = S>>(&mut self, sequence: I) {
+        let mut states = sequence.into_iter();
+
+        if let Some(mut previous) = states.next() {
+            for state in states {
+                self.observe(previous, state.clone());
+                previous = state;
+            }
+        }
+    }
+
+    /// Returns the weight of a transition, 0.0 if it hasn't been observed.
+    pub fn weight(&self, from: &S, to: &S) -> f64 {
+        self.transitions
+            .get(from)
+            .and_then(|t| t.positions.get(to).map(|&i| t.weights[i]))
+            .unwrap_or(0.0)
+    }
+
+    /// Returns the observed successors of a state with their weights in the order of their
+    /// first observation.
+    pub fn successors(&self, from: &S) -> Vec<(&S, f64)> {
+        match self.transitions.get(from) {
+            Some(t) => t.successors.iter().zip(t.weights.iter().cloned()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Chooses the next state by the transition weights of a state.
+    /// @return None, if no successor of the state with a positive weight has been observed.
+    pub fn sample_next<R: Rng>(&mut self, rng: &mut R, from: &S) -> Option<S> {
+        self.sample_next_n(rng, from, 1).pop()
+    }
+
+    /// Chooses n next states of one state with stochastic universal sampling.
+    /// @return n states in the order of their first observation or nothing, if no successor
+    ///         of the state with a positive weight has been observed.
+    pub fn sample_next_n<R: Rng>(&mut self, rng: &mut R, from: &S, n: usize) -> Vec<S> {
+        let transitions = match self.transitions.get_mut(from) {
+            Some(transitions) => transitions,
+            None => return Vec::new(),
+        };
+
+        if transitions.prepared().total_weight() <= 0.0 {
+            return Vec::new();
+        }
+
+        let indices = transitions.prepared().sample_n(rng, n);
+        indices.into_iter().map(|i| transitions.successors[i].clone()).collect()
+    }
+
+    /// Follows the chain from a start state.
+    /// @param steps the maximum number of transitions.
+    /// @return the start state and the visited states. The walk ends early in a state without
+    ///         observed successors.
+    pub fn walk<R: Rng>(&mut self, rng: &mut R, start: S, steps: usize) -> Vec<S> {
+        let mut path = Vec::with_capacity(steps + 1);
+        path.push(start);
+
+        for _ in 0..steps {
+            match self.sample_next(rng, &path[path.len() - 1]) {
+                Some(next) => path.push(next),
+                None => break,
+            }
+        }
+
+        path
+    }
+}
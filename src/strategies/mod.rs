@@ -0,0 +1,38 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Strategies
+//! The sampling designs and the applications, which are built on the core sampler and the
+//! prepared samplers: sampling without replacement, per group, with capacities, budgets,
+//! duplicate caps or exploration, quotas over crossed strata, subsets, bandits, Markov chains,
+//! random walks, time-varying weights, labeled categorical distributions, population resizing,
+//! fold assignment, draws until a condition holds and the semantics of `numpy.random.choice`.
+
+pub mod bandit;
+pub mod best_of;
+pub mod bitmask;
+pub mod budget;
+pub mod capacity;
+pub mod categorical;
+pub mod conditional_poisson;
+pub mod diversity;
+#[cfg(feature = "linalg")]
+pub mod dpp;
+pub mod exploration;
+pub mod export;
+#[cfg(feature = "fair-draw")]
+pub mod fair_draw;
+pub mod group;
+pub mod importance;
+pub mod kfold;
+pub mod markov;
+pub mod numpy_compat;
+pub mod population;
+pub mod quota;
+pub mod replay;
+pub mod spread;
+pub mod subsets;
+pub mod temporal;
+pub mod ties;
+pub mod until;
+pub mod walk;
@@ -0,0 +1,264 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # NumPy Compatibility
+//! `choice(a, size, replace, p, seed)` follows the semantics of `numpy.random.choice`, to ease
+//! porting scientific Python code and cross-validating its results:
+//!
+//! - `a` is either an integer n, which stands for the indices 0..n, or a slice of items.
+//! - With `replace`, the `size` draws are independent, otherwise they are distinct and in the
+//!   order they were drawn.
+//! - `p` are probabilities, which must not be negative and must sum up to 1 within
+//!   `SUM_TOLERANCE`. Without `p`, every item is equally likely.
+//! - The arguments are checked in the order of NumPy and the errors have its messages,
+//!   e.g. "probabilities do not sum to 1".
+//!
+//! The draws are distributed like those of NumPy, but they are not the same numbers for the
+//! same seed, because NumPy's generators are not reproduced.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::strategies::numpy_compat::choice;
+//!
+//! # fn main() {
+//! // np.random.choice(5, 3, replace=False, p=[0.1, 0, 0.3, 0.6, 0], seed=42)
+//! let p = [0.1, 0.0, 0.3, 0.6, 0.0];
+//! let indices = choice(5, 3, false, Some(&p), Some(42)).unwrap();
+//!
+//! let mut sorted = indices.clone();
+//! sorted.sort();
+//! assert_eq!(sorted, vec![0, 2, 3]);
+//!
+//! let error = choice(&["a", "b"][..], 3, false, None, None).unwrap_err();
+//! assert_eq!(error.to_string(),
+//!            "Cannot take a larger sample than population when 'replace=False'");
+//! # }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+
+use rand::{self, Rng, SeedableRng, StdRng};
+
+use crate::prepared::PreparedChoice;
+use crate::RandomChoice;
+
+/// The greatest difference of the sum of the probabilities to 1, i.e. NumPy's square root of the
+/// machine epsilon of f64.
+pub const SUM_TOLERANCE: f64 = 1.4901161193847656e-8;
+
+/// The `ValueError`s of `numpy.random.choice`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChoiceError {
+    /// `a` is the integer 0, but samples are taken.
+    EmptyRange,
+    /// `a` is an empty slice, but samples are taken.
+    EmptyArray,
+    /// `a` and `p` have different lengths.
+    SizeMismatch,
+    /// A probability is NaN.
+    NaN,
+    /// A probability is negative.
+    Negative,
+    /// The probabilities don't sum up to 1 within `SUM_TOLERANCE`.
+    NotNormalized(f64),
+    /// More distinct items are requested than there are.
+    LargerSampleThanPopulation,
+    /// More distinct items are requested than there are positive probabilities.
+    FewerNonZeroEntries,
+}
+
+impl fmt::Display for ChoiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChoiceError::EmptyRange => {
+                write!(f, "a must be greater than 0 unless no samples are taken")
+            }
+            ChoiceError::EmptyArray => {
+                write!(f, "'a' cannot be empty unless no samples are taken")
+            }
+            ChoiceError::SizeMismatch => write!(f, "'a' and 'p' must have same size"),
+            ChoiceError::NaN => write!(f, "probabilities contain NaN"),
+            ChoiceError::Negative => write!(f, "probabilities are not non-negative"),
+            ChoiceError::NotNormalized(_) => write!(f, "probabilities do not sum to 1"),
+            ChoiceError::LargerSampleThanPopulation => {
+                write!(f, "Cannot take a larger sample than population when 'replace=False'")
+            }
+            ChoiceError::FewerNonZeroEntries => write!(f, "Fewer non-zero entries in p than size"),
+        }
+    }
+}
+
+impl Error for ChoiceError {}
+
+/// The argument `a` of `choice`: an integer or a one-dimensional array.
+pub trait ArrayLike {
+    /// The type of the chosen items.
+    type Item;
+
+    /// Returns the number of items.
+    fn len(&self) -> usize;
+
+    /// Returns true, if there are no items.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the item at index i.
+    fn item(&self, i: usize) -> Self::Item;
+
+    /// Returns the error, if samples are taken from no items.
+    fn empty_error(&self) -> ChoiceError {
+        ChoiceError::EmptyArray
+    }
+}
+
+/// The integer n stands for the indices 0..n like `np.arange(n)`.
+impl ArrayLike for usize {
+    type Item = usize;
+
+    fn len(&self) -> usize {
+        *self
+    }
+
+    fn item(&self, i: usize) -> usize {
+        i
+    }
+
+    fn empty_error(&self) -> ChoiceError {
+        ChoiceError::EmptyRange
+    }
+}
+
+impl<T: Clone> ArrayLike for &[T] {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn item(&self, i: usize) -> T {
+        self[i].clone()
+    }
+}
+
+impl<T: Clone> ArrayLike for &Vec<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn item(&self, i: usize) -> T {
+        self[i].clone()
+    }
+}
+
+/// Chooses items like `numpy.random.choice`.
+///
+/// @param a an integer n for the indices 0..n or the items.
+/// @param size the number of chosen items.
+/// @param replace if false, every item is chosen at most once.
+/// @param p the probabilities of the items or None, if all items are equally likely.
+/// @param seed the seed of the generator or None for the thread's generator.
+/// @return the chosen items in the order they were drawn or NumPy's error.
+pub fn choice<A: ArrayLike>(a: A,
+                            size: usize,
+                            replace: bool,
+                            p: Option<&[f64]>,
+                            seed: Option<u64>)
+                            -> Result<Vec<A::Item>, ChoiceError> {
+    match seed {
+        Some(seed) => {
+            let rng = StdRng::from_seed(&[seed as usize, (seed >> 32) as usize]);
+            choice_with(&mut RandomChoice::new(rng), a, size, replace, p)
+        }
+        None => choice_with(&mut RandomChoice::new(rand::thread_rng()), a, size, replace, p),
+    }
+}
+
+/// Chooses items like `numpy.random.choice` with the generator of a `RandomChoice`.
+/// See `choice` for the parameters.
+pub fn choice_with<RNG, A>(random_choice: &mut RandomChoice<RNG>,
+                           a: A,
+                           size: usize,
+                           replace: bool,
+                           p: Option<&[f64]>)
+                           -> Result<Vec<A::Item>, ChoiceError>
+    where RNG: Rng,
+          A: ArrayLike
+{
+    let len = a.len();
+
+    if len == 0 && size != 0 {
+        return Err(a.empty_error());
+    }
+
+    if let Some(p) = p {
+        check_probabilities(p, len)?;
+    }
+
+    if !replace {
+        if size > len {
+            return Err(ChoiceError::LargerSampleThanPopulation);
+        }
+
+        if p.is_some_and(|p| p.iter().filter(|&&p| p > 0.0).count() < size) {
+            return Err(ChoiceError::FewerNonZeroEntries);
+        }
+    }
+
+    let indices = match (p, replace) {
+        (Some(p), true) => {
+            let prepared = PreparedChoice::new(p);
+            (0..size).filter_map(|_| prepared.sample(&mut random_choice.rng)).collect()
+        }
+        (Some(p), false) => random_choice.random_choice_indices_without_replacement_f64(p, size),
+        (None, true) => (0..size).map(|_| random_choice.rng.gen_range(0, len)).collect(),
+        (None, false) => {
+            // the first size positions of a random permutation like `np.random.permutation`
+            let mut indices: Vec<usize> = (0..len).collect();
+
+            for i in 0..size {
+                let j = random_choice.rng.gen_range(i, len);
+                indices.swap(i, j);
+            }
+
+            indices.truncate(size);
+            indices
+        }
+    };
+
+    Ok(indices.into_iter().map(|i| a.item(i)).collect())
+}
+
+/// Checks the probabilities in the order of NumPy, which sums them up by Kahan summation.
+fn check_probabilities(p: &[f64], len: usize) -> Result<(), ChoiceError> {
+    if p.len() != len {
+        return Err(ChoiceError::SizeMismatch);
+    }
+
+    let mut sum = 0.0;
+    let mut compensation = 0.0;
+
+    for &probability in p {
+        let y = probability - compensation;
+        let t = sum + y;
+        compensation = (t - sum) - y;
+        sum = t;
+    }
+
+    if sum.is_nan() {
+        return Err(ChoiceError::NaN);
+    }
+
+    if p.iter().any(|&probability| probability < 0.0) {
+        return Err(ChoiceError::Negative);
+    }
+
+    if (sum - 1.0).abs() > SUM_TOLERANCE {
+        return Err(ChoiceError::NotNormalized(sum));
+    }
+
+    Ok(())
+}
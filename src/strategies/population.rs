@@ -0,0 +1,111 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Population Resizing
+//! Genetic algorithms shrink or grow their population to an exact size every generation.
+//! `RandomChoice::resize_population` gives every sample its expected number of copies
+//! new_size * w_i / Σ_k w_k rounded down and distributes the copies, which are left, by
+//! stochastic universal sampling over the remainders (stochastic remainder selection). The
+//! expected number of copies of every sample is exactly proportional to its weight, while the
+//! deviation from it is less than one copy.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//!
+//! # fn main() {
+//! let population = vec!["a", "b", "c"];
+//! let fitness: Vec<f64> = vec![6.0, 3.0, 1.0];
+//!
+//! let (next, weights) = random_choice().resize_population(&population, &fitness, 20);
+//!
+//! assert_eq!(next.len(), 20);
+//! assert_eq!(next.iter().filter(|&&s| s == "a").count(), 12);
+//! assert_eq!(weights[0], 6.0);
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Resizes a population to exactly new_size samples, which are copied in proportion to their
+    /// weights.
+    ///
+    /// @invariant sum of weights must not overflow and weights must not be negative.
+    /// @param samples the population.
+    /// @param weights Weights of the samples. They don't have to sum up to 1.0.
+    /// @param new_size the size of the resized population.
+    /// @return the copies of the samples in the order of the samples and their weights,
+    ///         or nothing, if no weight is positive.
+    pub fn resize_population<T: Clone>(&mut self,
+                                       samples: &[T],
+                                       weights: &[f64],
+                                       new_size: usize)
+                                       -> (Vec<T>, Vec<f64>) {
+        let counts = self.resize_counts(weights, new_size);
+        let mut resized: Vec<T> = Vec::with_capacity(new_size);
+        let mut resized_weights: Vec<f64> = Vec::with_capacity(new_size);
+
+        for (i, &count) in counts.iter().enumerate() {
+            for _ in 0..count {
+                resized.push(samples[i].clone());
+                resized_weights.push(weights[i]);
+            }
+        }
+
+        (resized, resized_weights)
+    }
+
+    /// Returns the number of copies of every sample, see `resize_population`.
+    /// @return the counts, which sum up to new_size, unless no weight is positive.
+    pub fn resize_counts(&mut self, weights: &[f64], new_size: usize) -> Vec<usize> {
+        let sum: f64 = weights.iter().fold(0.0, |acc, &w| acc + w);
+        let mut counts = vec![0; weights.len()];
+
+        if sum <= 0.0 || new_size == 0 {
+            return counts;
+        }
+
+        let mut remainders: Vec<f64> = Vec::with_capacity(weights.len());
+        let mut assigned: usize = 0;
+
+        for (i, &weight) in weights.iter().enumerate() {
+            let expected = new_size as f64 * weight / sum;
+            let count = expected.floor();
+
+            counts[i] = count as usize;
+            assigned += counts[i];
+            remainders.push(expected - count);
+        }
+
+        // rounding errors may round up an expected count, which is slightly below an integer
+        while assigned > new_size {
+            let i = counts.iter().rposition(|&count| count > 0).unwrap();
+            counts[i] -= 1;
+            assigned -= 1;
+        }
+
+        let left = new_size - assigned;
+        let remainder_sum: f64 = remainders.iter().fold(0.0, |acc, &r| acc + r);
+
+        if left > 0 && remainder_sum > 0.0 {
+            // next_f64() ∈ [0.0, 1.0)
+            let spin = self.rng.next_f64();
+            let extra = crate::sus_indices(weights.len(), left, spin, remainder_sum, |i| {
+                remainders[i]
+            });
+
+            for i in extra {
+                counts[i] += 1;
+            }
+        } else if left > 0 {
+            // the expected counts are integers up to rounding errors
+            let i = weights.iter().position(|&w| w > 0.0).unwrap();
+            counts[i] += left;
+        }
+
+        counts
+    }
+}
@@ -0,0 +1,153 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Quota Sampling
+//! Market research samples respondents by quotas over crossed attributes, e.g. 20 respondents
+//! aged 18-29 from the north and 30 aged 30-49 from the south. Every combination of attributes
+//! is a cell, e.g. a tuple `(age, region)`, and `sample_quotas` draws exactly the quota of
+//! every cell by the weights of its items without replacement. Items of cells without a quota
+//! are never chosen.
+//!
+//! A cell with fewer items of positive weight than its quota can't be filled. Then no item is
+//! drawn and `QuotaError` reports every such cell with its quota and its available items.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//!
+//! # fn main() {
+//! let cells = [("18-29", "north"), ("18-29", "south"), ("30-49", "north"),
+//!              ("18-29", "north"), ("30-49", "north"), ("30-49", "south")];
+//! let weights = [1.0, 2.0, 1.5, 0.5, 1.0, 3.0];
+//! let quotas = [(("18-29", "north"), 2), (("30-49", "north"), 1)];
+//!
+//! let sample = random_choice().sample_quotas(&cells, &weights, &quotas).unwrap();
+//! assert_eq!(sample.len(), 3);
+//! assert!(sample.contains(&0) && sample.contains(&3));
+//!
+//! let error = random_choice().sample_quotas(&cells, &weights, &[(("30-49", "south"), 2)]);
+//! assert_eq!(error.unwrap_err().shortfalls[0].available, 1);
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+/// A cell, whose quota exceeds its items of positive weight.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shortfall<C> {
+    /// The cell of the quota.
+    pub cell: C,
+    /// The number of items, which the quota demands.
+    pub quota: usize,
+    /// The number of items of the cell with a positive weight.
+    pub available: usize,
+}
+
+/// The error of quotas, which can't all be met.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaError<C> {
+    /// Every infeasible cell in the order of the quotas.
+    pub shortfalls: Vec<Shortfall<C>>,
+}
+
+impl<C: fmt::Debug> fmt::Display for QuotaError<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "quotas are infeasible")?;
+
+        for (i, shortfall) in self.shortfalls.iter().enumerate() {
+            write!(f,
+                   "{} cell {:?} needs {} items, but has {}",
+                   if i == 0 { ":" } else { ";" },
+                   shortfall.cell,
+                   shortfall.quota,
+                   shortfall.available)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: fmt::Debug> Error for QuotaError<C> {}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses exactly the quota of distinct indices of every cell by their weights within the
+    /// cell, i.e. without replacement. Indices with a weight of zero are never chosen.
+    ///
+    /// @invariant every cell has at most one quota.
+    /// @param cells The cell of each index, e.g. a tuple of its attributes.
+    /// @param weights Weights of the indices. One weight can be greater 1.
+    /// @param quotas The number of indices, which are chosen from each cell.
+    /// @return the chosen indices in ascending order or every cell, which can't meet its quota.
+    pub fn sample_quotas<C>(&mut self,
+                            cells: &[C],
+                            weights: &[f64],
+                            quotas: &[(C, usize)])
+                            -> Result<Vec<usize>, QuotaError<C>>
+        where C: Hash + Eq + Clone
+    {
+        let mut slots: HashMap<&C, usize> = HashMap::with_capacity(quotas.len());
+
+        for (slot, (cell, _)) in quotas.iter().enumerate() {
+            assert!(slots.insert(cell, slot).is_none(), "every cell must have at most one quota");
+        }
+
+        let mut candidates: Vec<Vec<usize>> = vec![Vec::new(); quotas.len()];
+
+        for (i, (cell, &weight)) in cells.iter().zip(weights).enumerate() {
+            if let Some(&slot) = slots.get(cell) {
+                if weight > 0.0 {
+                    candidates[slot].push(i);
+                }
+            }
+        }
+
+        let shortfalls: Vec<Shortfall<C>> = quotas.iter()
+            .zip(&candidates)
+            .filter(|&(&(_, quota), candidates)| candidates.len() < quota)
+            .map(|(&(ref cell, quota), candidates)| {
+                Shortfall {
+                    cell: cell.clone(),
+                    quota,
+                    available: candidates.len(),
+                }
+            })
+            .collect();
+
+        if !shortfalls.is_empty() {
+            return Err(QuotaError { shortfalls });
+        }
+
+        // Efraimidis and Spirakis within every cell: the indices with the quota greatest keys
+        // u^(1/w) are a weighted sample without replacement.
+        let rng = &mut self.rng;
+        let descending = |a: &(f64, usize), b: &(f64, usize)| b.0.partial_cmp(&a.0).unwrap();
+        let mut chosen: Vec<usize> = Vec::with_capacity(quotas.iter().map(|q| q.1).sum());
+
+        for (&(_, quota), candidates) in quotas.iter().zip(candidates) {
+            if quota == 0 {
+                continue;
+            }
+
+            let mut keys: Vec<(f64, usize)> = candidates.into_iter()
+                .map(|i| ((1.0 - rng.next_f64()).ln() / weights[i], i))
+                .collect();
+
+            if keys.len() > quota {
+                keys.select_nth_unstable_by(quota, descending);
+                keys.truncate(quota);
+            }
+
+            chosen.extend(keys.into_iter().map(|(_, i)| i));
+        }
+
+        chosen.sort_unstable();
+        Ok(chosen)
+    }
+}
@@ -0,0 +1,231 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Prioritized Replay Buffer
+//! A `PrioritizedReplayBuffer` stores the transitions of a reinforcement learning agent in a
+//! ring buffer of fixed capacity and draws batches for prioritized experience replay. See the
+//! `importance` module for the probabilities and the importance sampling weights.
+//!
+//! The priorities to the power of α are kept in a `FenwickSampler`, and their minimum, which
+//! normalizes the weights, in a segment tree. Pushing a transition, drawing an index and
+//! updating a priority after learning cost O(log n) each.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::strategies::replay::PrioritizedReplayBuffer;
+//!
+//! # fn main() {
+//! let mut buffer = PrioritizedReplayBuffer::new(1000, 0.6, 0.4);
+//! let mut rng = rand::thread_rng();
+//!
+//! for step in 0..100 {
+//!     // new transitions get the greatest priority so far
+//!     let priority = buffer.max_priority();
+//!     buffer.push(("state", "action", step as f64), priority);
+//! }
+//!
+//! let batch = buffer.sample(&mut rng, 32);
+//! let errors: Vec<f64> = batch.indices.iter()
+//!     .map(|&i| buffer.get(i).unwrap().2 * 0.01)
+//!     .collect();
+//!
+//! buffer.update_priorities(&batch.indices, &errors);
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::prepared::fenwick::FenwickSampler;
+use crate::strategies::importance::{correction, ImportanceSample};
+
+/// A ring buffer of transitions, which are drawn by their priorities.
+#[derive(Debug, Clone)]
+pub struct PrioritizedReplayBuffer<T> {
+    items: Vec<T>,
+    /// The slot, which the next transition is written to.
+    next: usize,
+    capacity: usize,
+    priorities: Vec<f64>,
+    /// The priorities to the power of alpha.
+    sums: FenwickSampler,
+    minimums: MinTree,
+    alpha: f64,
+    beta: f64,
+    max_priority: f64,
+}
+
+impl<T> PrioritizedReplayBuffer<T> {
+    /// @param capacity the number of transitions, after which the oldest ones are overwritten.
+    ///                 It must be greater than zero.
+    /// @param alpha How much the priorities count. With 0.0, all transitions are equally likely.
+    /// @param beta How much the bias is corrected. With 1.0, it is corrected fully.
+    pub fn new(capacity: usize, alpha: f64, beta: f64) -> Self {
+        assert!(capacity > 0, "capacity must be greater than zero");
+
+        PrioritizedReplayBuffer {
+            items: Vec::with_capacity(capacity),
+            next: 0,
+            capacity,
+            priorities: Vec::with_capacity(capacity),
+            sums: FenwickSampler::new(&vec![0.0; capacity]),
+            minimums: MinTree::new(capacity),
+            alpha,
+            beta,
+            max_priority: 1.0,
+        }
+    }
+
+    /// Returns the maximum number of transitions.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of stored transitions.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns true, if no transition has been pushed.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Returns alpha, see `new`.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    /// Returns beta, see `new`.
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Changes beta, which is usually annealed towards 1.0 during the training.
+    pub fn set_beta(&mut self, beta: f64) {
+        self.beta = beta;
+    }
+
+    /// Returns the greatest priority so far, 1.0 before the first transition.
+    pub fn max_priority(&self) -> f64 {
+        self.max_priority
+    }
+
+    /// Returns the transition of an index.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        self.items.get(i)
+    }
+
+    /// Returns the priority of an index.
+    pub fn priority(&self, i: usize) -> f64 {
+        self.priorities[i]
+    }
+
+    /// Stores a transition and overwrites the oldest one, if the buffer is full.
+    /// @invariant the priority must not be negative.
+    /// @return the index of the transition.
+    pub fn push(&mut self, item: T, priority: f64) -> usize {
+        let i = self.next;
+
+        if i < self.items.len() {
+            self.items[i] = item;
+        } else {
+            self.items.push(item);
+            self.priorities.push(0.0);
+        }
+
+        self.next = (i + 1) % self.capacity;
+        self.update_priority(i, priority);
+        i
+    }
+
+    /// Changes the priority of a stored transition, e.g. to its new temporal difference error.
+    /// @invariant the priority must not be negative.
+    pub fn update_priority(&mut self, i: usize, priority: f64) {
+        assert!(i < self.items.len(), "index {} is out of bounds", i);
+
+        let scaled = priority.powf(self.alpha);
+        self.priorities[i] = priority;
+
+        self.sums.set_weight(i, scaled);
+        self.minimums.set(i, if scaled > 0.0 { scaled } else { f64::INFINITY });
+        self.max_priority = self.max_priority.max(priority);
+    }
+
+    /// Changes the priorities of several stored transitions.
+    pub fn update_priorities(&mut self, indices: &[usize], priorities: &[f64]) {
+        assert_eq!(indices.len(), priorities.len(), "every index needs a priority");
+
+        for (&i, &priority) in indices.iter().zip(priorities.iter()) {
+            self.update_priority(i, priority);
+        }
+    }
+
+    /// Chooses a batch of n indices by stochastic universal sampling over the priorities.
+    /// @return the indices in ascending order with their importance sampling weights
+    ///         or nothing, if no priority is positive.
+    pub fn sample<R: Rng>(&self, rng: &mut R, n: usize) -> ImportanceSample {
+        let total = self.sums.total_weight();
+
+        if n == 0 || !self.sums.has_positive_weight() || total <= 0.0 {
+            return ImportanceSample {
+                indices: Vec::new(),
+                weights: Vec::new(),
+            };
+        }
+
+        let len = self.items.len();
+        let spoke_gap = total / n as f64;
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = rng.next_f64();
+
+        let indices: Vec<usize> = (0..n)
+            .map(|j| self.sums.find((spin + j as f64) * spoke_gap))
+            .collect();
+
+        let max_weight = correction(self.minimums.min() / total, len, self.beta);
+        let weights = indices.iter()
+            .map(|&i| correction(self.sums.weight(i) / total, len, self.beta) / max_weight)
+            .collect();
+
+        ImportanceSample { indices, weights }
+    }
+
+    /// Returns the stored transitions in the order of their indices.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+}
+
+/// A segment tree of the minimum of the positive priorities.
+#[derive(Debug, Clone)]
+struct MinTree {
+    /// Node i has the children 2i and 2i + 1, the leaves start at `leaves`.
+    nodes: Vec<f64>,
+    leaves: usize,
+}
+
+impl MinTree {
+    fn new(len: usize) -> Self {
+        let leaves = len.next_power_of_two();
+
+        MinTree {
+            nodes: vec![f64::INFINITY; 2 * leaves],
+            leaves,
+        }
+    }
+
+    fn set(&mut self, i: usize, value: f64) {
+        let mut node = self.leaves + i;
+        self.nodes[node] = value;
+
+        while node > 1 {
+            node /= 2;
+            self.nodes[node] = self.nodes[2 * node].min(self.nodes[2 * node + 1]);
+        }
+    }
+
+    fn min(&self) -> f64 {
+        self.nodes[1]
+    }
+}
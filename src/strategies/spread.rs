@@ -0,0 +1,92 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Deterministic Spreading
+//! The deterministic counterpart to multinomial counts: n selections are spread over the indices,
+//! so that every index gets its expected count n * p_i, rounded down or up. The selections which
+//! are left after rounding down go to the indices with the largest remainders
+//! (largest remainder method, also known as Hamilton's method).
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::strategies::spread::spread;
+//!
+//! # fn main() {
+//! let probabilities: Vec<f64> = vec![0.5, 0.3, 0.2];
+//!
+//! assert_eq!(spread(7, &probabilities), vec![4, 2, 1]);
+//! # }
+//! ```
+
+use std::cmp::Ordering;
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+/// Spreads n selections over the indices by the largest remainder method.
+/// Equal remainders are broken by the index: the smaller index gets the selection.
+///
+/// @param n the number of selections.
+/// @param probabilities the probabilities of the indices. They don't have to sum up to 1.0.
+///        Probabilities, which are not positive, get no selection.
+/// @return the number of selections of each index. They sum up to n, unless no probability is
+///         positive.
+pub fn spread(n: usize, probabilities: &[f64]) -> Vec<usize> {
+    spread_by(n, probabilities, |i| i as u64)
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Spreads n selections over the indices by the largest remainder method. See `spread`.
+    /// @param randomize_ties if true, equal remainders are broken randomly instead of by the index.
+    pub fn spread(&mut self, n: usize, probabilities: &[f64], randomize_ties: bool) -> Vec<usize> {
+        if !randomize_ties {
+            return spread(n, probabilities);
+        }
+
+        let tie_keys: Vec<u64> = (0..probabilities.len()).map(|_| self.rng.next_u64()).collect();
+
+        spread_by(n, probabilities, |i| tie_keys[i])
+    }
+}
+
+/// The largest remainder method. Of equal remainders, the one with the smaller tie key wins.
+fn spread_by<F>(n: usize, probabilities: &[f64], tie_key: F) -> Vec<usize>
+    where F: Fn(usize) -> u64
+{
+    let positive = |p: f64| if p > 0.0 { p } else { 0.0 };
+    let sum: f64 = probabilities.iter().fold(0.0, |acc, &p| acc + positive(p));
+
+    let mut counts = vec![0; probabilities.len()];
+
+    if sum <= 0.0 {
+        return counts;
+    }
+
+    let mut remainders: Vec<(usize, f64)> = Vec::with_capacity(probabilities.len());
+    let mut assigned: usize = 0;
+
+    for (i, &p) in probabilities.iter().enumerate() {
+        let expected = n as f64 * positive(p) / sum;
+        let count = expected.floor();
+
+        counts[i] = count as usize;
+        assigned += counts[i];
+
+        if p > 0.0 {
+            remainders.push((i, expected - count));
+        }
+    }
+
+    let left = n.saturating_sub(assigned).min(remainders.len());
+
+    remainders.sort_by(|&(i, a), &(j, b)| {
+        b.partial_cmp(&a).unwrap_or(Ordering::Equal).then_with(|| tie_key(i).cmp(&tie_key(j)))
+    });
+
+    for &(i, _) in &remainders[..left] {
+        counts[i] += 1;
+    }
+
+    counts
+}
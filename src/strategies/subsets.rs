@@ -0,0 +1,219 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Weighted Subsets
+//! Chooses a subset of exactly k distinct indices, e.g. a committee or a feature subset,
+//! whose probability is proportional to the product or to the sum of the weights of its members.
+//!
+//! - **Product**: the indices are decided one after another, each with its conditional inclusion
+//!   probability. These are derived from the elementary symmetric polynomials of the weights,
+//!   which are computed in O(n * k) by dynamic programming.
+//! - **Sum**: one member is chosen by its weight, the remaining k - 1 members uniformly.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//! use random_choice::strategies::subsets::SubsetWeighting;
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//!
+//! let committee = random_choice().random_subset_indices_f64(&weights, 3, SubsetWeighting::Product);
+//!
+//! assert_eq!(committee.len(), 3);
+//! # }
+//! ```
+
+use std::collections::BTreeSet;
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+/// How the weight of a subset is derived from the weights of its members.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubsetWeighting {
+    /// A subset is chosen proportionally to the product of the weights of its members.
+    Product,
+    /// A subset is chosen proportionally to the sum of the weights of its members.
+    Sum,
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses a subset of k samples by the weights of its members.
+    /// See `random_subset_indices_f64`.
+    pub fn random_subset_f64<'a, T>(&mut self,
+                                    samples: &'a [T],
+                                    weights: &[f64],
+                                    k: usize,
+                                    weighting: SubsetWeighting)
+                                    -> Vec<&'a T> {
+        self.random_subset_indices_f64(weights, k, weighting)
+            .into_iter()
+            .map(|i| &samples[i])
+            .collect()
+    }
+
+    /// Chooses a subset of k distinct indices by the weights of its members.
+    ///
+    /// @invariant weights must not be negative.
+    /// @param weights Weights of the indices 0..weights.len().
+    /// @param k Size of the subset.
+    /// @param weighting Whether the probability of a subset is proportional to the product
+    ///                  or to the sum of the weights of its members.
+    /// @return the members in ascending order or nothing, if no subset of size k has a
+    ///         positive weight or k is zero.
+    pub fn random_subset_indices_f64(&mut self,
+                                     weights: &[f64],
+                                     k: usize,
+                                     weighting: SubsetWeighting)
+                                     -> Vec<usize> {
+        if k == 0 || k > weights.len() {
+            return Vec::new();
+        }
+
+        match weighting {
+            SubsetWeighting::Product => {
+                let polynomials = SuffixPolynomials::new(weights, k);
+
+                if polynomials.is_feasible() {
+                    polynomials.sample(&mut self.rng, weights)
+                } else {
+                    Vec::new()
+                }
+            }
+            SubsetWeighting::Sum => self.random_subset_by_sum(weights, k),
+        }
+    }
+
+    fn random_subset_by_sum(&mut self, weights: &[f64], k: usize) -> Vec<usize> {
+        let sum: f64 = weights.iter().fold(0.0, |acc, &i| acc + i);
+
+        if sum <= 0.0 {
+            return Vec::new();
+        }
+
+        // Every subset S is counted once for each of its members i, with the probability
+        // w_i / sum * 1 / C(n - 1, k - 1), which adds up to a probability proportional to the sum.
+        let spin = self.rng.next_f64();
+        let first = crate::sus_indices(weights.len(), 1, spin, sum, |i| weights[i])[0];
+
+        // Floyd's algorithm chooses k - 1 of the other n - 1 positions uniformly
+        let others = weights.len() - 1;
+        let mut positions: BTreeSet<usize> = BTreeSet::new();
+
+        for j in (others + 1 - (k - 1))..(others + 1) {
+            let t = self.rng.gen_range(0, j);
+
+            if !positions.insert(t) {
+                positions.insert(j - 1);
+            }
+        }
+
+        let mut subset: Vec<usize> = positions.into_iter()
+            .map(|p| if p >= first { p + 1 } else { p })
+            .collect();
+
+        subset.push(first);
+        subset.sort();
+        subset
+    }
+}
+
+/// The elementary symmetric polynomials e_j(w_i, ..., w_n-1) of all suffixes of the weights
+/// for j = 0..k. Every row is normalized by its maximum to avoid overflows,
+/// the normalization factors are kept to derive the conditional inclusion probabilities.
+#[derive(Debug, Clone)]
+pub(crate) struct SuffixPolynomials {
+    k: usize,
+    /// (n + 1) rows of k + 1 values.
+    rows: Vec<f64>,
+    /// The factor by which row i was divided relative to row i + 1.
+    factors: Vec<f64>,
+    /// The weights are divided by their maximum, which doesn't change the distribution.
+    scale: f64,
+}
+
+impl SuffixPolynomials {
+    pub fn new(weights: &[f64], k: usize) -> Self {
+        let n = weights.len();
+        let width = k + 1;
+        let max = weights.iter().cloned().fold(0.0, f64::max);
+        let scale = if max > 0.0 { 1.0 / max } else { 1.0 };
+
+        let mut rows = vec![0.0; (n + 1) * width];
+        let mut factors = vec![1.0; n];
+        rows[n * width] = 1.0;
+
+        for i in (0..n).rev() {
+            let (head, tail) = rows.split_at_mut((i + 1) * width);
+            let row = &mut head[i * width..];
+            let next = &tail[..width];
+            let weight = weights[i] * scale;
+
+            let mut row_max: f64 = 0.0;
+
+            for j in 0..width {
+                let value = if j > 0 {
+                    next[j] + weight * next[j - 1]
+                } else {
+                    next[j]
+                };
+
+                row[j] = value;
+                row_max = row_max.max(value);
+            }
+
+            for value in row.iter_mut() {
+                *value /= row_max;
+            }
+
+            factors[i] = row_max;
+        }
+
+        SuffixPolynomials {
+            k,
+            rows,
+            factors,
+            scale,
+        }
+    }
+
+    fn value(&self, i: usize, j: usize) -> f64 {
+        self.rows[i * (self.k + 1) + j]
+    }
+
+    /// Returns true, if at least one subset of size k has a positive product of weights.
+    pub fn is_feasible(&self) -> bool {
+        self.value(0, self.k) > 0.0
+    }
+
+    /// Decides for the indices one after another, whether they are members of the subset.
+    pub fn sample<R: Rng>(&self, rng: &mut R, weights: &[f64]) -> Vec<usize> {
+        let n = weights.len();
+        let mut remaining = self.k;
+        let mut subset: Vec<usize> = Vec::with_capacity(self.k);
+
+        for (i, &weight) in weights.iter().enumerate() {
+            if remaining == 0 {
+                break;
+            }
+
+            // all of the remaining indices are needed, which avoids rounding errors
+            if n - i == remaining {
+                subset.extend(i..n);
+                break;
+            }
+
+            let probability = weight * self.scale * self.value(i + 1, remaining - 1) /
+                              (self.factors[i] * self.value(i, remaining));
+
+            if rng.next_f64() < probability {
+                subset.push(i);
+                remaining -= 1;
+            }
+        }
+
+        subset
+    }
+}
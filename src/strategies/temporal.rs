@@ -0,0 +1,164 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Time-Varying Weights
+//! In simulations, the weights often follow a known schedule, e.g. the demand of every hour of
+//! a day, instead of being updated arbitrarily. A `TemporalSampler` takes the schedule as a
+//! function of the time step t and the index i and samples at any t.
+//!
+//! The weights of a time step are prepared on its first draw and kept in a cache of the most
+//! recently prepared time steps, so that every further draw at that step costs O(log n).
+//! If the schedule changes, `invalidate` marks every cached table as stale in O(1), and a table
+//! is only prepared again on the next draw at its time step.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::strategies::temporal::TemporalSampler;
+//!
+//! # fn main() {
+//! let base: Vec<f64> = vec![5.0, 3.0, 1.0];
+//! // index 2 becomes popular in the evening
+//! let mut sampler = TemporalSampler::new(base.len(), |hour, i| {
+//!     if i == 2 && hour % 24 >= 18 { 50.0 } else { base[i] }
+//! });
+//!
+//! let mut rng = rand::thread_rng();
+//!
+//! for hour in 0..48 {
+//!     let arrivals = sampler.sample_n(&mut rng, hour, 10);
+//!     assert_eq!(arrivals.len(), 10);
+//! }
+//!
+//! assert_eq!(sampler.rebuilds(), 48);
+//! # }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+
+use rand::Rng;
+
+use crate::prepared::PreparedChoice;
+
+/// The default number of time steps, whose tables are cached.
+pub const DEFAULT_CACHE_SIZE: usize = 16;
+
+/// A prepared table and the generation of the schedule, which it was built from.
+#[derive(Debug, Clone)]
+struct Table {
+    generation: u64,
+    prepared: PreparedChoice,
+}
+
+/// Samples indices by weights, which are a function of the time step.
+#[derive(Clone)]
+pub struct TemporalSampler<F> {
+    len: usize,
+    weight: F,
+    tables: HashMap<u64, Table>,
+    /// The cached time steps in the order they were prepared first.
+    order: VecDeque<u64>,
+    cache_size: usize,
+    /// Increased by every invalidation, tables of older generations are stale.
+    generation: u64,
+    rebuilds: usize,
+}
+
+impl<F: Fn(u64, usize) -> f64> TemporalSampler<F> {
+    /// @param len the number of indices.
+    /// @param weight the weight of index i at time step t.
+    ///               It must not be negative and must not change between invalidations.
+    pub fn new(len: usize, weight: F) -> Self {
+        TemporalSampler {
+            len,
+            weight,
+            tables: HashMap::new(),
+            order: VecDeque::new(),
+            cache_size: DEFAULT_CACHE_SIZE,
+            generation: 0,
+            rebuilds: 0,
+        }
+    }
+
+    /// Sets the number of time steps, whose tables are cached. It must be greater than zero.
+    pub fn with_cache_size(mut self, cache_size: usize) -> Self {
+        assert!(cache_size > 0, "cache size must be greater than zero");
+
+        self.cache_size = cache_size;
+        self.evict();
+        self
+    }
+
+    /// Returns the number of indices.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true, if there are no indices.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of tables, which have been prepared so far.
+    pub fn rebuilds(&self) -> usize {
+        self.rebuilds
+    }
+
+    /// Marks the tables of all time steps as stale, e.g. after the schedule was changed through
+    /// shared state. They are prepared again on the next draw at their time step.
+    pub fn invalidate(&mut self) {
+        self.generation += 1;
+    }
+
+    /// Removes the table of one time step from the cache.
+    pub fn invalidate_at(&mut self, t: u64) {
+        if self.tables.remove(&t).is_some() {
+            self.order.retain(|&cached| cached != t);
+        }
+    }
+
+    /// Returns the prepared weights of a time step and prepares them, if they are not cached or
+    /// stale.
+    pub fn table(&mut self, t: u64) -> &PreparedChoice {
+        let generation = self.generation;
+        let fresh = self.tables.get(&t).is_some_and(|table| table.generation == generation);
+
+        if !fresh {
+            let weights: Vec<f64> = (0..self.len).map(|i| (self.weight)(t, i)).collect();
+            let table = Table {
+                generation,
+                prepared: PreparedChoice::new(&weights),
+            };
+
+            self.rebuilds += 1;
+
+            if self.tables.insert(t, table).is_none() {
+                self.order.push_back(t);
+                self.evict();
+            }
+        }
+
+        &self.tables[&t].prepared
+    }
+
+    /// Chooses one index by its weight at time step t.
+    /// @return None, if there are no indices.
+    pub fn sample<R: Rng>(&mut self, rng: &mut R, t: u64) -> Option<usize> {
+        self.table(t).sample(rng)
+    }
+
+    /// Chooses n indices by their weights at time step t with stochastic universal sampling.
+    /// @return n indices in ascending order or nothing, if there are no indices.
+    pub fn sample_n<R: Rng>(&mut self, rng: &mut R, t: u64, n: usize) -> Vec<usize> {
+        self.table(t).sample_n(rng, n)
+    }
+
+    /// Removes the time steps, which were prepared first, until the cache size is kept.
+    fn evict(&mut self) {
+        while self.order.len() > self.cache_size {
+            if let Some(t) = self.order.pop_front() {
+                self.tables.remove(&t);
+            }
+        }
+    }
+}
@@ -0,0 +1,123 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Tie-Breaking
+//! Which indices stochastic universal sampling chooses depends on the order of the weights on the
+//! wheel, although their probabilities don't. If the weights come from an unordered source, e.g. a
+//! `HashMap`, the same spin chooses different samples from run to run.
+//!
+//! The functions of this module lay out the wheel in a canonical order first: by descending
+//! weight and, for exactly equal weights, by a `TieBreaking` rule. With a seeded random number
+//! generator, the result only depends on the weights and the rule, which keeps snapshot tests
+//! stable. Every order of the wheel gives the same probabilities, so this costs O(n * log n)
+//! for the sorting, but nothing of the correctness.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//! use random_choice::strategies::ties::TieBreaking;
+//!
+//! # fn main() {
+//! let ids = vec![17, 4, 23, 8];
+//! let weights: Vec<f64> = vec![1.0, 1.0, 2.0, 1.0];
+//!
+//! // ties are broken by the ids instead of the positions
+//! let by_id = |a: usize, b: usize| ids[a].cmp(&ids[b]);
+//! let choices = random_choice()
+//!     .random_choice_indices_with_ties_f64(&weights, 3, TieBreaking::SecondaryKey(&by_id));
+//!
+//! assert_eq!(choices.len(), 3);
+//! # }
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+/// The order of indices with exactly equal weights on the wheel.
+#[derive(Clone, Copy)]
+pub enum TieBreaking<'a> {
+    /// The smaller index comes first.
+    Index,
+    /// The indices are compared by a secondary key, e.g. an id of the samples.
+    SecondaryKey(&'a dyn Fn(usize, usize) -> Ordering),
+    /// The indices are shuffled randomly.
+    Random,
+}
+
+impl<'a> fmt::Debug for TieBreaking<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TieBreaking::Index => write!(f, "Index"),
+            TieBreaking::SecondaryKey(_) => write!(f, "SecondaryKey(..)"),
+            TieBreaking::Random => write!(f, "Random"),
+        }
+    }
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n samples by their weights on a wheel in canonical order.
+    /// See `random_choice_indices_with_ties_f64`.
+    pub fn random_choice_with_ties_f64<'a, T>(&mut self,
+                                              samples: &'a [T],
+                                              weights: &[f64],
+                                              n: usize,
+                                              tie_breaking: TieBreaking)
+                                              -> Vec<&'a T> {
+        self.random_choice_indices_with_ties_f64(weights, n, tie_breaking)
+            .into_iter()
+            .map(|i| &samples[i])
+            .collect()
+    }
+
+    /// Chooses n indices by their weights on a wheel, which is ordered by descending weight
+    /// and by the tie-breaking rule.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param n Number of randomly chosen indices by weight.
+    /// @param tie_breaking the order of indices with exactly equal weights.
+    /// @return randomly selected indices in the order of the wheel
+    pub fn random_choice_indices_with_ties_f64(&mut self,
+                                               weights: &[f64],
+                                               n: usize,
+                                               tie_breaking: TieBreaking)
+                                               -> Vec<usize> {
+        if weights.is_empty() || n == 0 {
+            return Vec::new();
+        }
+
+        // next_f64() ∈ [0.0, 1.0)
+        let spin = self.rng.next_f64();
+
+        let mut order: Vec<usize> = (0..weights.len()).collect();
+        let by_weight = |a: usize, b: usize| {
+            weights[b].partial_cmp(&weights[a]).unwrap_or(Ordering::Equal)
+        };
+
+        match tie_breaking {
+            // the sort is stable, so equal weights keep the order of their indices
+            TieBreaking::Index => order.sort_by(|&a, &b| by_weight(a, b)),
+            TieBreaking::SecondaryKey(key) => {
+                order.sort_by(|&a, &b| by_weight(a, b).then_with(|| key(a, b)))
+            }
+            TieBreaking::Random => {
+                let rng = &mut self.rng;
+                let tie_keys: Vec<u64> = weights.iter().map(|_| rng.next_u64()).collect();
+                order.sort_by(|&a, &b| by_weight(a, b).then(tie_keys[a].cmp(&tie_keys[b])));
+            }
+        }
+
+        // summed up in the order of the wheel, because float addition in another order can end
+        // above the last accumulated weight, and a spoke in between would run past the wheel
+        let sum: f64 = order.iter().fold(0.0, |acc, &i| acc + weights[i]);
+
+        crate::sus_indices(order.len(), n, spin, sum, |i| weights[order[i]])
+            .into_iter()
+            .map(|i| order[i])
+            .collect()
+    }
+}
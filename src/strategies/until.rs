@@ -0,0 +1,114 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Sampling Until a Condition
+//! Coverage tests draw weighted test cases, until the drawn ones satisfy a condition, e.g. cover
+//! every required category, and give up after a maximum number of draws. `sample_until` draws
+//! one item at a time with replacement and checks the predicate after every draw. The weights
+//! are prepared once, so every draw costs O(log n).
+//!
+//! The predicate gets all draws so far. A predicate, which is expensive over all draws, can
+//! keep its own state and only look at the last draw, since it is called once per draw.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//! use std::collections::HashSet;
+//!
+//! # fn main() {
+//! let categories = vec!["parser", "network", "storage", "ui"];
+//! let weights: Vec<f64> = vec![5.0, 3.0, 1.5, 0.5];
+//!
+//! let covers_all = |draws: &[&&str]| draws.iter().collect::<HashSet<_>>().len() == 4;
+//! let (draws, covered) = random_choice().sample_until(&categories, &weights, covers_all, 10000);
+//!
+//! assert!(covered);
+//! assert!(draws.len() >= 4);
+//! # }
+//! ```
+
+use rand::Rng;
+
+use crate::prepared::PreparedChoice;
+use crate::RandomChoice;
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Draws samples by their weights one at a time, until the draws satisfy the predicate or
+    /// max_draws samples are drawn. See `sample_indices_until`.
+    ///
+    /// @invariant samples and weights have the same length.
+    pub fn sample_until<'a, T, P>(&mut self,
+                                  samples: &'a [T],
+                                  weights: &[f64],
+                                  mut predicate: P,
+                                  max_draws: usize)
+                                  -> (Vec<&'a T>, bool)
+        where P: FnMut(&[&'a T]) -> bool
+    {
+        let mut draws: Vec<&'a T> = Vec::new();
+
+        if predicate(&draws) {
+            return (draws, true);
+        }
+
+        let satisfied = self.draw_until(weights, max_draws, |i| {
+            draws.push(&samples[i]);
+            predicate(&draws)
+        });
+
+        (draws, satisfied)
+    }
+
+    /// Draws indices by their weights one at a time, until the draws satisfy the predicate or
+    /// max_draws indices are drawn. The draws are independent, i.e. with replacement.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param predicate checks all draws in the order they were drawn. It is called before
+    ///        the first draw and after every draw.
+    /// @param max_draws the maximum number of draws.
+    /// @return the draws in the order they were drawn and whether they satisfy the predicate.
+    ///         Nothing is drawn, if no weight is positive.
+    pub fn sample_indices_until<P>(&mut self,
+                                   weights: &[f64],
+                                   mut predicate: P,
+                                   max_draws: usize)
+                                   -> (Vec<usize>, bool)
+        where P: FnMut(&[usize]) -> bool
+    {
+        let mut draws: Vec<usize> = Vec::new();
+
+        if predicate(&draws) {
+            return (draws, true);
+        }
+
+        let satisfied = self.draw_until(weights, max_draws, |i| {
+            draws.push(i);
+            predicate(&draws)
+        });
+
+        (draws, satisfied)
+    }
+
+    /// Draws up to max_draws indices, until satisfied returns true for the last one.
+    fn draw_until<F>(&mut self, weights: &[f64], max_draws: usize, mut satisfied: F) -> bool
+        where F: FnMut(usize) -> bool
+    {
+        let prepared = PreparedChoice::new(weights);
+        let sum = prepared.total_weight();
+
+        if sum.is_nan() || sum <= 0.0 {
+            return false;
+        }
+
+        for _ in 0..max_draws {
+            let i = prepared.sample(&mut self.rng).expect("weights are not empty");
+
+            if satisfied(i) {
+                return true;
+            }
+        }
+
+        false
+    }
+}
@@ -0,0 +1,106 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Weighted Random Walks
+//! A `WeightedRandomWalk` follows the weighted edges of a graph: from every node, it moves to a
+//! neighbor, which is chosen by the weight of its edge with a single spin of the wheel. With a
+//! restart probability, it jumps back to the start node instead, so that the visit frequencies
+//! estimate the personalized PageRank of the start node. Walks of node2vec or DeepWalk are
+//! prefixes of the walk.
+//!
+//! The graph is given by a function from a node to its neighbors, so that adjacency lists,
+//! CSR matrices or generated graphs can be walked without copying them. A node without
+//! neighbors of positive weight restarts the walk.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::strategies::walk::WeightedRandomWalk;
+//!
+//! # fn main() {
+//! let graph: Vec<Vec<(usize, f64)>> = vec![
+//!     vec![(1, 1.0), (2, 3.0)],
+//!     vec![(2, 1.0)],
+//!     vec![(0, 1.0), (1, 0.5)],
+//! ];
+//! let neighbors = |node: usize| &graph[node][..];
+//!
+//! let walk = WeightedRandomWalk::new(&neighbors, 0, rand::thread_rng())
+//!     .with_restart(0.15);
+//! let nodes: Vec<usize> = walk.take(10).collect();
+//!
+//! assert_eq!(nodes[0], 0);
+//! # }
+//! ```
+
+use rand::Rng;
+
+/// An infinite iterator of the nodes of a weighted random walk, starting with the start node.
+pub struct WeightedRandomWalk<'a, R: Rng> {
+    adjacency: &'a dyn Fn(usize) -> &'a [(usize, f64)],
+    start: usize,
+    current: usize,
+    restart: f64,
+    rng: R,
+}
+
+impl<'a, R: Rng> WeightedRandomWalk<'a, R> {
+    /// @param adjacency the neighbors of a node with the weights of their edges.
+    /// @param start the first node of the walk and the target of restarts.
+    /// @param rng the random number generator.
+    pub fn new(adjacency: &'a dyn Fn(usize) -> &'a [(usize, f64)], start: usize, rng: R) -> Self {
+        WeightedRandomWalk {
+            adjacency,
+            start,
+            current: start,
+            restart: 0.0,
+            rng,
+        }
+    }
+
+    /// Jumps back to the start node with a probability before every step.
+    /// @param probability the restart probability ∈ [0.0, 1.0], e.g. 0.15 for PageRank.
+    pub fn with_restart(mut self, probability: f64) -> Self {
+        assert!((0.0..=1.0).contains(&probability), "probability must be in [0.0, 1.0]");
+
+        self.restart = probability;
+        self
+    }
+
+    /// Returns the node, which the walk yields next.
+    pub fn current(&self) -> usize {
+        self.current
+    }
+
+    /// Chooses the node after the current one.
+    fn step(&mut self) -> usize {
+        // next_f64() ∈ [0.0, 1.0)
+        if self.restart > 0.0 && self.rng.next_f64() < self.restart {
+            return self.start;
+        }
+
+        let neighbors = (self.adjacency)(self.current);
+        let sum: f64 = neighbors.iter().fold(0.0, |acc, &(_, w)| acc + w);
+
+        if sum <= 0.0 {
+            return self.start;
+        }
+
+        let spin = self.rng.next_f64();
+
+        match crate::sus_indices(neighbors.len(), 1, spin, sum, |i| neighbors[i].1).pop() {
+            Some(i) => neighbors[i].0,
+            None => self.start,
+        }
+    }
+}
+
+impl<'a, R: Rng> Iterator for WeightedRandomWalk<'a, R> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        let node = self.current;
+        self.current = self.step();
+        Some(node)
+    }
+}
@@ -0,0 +1,11 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Streaming
+//! The samplers, whose memory doesn't grow with the input or the output: reservoirs and sketches
+//! of weighted streams, lazy draws and draws, which are written directly to a sink.
+
+pub mod output;
+pub mod priority;
+pub mod reservoir;
+pub mod spokes;
@@ -0,0 +1,83 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Streaming Output
+//! Writes the chosen indices of huge draws directly to a sink, e.g. a file or a socket,
+//! in chunks of `CHUNK_SIZE` indices. The memory usage is independent of the number of choices,
+//! so drawing 10^9 indices doesn't need a `Vec` of 8 GB.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//! use random_choice::streaming::output::OutputFormat;
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//! let mut csv: Vec<u8> = Vec::new();
+//!
+//! random_choice().sample_to_writer(&weights, 3, &mut csv, OutputFormat::Csv).unwrap();
+//!
+//! assert_eq!(String::from_utf8(csv).unwrap().lines().count(), 4);
+//! # }
+//! ```
+
+use std::io::{self, Write};
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+/// The number of indices, which are encoded and written at once.
+pub const CHUNK_SIZE: usize = 8192;
+
+/// The encoding of the chosen indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Every index as `u64` in little endian.
+    Binary,
+    /// A header line `index` followed by one index per line.
+    Csv,
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n indices by their weights and writes them to the writer in ascending order.
+    /// It chooses the same indices as `random_choice_indices_f64`, tiny weights are rescaled
+    /// the same way, see `spokes_f64`.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param n Number of randomly chosen indices by weight.
+    /// @param writer the sink. It doesn't need to be buffered, the indices are written in chunks.
+    /// @param format the encoding of the indices.
+    /// @return the number of written indices.
+    pub fn sample_to_writer<W: Write>(&mut self,
+                                      weights: &[f64],
+                                      n: usize,
+                                      mut writer: W,
+                                      format: OutputFormat)
+                                      -> io::Result<usize> {
+        let mut spokes = self.spokes_f64(weights, n);
+        let mut buffer: Vec<u8> = Vec::with_capacity(CHUNK_SIZE * 8);
+
+        if format == OutputFormat::Csv {
+            writer.write_all(b"index\n")?;
+        }
+
+        while spokes.len() > 0 {
+            buffer.clear();
+
+            for index in spokes.by_ref().take(CHUNK_SIZE) {
+                match format {
+                    OutputFormat::Binary => buffer.extend_from_slice(&(index as u64).to_le_bytes()),
+                    OutputFormat::Csv => writeln!(buffer, "{}", index)?,
+                }
+            }
+
+            writer.write_all(&buffer)?;
+        }
+
+        writer.flush()?;
+
+        Ok(spokes.drawn())
+    }
+}
@@ -0,0 +1,200 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Priority Sampling
+//! A fixed-size sketch of a weighted stream, e.g. of network flows and their byte counts,
+//! which estimates the sum of the weights of any subset without bias
+//! (Duffield, Lund and Thorup, "Priority sampling for estimation of arbitrary subset sums").
+//!
+//! Every item gets the priority w / u with u uniform in (0, 1]. The sketch keeps the k items with
+//! the highest priorities, the (k + 1)-th highest priority is the threshold τ. Every kept item
+//! estimates the weight max(w, τ), all other items estimate zero.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::streaming::priority::PrioritySketch;
+//!
+//! # fn main() {
+//! let flows = vec![("10.0.0.1", 1500.0), ("10.0.0.2", 40.0), ("10.0.0.1", 9000.0),
+//!                  ("10.0.0.3", 576.0), ("10.0.0.2", 1500.0)];
+//!
+//! let mut rng = rand::thread_rng();
+//! let mut sketch = PrioritySketch::new(3);
+//!
+//! for (source, bytes) in flows {
+//!     sketch.offer(&mut rng, source, bytes);
+//! }
+//!
+//! let bytes_of_first_host = sketch.estimate_subset_sum(|&source| source == "10.0.0.1");
+//! assert!(bytes_of_first_host >= 0.0);
+//! # }
+//! ```
+
+use std::collections::BinaryHeap;
+use std::ptr;
+
+use rand::Rng;
+
+use crate::streaming::reservoir::Entry;
+use crate::RandomChoice;
+
+/// A priority sample of at most k items with their weights.
+#[derive(Debug, Clone)]
+pub struct PrioritySketch<T> {
+    capacity: usize,
+    /// The k + 1 items with the highest priorities. The smallest of them defines the threshold.
+    heap: BinaryHeap<Entry<(T, f64)>>,
+    seen: usize,
+}
+
+impl<T> PrioritySketch<T> {
+    /// Creates an empty sketch.
+    /// @param capacity the number k of items to keep.
+    pub fn new(capacity: usize) -> Self {
+        PrioritySketch {
+            capacity,
+            heap: BinaryHeap::with_capacity(capacity + 1),
+            seen: 0,
+        }
+    }
+
+    /// Offers the next item of the stream to the sketch.
+    /// Items with a weight, which is not positive, are never kept.
+    pub fn offer<R: Rng>(&mut self, rng: &mut R, item: T, weight: f64) {
+        self.seen += 1;
+
+        if weight.is_nan() || weight <= 0.0 {
+            return;
+        }
+
+        let priority = weight / (1.0 - rng.next_f64());
+
+        if self.heap.len() <= self.capacity {
+            self.heap.push(Entry {
+                key: priority,
+                item: (item, weight),
+            });
+        } else if self.heap.peek().is_some_and(|smallest| priority > smallest.key) {
+            self.heap.pop();
+            self.heap.push(Entry {
+                key: priority,
+                item: (item, weight),
+            });
+        }
+    }
+
+    /// Returns the number k of items to keep.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of kept items.
+    pub fn len(&self) -> usize {
+        self.heap.len().min(self.capacity)
+    }
+
+    /// Returns true, if no item has been kept.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of offered items, including those without a positive weight.
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Returns the threshold τ, i.e. the (k + 1)-th highest priority.
+    /// It is zero, as long as at most k items with a positive weight were offered.
+    /// In this case, the estimates are exact.
+    pub fn threshold(&self) -> f64 {
+        if self.heap.len() > self.capacity {
+            self.heap.peek().map_or(0.0, |smallest| smallest.key)
+        } else {
+            0.0
+        }
+    }
+
+    /// Returns the kept items with their weight estimates max(w, τ) in no particular order.
+    pub fn samples(&self) -> Vec<(&T, f64)> {
+        let threshold = self.threshold();
+
+        self.kept().map(|&(ref item, weight)| (item, weight.max(threshold))).collect()
+    }
+
+    /// Estimates the sum of the weights of all offered items, which satisfy the predicate.
+    /// The estimate is unbiased.
+    pub fn estimate_subset_sum<F>(&self, predicate: F) -> f64
+        where F: Fn(&T) -> bool
+    {
+        let threshold = self.threshold();
+
+        self.kept()
+            .filter(|&(item, _)| predicate(item))
+            .fold(0.0, |acc, &(_, weight)| acc + weight.max(threshold))
+    }
+
+    /// Estimates the sum of the weights of all offered items.
+    pub fn estimate_total(&self) -> f64 {
+        self.estimate_subset_sum(|_| true)
+    }
+
+    /// Estimates the variance of `estimate_subset_sum` for the same predicate without bias
+    /// by the sum of τ * max(0, τ - w) over the kept items, which satisfy the predicate.
+    pub fn estimate_subset_variance<F>(&self, predicate: F) -> f64
+        where F: Fn(&T) -> bool
+    {
+        let threshold = self.threshold();
+
+        self.kept()
+            .filter(|&(item, _)| predicate(item))
+            .fold(0.0, |acc, &(_, weight)| acc + threshold * (threshold - weight).max(0.0))
+    }
+
+    /// Returns the kept items with their weight estimates max(w, τ)
+    /// by descending priority.
+    pub fn into_samples(self) -> Vec<(T, f64)> {
+        let threshold = self.threshold();
+        let capacity = self.capacity;
+
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .take(capacity)
+            .map(|entry| {
+                let (item, weight) = entry.item;
+                (item, weight.max(threshold))
+            })
+            .collect()
+    }
+
+    /// The kept items and their weights, without the item, which defines the threshold.
+    fn kept(&self) -> impl Iterator<Item = &(T, f64)> {
+        let threshold_entry = if self.heap.len() > self.capacity {
+            self.heap.peek()
+        } else {
+            None
+        };
+
+        self.heap
+            .iter()
+            .filter(move |&entry| !threshold_entry.is_some_and(|t| ptr::eq(t, entry)))
+            .map(|entry| &entry.item)
+    }
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Builds a priority sketch of k items in one pass over the items. See `PrioritySketch`.
+    /// @param items pairs of an item and its weight. Their number doesn't have to be known.
+    pub fn priority_sample_f64<T, I>(&mut self, items: I, k: usize) -> PrioritySketch<T>
+        where I: IntoIterator<Item = (T, f64)>
+    {
+        let mut sketch = PrioritySketch::new(k);
+
+        for (item, weight) in items {
+            sketch.offer(&mut self.rng, item, weight);
+        }
+
+        sketch
+    }
+}
@@ -0,0 +1,394 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Weighted Reservoir Sampling
+//! Chooses k distinct items by their weights from a sequence of unknown length in one pass
+//! and with O(k) memory (Efraimidis and Spirakis, algorithm A-Res): every item gets the random
+//! key u^(1/w) and the reservoir keeps the items with the k greatest keys.
+//!
+//! A `VarOptReservoir` summarizes a weighted stream instead: it keeps k items with adjusted
+//! weights, whose subset sums estimate the subset sums of the stream without bias and with the
+//! optimal variance (Cohen, Duffield, Kaplan, Lund and Thorup, "Efficient stream sampling for
+//! variance-optimal estimation of subset sums"). Every item costs O(log k).
+//!
+//! With the `async` feature, a `ReservoirStreamBuilder` consumes a `Stream` of items and weights,
+//! e.g. from a paginated API, while they arrive.
+//!
+//! ```rust
+//! extern crate rand;
+//! extern crate random_choice;
+//! use random_choice::streaming::reservoir::WeightedReservoir;
+//!
+//! # fn main() {
+//! let mut rng = rand::thread_rng();
+//! let mut reservoir = WeightedReservoir::new(2);
+//!
+//! for (item, weight) in vec![("hi", 5.6), ("this", 7.8), ("is", 9.7), ("a", 1.1)] {
+//!     reservoir.offer(&mut rng, item, weight);
+//! }
+//!
+//! assert_eq!(reservoir.into_samples().len(), 2);
+//! # }
+//! ```
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use rand::Rng;
+
+use crate::RandomChoice;
+
+/// A reservoir of at most k items, which are chosen by their weights without replacement.
+#[derive(Debug, Clone)]
+pub struct WeightedReservoir<T> {
+    capacity: usize,
+    heap: BinaryHeap<Entry<T>>,
+    seen: usize,
+    total_weight: f64,
+}
+
+impl<T> WeightedReservoir<T> {
+    /// Creates an empty reservoir.
+    /// @param capacity the number of items to choose.
+    pub fn new(capacity: usize) -> Self {
+        WeightedReservoir {
+            capacity,
+            heap: BinaryHeap::with_capacity(capacity),
+            seen: 0,
+            total_weight: 0.0,
+        }
+    }
+
+    /// Offers the next item of the sequence to the reservoir.
+    /// Items with a weight, which is not positive, are never chosen.
+    pub fn offer<R: Rng>(&mut self, rng: &mut R, item: T, weight: f64) {
+        self.seen += 1;
+
+        if weight.is_nan() || weight <= 0.0 || self.capacity == 0 {
+            return;
+        }
+
+        self.total_weight += weight;
+
+        // the logarithm of u^(1/w) keeps the order of the keys and doesn't underflow
+        let key = (1.0 - rng.next_f64()).ln() / weight;
+
+        if self.heap.len() < self.capacity {
+            self.heap.push(Entry { key, item });
+        } else if self.heap.peek().is_some_and(|smallest| key > smallest.key) {
+            self.heap.pop();
+            self.heap.push(Entry { key, item });
+        }
+    }
+
+    /// Returns the maximum number of chosen items.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of chosen items so far.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns true, if no item has been chosen so far.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns the number of offered items, including those without a positive weight.
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Returns the sum of the positive weights of all offered items.
+    pub fn total_weight(&self) -> f64 {
+        self.total_weight
+    }
+
+    /// Returns the chosen items in the order they would have been drawn one after another,
+    /// i.e. by descending keys.
+    pub fn into_samples(self) -> Vec<T> {
+        // the heap is reversed, so the ascending order of the entries has the greatest key first
+        self.heap.into_sorted_vec().into_iter().map(|entry| entry.item).collect()
+    }
+}
+
+/// A VarOpt summary of at most k items of a weighted stream.
+#[derive(Debug, Clone)]
+pub struct VarOptReservoir<T> {
+    capacity: usize,
+    /// Items, whose weight is greater than the threshold. They keep their weight.
+    large: BinaryHeap<Entry<T>>,
+    /// Items, whose adjusted weight is the threshold.
+    small: Vec<T>,
+    threshold: f64,
+    seen: usize,
+}
+
+impl<T> VarOptReservoir<T> {
+    /// Creates an empty summary.
+    /// @param capacity the number k of items to keep.
+    pub fn new(capacity: usize) -> Self {
+        VarOptReservoir {
+            capacity,
+            large: BinaryHeap::with_capacity(capacity + 1),
+            small: Vec::with_capacity(capacity + 1),
+            threshold: 0.0,
+            seen: 0,
+        }
+    }
+
+    /// Offers the next item of the stream to the summary.
+    /// Items with a weight, which is not positive, are never kept.
+    pub fn offer<R: Rng>(&mut self, rng: &mut R, item: T, weight: f64) {
+        self.seen += 1;
+
+        if weight.is_nan() || weight <= 0.0 || self.capacity == 0 {
+            return;
+        }
+
+        if self.large.len() + self.small.len() < self.capacity {
+            self.large.push(Entry { key: weight, item });
+            return;
+        }
+
+        // the candidates for dropping, which get the new threshold as adjusted weight
+        let mut candidates: Vec<Entry<T>> = Vec::new();
+
+        if weight > self.threshold {
+            self.large.push(Entry { key: weight, item });
+        } else {
+            candidates.push(Entry { key: weight, item });
+        }
+
+        let mut sum = self.threshold * self.small.len() as f64 + weight * candidates.len() as f64;
+
+        while let Some(smallest) = self.large.peek().map(|entry| entry.key) {
+            let others = (self.small.len() + candidates.len()) as f64 - 1.0;
+
+            if sum < others * smallest {
+                break;
+            }
+
+            sum += smallest;
+            candidates.extend(self.large.pop());
+        }
+
+        let threshold = sum / ((self.small.len() + candidates.len()) as f64 - 1.0);
+
+        // every candidate is dropped with the probability 1 - w / τ,
+        // every small item with the probability 1 - τ_old / τ, which adds up to one
+        let mut r = rng.next_f64();
+        let mut dropped: Option<usize> = None;
+
+        for (i, candidate) in candidates.iter().enumerate() {
+            r -= 1.0 - candidate.key / threshold;
+
+            if r < 0.0 {
+                dropped = Some(i);
+                break;
+            }
+        }
+
+        match dropped {
+            Some(i) => {
+                candidates.swap_remove(i);
+            }
+            None if self.small.is_empty() => {
+                // only reachable by rounding errors of the candidates' probabilities
+                candidates.pop();
+            }
+            None => {
+                let per_item = 1.0 - self.threshold / threshold;
+                let i = ((r / per_item) as usize).min(self.small.len() - 1);
+                self.small.swap_remove(i);
+            }
+        }
+
+        self.small.extend(candidates.into_iter().map(|candidate| candidate.item));
+        self.threshold = threshold;
+    }
+
+    /// Returns the number k of items to keep.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of kept items.
+    pub fn len(&self) -> usize {
+        self.large.len() + self.small.len()
+    }
+
+    /// Returns true, if no item has been kept.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of offered items, including those without a positive weight.
+    pub fn seen(&self) -> usize {
+        self.seen
+    }
+
+    /// Returns the threshold τ, the adjusted weight of all kept items with a smaller weight.
+    /// It is zero, as long as at most k items with a positive weight were offered.
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Returns the kept items with their adjusted weights in no particular order.
+    pub fn samples(&self) -> Vec<(&T, f64)> {
+        self.large
+            .iter()
+            .map(|entry| (&entry.item, entry.key))
+            .chain(self.small.iter().map(|item| (item, self.threshold)))
+            .collect()
+    }
+
+    /// Estimates the sum of the weights of all offered items, which satisfy the predicate.
+    /// The estimate is unbiased.
+    pub fn estimate_subset_sum<F>(&self, predicate: F) -> f64
+        where F: Fn(&T) -> bool
+    {
+        self.samples()
+            .into_iter()
+            .filter(|&(item, _)| predicate(item))
+            .fold(0.0, |acc, (_, weight)| acc + weight)
+    }
+
+    /// Returns the sum of the weights of all offered items, which VarOpt keeps exactly.
+    pub fn estimate_total(&self) -> f64 {
+        self.estimate_subset_sum(|_| true)
+    }
+
+    /// Returns the kept items with their adjusted weights in no particular order.
+    pub fn into_samples(self) -> Vec<(T, f64)> {
+        let threshold = self.threshold;
+
+        self.large
+            .into_iter()
+            .map(|entry| (entry.item, entry.key))
+            .chain(self.small.into_iter().map(|item| (item, threshold)))
+            .collect()
+    }
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Chooses n distinct items by their weights in one pass over the items.
+    /// See `WeightedReservoir`.
+    ///
+    /// @param items pairs of an item and its weight. Their number doesn't have to be known.
+    /// @return at most n items in the order they were drawn.
+    pub fn reservoir_sample_f64<T, I>(&mut self, items: I, n: usize) -> Vec<T>
+        where I: IntoIterator<Item = (T, f64)>
+    {
+        let mut reservoir = WeightedReservoir::new(n);
+
+        for (item, weight) in items {
+            reservoir.offer(&mut self.rng, item, weight);
+        }
+
+        reservoir.into_samples()
+    }
+
+    /// Builds a VarOpt summary of k items in one pass over the items. See `VarOptReservoir`.
+    /// @param items pairs of an item and its weight. Their number doesn't have to be known.
+    pub fn varopt_sample_f64<T, I>(&mut self, items: I, k: usize) -> VarOptReservoir<T>
+        where I: IntoIterator<Item = (T, f64)>
+    {
+        let mut reservoir = VarOptReservoir::new(k);
+
+        for (item, weight) in items {
+            reservoir.offer(&mut self.rng, item, weight);
+        }
+
+        reservoir
+    }
+}
+
+/// An item and its key. The order is reversed, so that the `BinaryHeap` is a min-heap.
+#[derive(Debug, Clone)]
+pub(crate) struct Entry<T> {
+    pub key: f64,
+    pub item: T,
+}
+
+impl<T> PartialEq for Entry<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T> Eq for Entry<T> {}
+
+impl<T> PartialOrd for Entry<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Entry<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.key.partial_cmp(&self.key).unwrap_or(Ordering::Equal)
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::stream::ReservoirStreamBuilder;
+
+#[cfg(feature = "async")]
+mod stream {
+    use std::future::Future;
+
+    use futures::future::{self, FutureExt};
+    use futures::stream::{Stream, StreamExt};
+    use rand::Rng;
+
+    use super::WeightedReservoir;
+
+    /// Builds a `WeightedReservoir` from a stream of items and their weights.
+    ///
+    /// Every item is offered to the reservoir as soon as the stream yields it, so the stream is
+    /// never buffered: only the k chosen items are kept in memory.
+    #[derive(Debug)]
+    pub struct ReservoirStreamBuilder<RNG: Rng> {
+        rng: RNG,
+        reservoir_capacity: usize,
+    }
+
+    impl<RNG: Rng> ReservoirStreamBuilder<RNG> {
+        /// @param rng the random number generator for the keys of the items.
+        /// @param capacity the number of items to choose.
+        pub fn new(rng: RNG, capacity: usize) -> Self {
+            ReservoirStreamBuilder {
+                rng,
+                reservoir_capacity: capacity,
+            }
+        }
+
+        /// Consumes the stream and resolves to the reservoir after the stream has ended.
+        pub fn build<T, S>(self, stream: S) -> impl Future<Output = WeightedReservoir<T>>
+            where S: Stream<Item = (T, f64)>
+        {
+            let reservoir = WeightedReservoir::new(self.reservoir_capacity);
+            self.extend(reservoir, stream)
+        }
+
+        /// Continues a reservoir with the items of another stream, e.g. the next page of an API.
+        pub fn extend<T, S>(self,
+                            reservoir: WeightedReservoir<T>,
+                            stream: S)
+                            -> impl Future<Output = WeightedReservoir<T>>
+            where S: Stream<Item = (T, f64)>
+        {
+            let state = (self.rng, reservoir);
+
+            let finished = stream.fold(state, |(mut rng, mut reservoir), (item, weight)| {
+                reservoir.offer(&mut rng, item, weight);
+                future::ready((rng, reservoir))
+            });
+
+            finished.map(|(_, reservoir)| reservoir)
+        }
+    }
+}
@@ -0,0 +1,341 @@
+// Licensed under the Apache License, Version 2.0
+// <http://www.apache.org/licenses/LICENSE-2.0>
+
+//! # Lazy Draws
+//! For draws with hundreds of millions of spokes, `Spokes` yields the chosen indices one by one
+//! instead of collecting them, and `SpokeChunks` yields them in chunks and stops as soon as its
+//! `CancellationToken` is cancelled. An async service can process one chunk at a time and yield
+//! to its executor in between, so a long-running draw neither blocks the executor nor has to
+//! run to completion.
+//!
+//! If only the multiplicities matter, `sample_count_stream` yields every chosen index once
+//! together with the number of its spokes. It counts the spokes of an index by arithmetic
+//! instead of visiting them, so a draw of 10^10 spokes costs O(population) instead of O(n).
+//! `random_choice_unique_with_counts` collects these pairs, e.g. for aggregation code, which
+//! prefers every distinct index once with its multiplicity over n indices.
+//!
+//! ```rust
+//! extern crate random_choice;
+//! use random_choice::random_choice;
+//! use random_choice::streaming::spokes::CancellationToken;
+//!
+//! # fn main() {
+//! let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+//! let token = CancellationToken::new();
+//!
+//! let mut random_choice = random_choice();
+//! let mut chosen = 0;
+//!
+//! for chunk in random_choice.spokes_f64(&weights, 1000000).chunks(4096, token.clone()) {
+//!     match chunk {
+//!         Ok(indices) => chosen += indices.len(),
+//!         Err(cancelled) => panic!("{}", cancelled),
+//!     }
+//!     // e.g. another task may call token.cancel() while this one awaits
+//! }
+//!
+//! assert_eq!(chosen, 1000000);
+//! # }
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use rand::Rng;
+
+use crate::core::rescale::{self, Rescaling};
+use crate::RandomChoice;
+
+/// A flag shared between a draw and the parties, which may cancel it.
+/// Clones share the same flag.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a token, which is not cancelled.
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    /// Cancels all draws, which check this token or one of its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true, if the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// The error of a cancelled draw.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled {
+    /// The number of indices, which were drawn before the cancellation.
+    pub drawn: usize,
+}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "draw was cancelled after {} choices", self.drawn)
+    }
+}
+
+impl Error for Cancelled {}
+
+/// Yields the indices chosen by stochastic universal sampling in ascending order.
+/// Created by `RandomChoice::spokes_f64`.
+#[derive(Debug, Clone)]
+pub struct Spokes<'a> {
+    weights: &'a [f64],
+    rescaling: Option<Rescaling>,
+    sum: f64,
+    spoke_gap: f64,
+    current_spoke: f64,
+    i: usize,
+    accumulated_weights: f64,
+    n: usize,
+    drawn: usize,
+}
+
+impl<'a> Spokes<'a> {
+    /// @param sum the sum of the weights after they are rescaled.
+    fn new(weights: &'a [f64],
+           rescaling: Option<Rescaling>,
+           sum: f64,
+           n: usize,
+           spin: f64)
+           -> Self {
+        let spoke_gap: f64 = sum / n as f64;
+        let first = if weights.is_empty() { 0.0 } else { weight(weights, rescaling, 0) };
+
+        Spokes {
+            weights,
+            rescaling,
+            sum,
+            spoke_gap,
+            current_spoke: spin * spoke_gap,
+            i: 0,
+            accumulated_weights: first,
+            n: if weights.is_empty() { 0 } else { n },
+            drawn: 0,
+        }
+    }
+
+    /// Returns the number of indices, which have been drawn so far.
+    pub fn drawn(&self) -> usize {
+        self.drawn
+    }
+
+    /// Yields the indices in chunks of chunk_size and checks the token before every chunk.
+    /// @param chunk_size maximum number of indices per chunk. It must be greater than zero.
+    pub fn chunks(self, chunk_size: usize, token: CancellationToken) -> SpokeChunks<'a> {
+        assert!(chunk_size > 0, "chunk size must be greater than zero");
+
+        SpokeChunks {
+            spokes: self,
+            chunk_size,
+            token,
+            cancelled: false,
+        }
+    }
+}
+
+impl<'a> Iterator for Spokes<'a> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.drawn == self.n {
+            return None;
+        }
+
+        // if float leads to inaccurate calculations, which miss some samples,
+        // the last index is repeated
+        if self.current_spoke < self.sum {
+            while self.accumulated_weights < self.current_spoke {
+                self.i += 1;
+                self.accumulated_weights += weight(self.weights, self.rescaling, self.i);
+            }
+            self.current_spoke += self.spoke_gap;
+        }
+
+        self.drawn += 1;
+        Some(self.i)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.n - self.drawn;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a> ExactSizeIterator for Spokes<'a> {}
+
+/// Yields the indices of `Spokes` in chunks, until the draw is finished or cancelled.
+/// After a cancellation, `Cancelled` is yielded once and the iteration ends.
+#[derive(Debug, Clone)]
+pub struct SpokeChunks<'a> {
+    spokes: Spokes<'a>,
+    chunk_size: usize,
+    token: CancellationToken,
+    cancelled: bool,
+}
+
+impl<'a> Iterator for SpokeChunks<'a> {
+    type Item = Result<Vec<usize>, Cancelled>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.cancelled || self.spokes.len() == 0 {
+            return None;
+        }
+
+        if self.token.is_cancelled() {
+            self.cancelled = true;
+            return Some(Err(Cancelled { drawn: self.spokes.drawn() }));
+        }
+
+        Some(Ok(self.spokes.by_ref().take(self.chunk_size).collect()))
+    }
+}
+
+/// Yields the indices chosen by stochastic universal sampling in ascending order together with
+/// the number of times they are chosen. Created by `RandomChoice::sample_count_stream`.
+#[derive(Debug, Clone)]
+pub struct SpokeCounts<'a> {
+    weights: &'a [f64],
+    rescaling: Option<Rescaling>,
+    n: u64,
+    spin: f64,
+    /// The number of spokes per unit of weight.
+    scale: f64,
+    i: usize,
+    accumulated_weights: f64,
+    /// The number of spokes up to the previous index.
+    counted: u64,
+}
+
+impl<'a> SpokeCounts<'a> {
+    /// @param sum the sum of the weights after they are rescaled.
+    fn new(weights: &'a [f64], rescaling: Option<Rescaling>, sum: f64, n: u64, spin: f64) -> Self {
+        SpokeCounts {
+            weights,
+            rescaling,
+            n: if sum > 0.0 { n } else { 0 },
+            spin,
+            scale: n as f64 / sum,
+            i: 0,
+            accumulated_weights: 0.0,
+            counted: 0,
+        }
+    }
+
+    /// Returns the number of spokes at or before the accumulated weight c,
+    /// i.e. of the j ∈ 0..n with (spin + j) * sum / n ≤ c.
+    fn spokes_until(&self, c: f64) -> u64 {
+        let last = c * self.scale - self.spin;
+
+        if last < 0.0 {
+            0
+        } else {
+            // the cast saturates, if the last spoke is beyond u64
+            (last.floor() as u64).saturating_add(1).min(self.n)
+        }
+    }
+}
+
+impl<'a> Iterator for SpokeCounts<'a> {
+    type Item = (usize, u64);
+
+    fn next(&mut self) -> Option<(usize, u64)> {
+        while self.counted < self.n && self.i < self.weights.len() {
+            let i = self.i;
+            self.i += 1;
+            self.accumulated_weights += weight(self.weights, self.rescaling, i);
+
+            // if float leads to inaccurate calculations, which miss some spokes,
+            // the last index gets them
+            let counted = if self.i == self.weights.len() {
+                self.n
+            } else {
+                self.spokes_until(self.accumulated_weights).max(self.counted)
+            };
+
+            let count = counted - self.counted;
+            self.counted = counted;
+
+            if count > 0 {
+                return Some((i, count));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.counted < self.n {
+            (1, Some(self.weights.len() - self.i))
+        } else {
+            (0, Some(0))
+        }
+    }
+}
+
+/// Returns the weight of an index after it is rescaled like by `random_choice_indices_f64`.
+fn weight(weights: &[f64], rescaling: Option<Rescaling>, i: usize) -> f64 {
+    rescale::rescaled(rescaling, weights[i])
+}
+
+impl<RNG: Rng> RandomChoice<RNG> {
+    /// Spins the wheel for n spokes, but chooses the indices lazily.
+    /// It chooses the same indices as `random_choice_indices_f64`, tiny weights are rescaled
+    /// the same way.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param n Number of randomly chosen indices by weight.
+    pub fn spokes_f64<'a>(&mut self, weights: &'a [f64], n: usize) -> Spokes<'a> {
+        let (sum, rescaling) = self.rescaled_sum(weights);
+
+        // next_f64() ∈ [0.0, 1.0)
+        Spokes::new(weights, rescaling, sum, n, self.rng.next_f64())
+    }
+
+    /// Spins the wheel for n spokes and yields every chosen index with its number of spokes.
+    /// The indices and counts are the same as those of `spokes_f64` up to float rounding,
+    /// but n spokes cost O(weights.len()) instead of O(n).
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param n Number of spokes. It may exceed the memory by far.
+    /// @return the pairs of index and multiplicity in ascending order of the indices.
+    ///         The multiplicities sum up to n, unless no weight is positive.
+    pub fn sample_count_stream<'a>(&mut self, weights: &'a [f64], n: u64) -> SpokeCounts<'a> {
+        let (sum, rescaling) = self.rescaled_sum(weights);
+
+        // next_f64() ∈ [0.0, 1.0)
+        SpokeCounts::new(weights, rescaling, sum, n, self.rng.next_f64())
+    }
+
+    /// Chooses n indices by their weights and returns every distinct index once together with
+    /// the number of times it is chosen. The pairs are the ones of `sample_count_stream`.
+    ///
+    /// @invariant sum of weights must not overflow.
+    /// @param weights Weights of the indices 0..weights.len(). One weight can be greater 1.
+    /// @param n Number of randomly chosen indices by weight. It must not exceed u32::MAX.
+    /// @return the pairs of index and multiplicity in ascending order of the indices.
+    ///         The multiplicities sum up to n, unless no weight is positive.
+    pub fn random_choice_unique_with_counts(&mut self,
+                                            weights: &[f64],
+                                            n: usize)
+                                            -> Vec<(usize, u32)> {
+        assert!(n <= u32::MAX as usize, "n must not exceed u32::MAX");
+
+        self.sample_count_stream(weights, n as u64)
+            .map(|(i, count)| (i, count as u32))
+            .collect()
+    }
+}
@@ -0,0 +1,283 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use random_choice::prepared::alias::{AliasTable, AliasTable32, F32_ERROR_BOUND};
+    use random_choice::core::progress::{Phase, Progress};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_alias_table_frequencies() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 0.0];
+        let table = AliasTable::new(&weights);
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 100000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for index in table.sample_n(&mut rng, number_choices) {
+            counter[index] += 1;
+        }
+
+        assert_eq!(counter[4], 0);
+
+        for i in 0..4 {
+            let expected = number_choices as f64 * weights[i] / 10.0;
+            assert!((counter[i] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_alias_table_probabilities_sum_up() {
+        let capacity: usize = 1000;
+        let weights: Vec<f64> = (0..capacity).map(|i| (i % 17) as f64 + 0.5).collect();
+        let table = AliasTable::new(&weights);
+        let sum: f64 = weights.iter().sum();
+
+        // reconstruct the probability of each index from the slots
+        let mut probabilities = vec![0.0; capacity];
+
+        for slot in 0..capacity {
+            probabilities[slot] += table.probabilities()[slot] / capacity as f64;
+            probabilities[table.aliases()[slot]] += (1.0 - table.probabilities()[slot]) /
+                                                    capacity as f64;
+        }
+
+        for i in 0..capacity {
+            assert!((probabilities[i] - weights[i] / sum).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_alias_table_empty() {
+        let table = AliasTable::new(&[]);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(table.is_empty());
+        assert_eq!(table.sample(&mut rng), None);
+        assert!(table.sample_n(&mut rng, 10).is_empty());
+    }
+
+    #[test]
+    fn test_alias_table_zero_weights() {
+        let table = AliasTable::new(&[0.0, 0.0, 0.0]);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert_eq!(table.sample_n(&mut rng, 10).len(), 10);
+    }
+
+    #[test]
+    fn test_alias_table_32_frequencies() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 0.0];
+        let table = AliasTable32::new(&weights).unwrap();
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 100000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for index in table.sample_n(&mut rng, number_choices) {
+            counter[index] += 1;
+        }
+
+        assert_eq!(counter[4], 0);
+
+        for i in 0..4 {
+            let expected = number_choices as f64 * weights[i] / 10.0;
+            assert!((counter[i] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_alias_table_32_error_within_bound() {
+        let capacity: usize = 100000;
+        let weights: Vec<f64> = (0..capacity).map(|i| ((i * 7919) % 1013) as f64 + 0.1).collect();
+        let table = AliasTable32::new(&weights).unwrap();
+        let sum: f64 = weights.iter().sum();
+
+        let mut probabilities = vec![0.0; capacity];
+
+        for slot in 0..capacity {
+            let p = table.probabilities()[slot] as f64;
+            probabilities[slot] += p / capacity as f64;
+            probabilities[table.aliases()[slot] as usize] += (1.0 - p) / capacity as f64;
+        }
+
+        let total_error: f64 = (0..capacity)
+            .map(|i| (probabilities[i] - weights[i] / sum).abs())
+            .sum::<f64>() / 2.0;
+
+        assert!(total_error <= F32_ERROR_BOUND);
+        assert!((total_error - table.total_error()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_alias_table_32_halves_memory() {
+        let table = AliasTable32::new(&[1.0, 2.0, 3.0]).unwrap();
+
+        assert_eq!(std::mem::size_of_val(table.probabilities()) +
+                   std::mem::size_of_val(table.aliases()),
+                   3 * 8);
+    }
+
+    #[test]
+    fn test_alias_table_32_empty_and_zero_weights() {
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let empty = AliasTable32::new(&[]).unwrap();
+
+        assert!(empty.is_empty());
+        assert_eq!(empty.sample(&mut rng), None);
+
+        let zeros = AliasTable32::new(&[0.0, 0.0, 0.0]).unwrap();
+
+        assert_eq!(zeros.len(), 3);
+        assert_eq!(zeros.sample_n(&mut rng, 10).len(), 10);
+    }
+
+    #[test]
+    fn test_alias_table_with_progress() {
+        let capacity: usize = 1000;
+        let weights: Vec<f64> = (0..capacity).map(|i| i as f64).collect();
+        let mut reports: Vec<Progress> = Vec::new();
+
+        let table = AliasTable::with_progress(&weights, 100, |progress| {
+                reports.push(*progress);
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+
+        assert_eq!(table, AliasTable::new(&weights));
+        assert_eq!(reports.len(), 30);
+        assert_eq!(reports[0],
+                   Progress {
+                       phase: Phase::Summing,
+                       processed: 100,
+                       total: capacity,
+                   });
+
+        for &phase in &[Phase::Summing, Phase::Partitioning, Phase::Pairing] {
+            let processed: Vec<usize> = reports.iter()
+                .filter(|progress| progress.phase == phase)
+                .map(|progress| progress.processed)
+                .collect();
+            assert_eq!(processed, (1..11).map(|i| i * 100).collect::<Vec<usize>>());
+        }
+    }
+
+    #[test]
+    fn test_alias_table_with_progress_aborted() {
+        let weights: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+
+        let error = AliasTable::with_progress(&weights, 250, |progress| {
+                if progress.phase == Phase::Partitioning && progress.processed >= 500 {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .unwrap_err();
+
+        assert_eq!(error.progress.phase, Phase::Partitioning);
+        assert_eq!(error.progress.processed, 500);
+    }
+
+    #[cfg(feature = "mmap")]
+    mod shared {
+        use std::env;
+        use std::fs::{self, File};
+        use std::io::{ErrorKind, Write};
+        use std::path::PathBuf;
+
+        use random_choice::prepared::alias::{AliasTable, SharedAliasTable, FORMAT_VERSION};
+        use rand::SeedableRng;
+
+        fn temp_path(name: &str) -> PathBuf {
+            env::temp_dir().join(format!("random_choice_{}_{}", name, ::std::process::id()))
+        }
+
+        #[test]
+        fn test_shared_alias_table_matches_in_memory_table() {
+            let weights: Vec<f64> = (0..500).map(|i| i as f64).collect();
+            let path = temp_path("shared_alias");
+
+            let shared = SharedAliasTable::create(&path, &weights).unwrap();
+            let table = AliasTable::new(&weights);
+
+            assert_eq!(shared.len(), table.len());
+
+            for slot in 0..table.len() {
+                assert_eq!(shared.probability(slot), table.probabilities()[slot]);
+                assert_eq!(shared.alias(slot), table.aliases()[slot]);
+            }
+
+            let mut rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let mut rng_shared = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+            assert_eq!(table.sample_n(&mut rng, 1000),
+                       shared.sample_n(&mut rng_shared, 1000));
+
+            // a second process would open the same file
+            let reopened = SharedAliasTable::open(&path).unwrap();
+            assert_eq!(reopened.len(), table.len());
+
+            fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn test_shared_alias_table_rejects_other_version() {
+            let path = temp_path("shared_alias_version");
+
+            let mut bytes = Vec::new();
+            AliasTable::new(&[1.0, 2.0]).write_to(&mut bytes).unwrap();
+            bytes[8..12].copy_from_slice(&(FORMAT_VERSION + 1).to_le_bytes());
+            File::create(&path).unwrap().write_all(&bytes).unwrap();
+
+            let error = SharedAliasTable::open(&path).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+            // truncated files are rejected as well
+            File::create(&path).unwrap().write_all(&bytes[..30]).unwrap();
+            let error = SharedAliasTable::open(&path).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+            fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn test_shared_alias_table_rejects_corrupt_files() {
+            let path = temp_path("shared_alias_corrupt");
+
+            let mut bytes = Vec::new();
+            AliasTable::new(&[1.0, 2.0, 3.0]).write_to(&mut bytes).unwrap();
+
+            // the first alias points past the last index, which only validate reads
+            let mut corrupt = bytes.clone();
+            corrupt[48..56].copy_from_slice(&3u64.to_le_bytes());
+            File::create(&path).unwrap().write_all(&corrupt).unwrap();
+
+            let table = SharedAliasTable::open(&path).unwrap();
+            assert_eq!(table.validate().unwrap_err().kind(), ErrorKind::InvalidData);
+            assert!(SharedAliasTable::create(&path, &[1.0, 2.0]).unwrap().validate().is_ok());
+
+            // a reserved field, which isn't zero, may belong to a later format
+            let mut corrupt = bytes.clone();
+            corrupt[12..16].copy_from_slice(&1u32.to_le_bytes());
+            File::create(&path).unwrap().write_all(&corrupt).unwrap();
+
+            let error = SharedAliasTable::open(&path).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+            // a length, whose size overflows, is rejected instead of wrapping around
+            let mut corrupt = bytes.clone();
+            corrupt[16..24].copy_from_slice(&(u64::MAX / 16).to_le_bytes());
+            File::create(&path).unwrap().write_all(&corrupt).unwrap();
+
+            let error = SharedAliasTable::open(&path).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+            fs::remove_file(&path).unwrap();
+        }
+    }
+}
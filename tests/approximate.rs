@@ -0,0 +1,125 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::prepared::approximate::{total_variation_bound, ApproximateSampler};
+    use rand::SeedableRng;
+
+    fn weights() -> Vec<f64> {
+        (1..20000).map(|i| 1.0 / i as f64 + ((i * 7919) % 1000) as f64).collect()
+    }
+
+    /// The total variation distance between the weights and the rounded weights of the bands.
+    fn total_variation(sampler: &ApproximateSampler, weights: &[f64]) -> f64 {
+        let mut rounded = vec![0.0; weights.len()];
+
+        for band in 0..sampler.bands() {
+            let (representative, members) = sampler.band(band);
+
+            for &i in members {
+                rounded[i] = representative;
+            }
+        }
+
+        let sum: f64 = weights.iter().sum();
+        let rounded_sum: f64 = rounded.iter().sum();
+
+        0.5 * weights.iter()
+            .zip(&rounded)
+            .map(|(w, q)| (w / sum - q / rounded_sum).abs())
+            .sum::<f64>()
+    }
+
+    #[test]
+    fn test_approximate_sampler_total_variation_bound() {
+        let weights = weights();
+
+        for &bits in &[0, 1, 4, 8, 16] {
+            let sampler = ApproximateSampler::new(&weights, bits);
+
+            assert_eq!(sampler.total_variation_bound(), total_variation_bound(bits));
+            assert!(total_variation(&sampler, &weights) <= sampler.total_variation_bound());
+        }
+
+        assert_eq!(total_variation_bound(0), 0.5);
+        assert_eq!(total_variation_bound(8), 1.0 / 512.0);
+        assert_eq!(total_variation_bound(60), 0.0);
+    }
+
+    #[test]
+    fn test_approximate_sampler_has_few_bands() {
+        let weights = weights();
+        let sampler = ApproximateSampler::new(&weights, 4);
+
+        assert_eq!(sampler.len(), weights.len());
+        assert!(sampler.bands() < 300);
+
+        let members: usize = (0..sampler.bands()).map(|band| sampler.band(band).1.len()).sum();
+        assert_eq!(members, weights.len());
+    }
+
+    #[test]
+    fn test_approximate_sampler_frequencies() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 0.0, -1.0];
+        let sampler = ApproximateSampler::new(&weights, 8);
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 100000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for index in sampler.sample_n(&mut rng, number_choices) {
+            counter[index] += 1;
+        }
+
+        assert_eq!(counter[4], 0);
+        assert_eq!(counter[5], 0);
+
+        // these weights are exact in 8 bits
+        for i in 0..4 {
+            let expected = number_choices as f64 * weights[i] / 10.0;
+            assert!((counter[i] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_approximate_sampler_zero_and_empty() {
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        let zeros = ApproximateSampler::new(&[0.0, 0.0, 0.0], 8);
+        assert_eq!(zeros.sample_n(&mut rng, 10).len(), 10);
+
+        let empty = ApproximateSampler::new(&[], 8);
+        assert!(empty.is_empty());
+        assert_eq!(empty.sample(&mut rng), None);
+        assert!(empty.sample_n(&mut rng, 10).is_empty());
+    }
+
+    #[test]
+    fn test_approximate_sampler_subnormal_weights() {
+        // both weights are a few hundred multiples of the smallest subnormal, so clearing their
+        // lower bits would round them down to zero
+        let weights: Vec<f64> = vec![5e-321, 1.5e-320, 0.0];
+        let sampler = ApproximateSampler::new(&weights, 8);
+
+        assert_eq!(sampler.bands(), 2);
+
+        for band in 0..sampler.bands() {
+            let (representative, members) = sampler.band(band);
+            assert_eq!(representative, weights[members[0]]);
+        }
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 100000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for index in sampler.sample_n(&mut rng, number_choices) {
+            counter[index] += 1;
+        }
+
+        assert_eq!(counter[2], 0);
+
+        let expected = number_choices as f64 / 4.0;
+        assert!((counter[0] as f64 - expected).abs() < expected * 0.05);
+    }
+}
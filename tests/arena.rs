@@ -0,0 +1,96 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "slotmap")]
+    mod slot_map {
+        extern crate slotmap;
+
+        use self::slotmap::{DefaultKey, SlotMap};
+        use random_choice::RandomChoice;
+        use random_choice::core::collection::WeightedCollection;
+        use rand::SeedableRng;
+
+        #[test]
+        fn test_slot_map_keys_match_indices() {
+            let mut population: SlotMap<DefaultKey, f64> = SlotMap::new();
+            let keys: Vec<DefaultKey> = (0..1000)
+                .map(|i| population.insert((i % 7) as f64))
+                .collect();
+
+            // removed entries leave holes in the slots
+            for key in keys.iter().step_by(3) {
+                population.remove(*key);
+            }
+
+            let weights: Vec<f64> = population.values().cloned().collect();
+            assert_eq!(WeightedCollection::len(&population), weights.len());
+            assert_eq!(population.total_weight(), weights.iter().sum::<f64>());
+            assert_eq!(WeightedCollection::weight(&population, 10), weights[10]);
+
+            let rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let chosen = RandomChoice::new(rng).random_choice_keys_from(&population, 300);
+
+            let rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let indices = RandomChoice::new(rng).random_choice_indices_f64(&weights, 300);
+            let all_keys: Vec<DefaultKey> = population.keys().collect();
+
+            assert_eq!(chosen, indices.iter().map(|&i| all_keys[i]).collect::<Vec<_>>());
+            assert!(chosen.iter().all(|&key| population[key] > 0.0));
+        }
+
+        #[test]
+        fn test_empty_slot_map() {
+            let population: SlotMap<DefaultKey, f32> = SlotMap::new();
+            let rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+            assert!(RandomChoice::new(rng).random_choice_keys_from(&population, 3).is_empty());
+        }
+    }
+
+    #[cfg(feature = "generational-arena")]
+    mod generational {
+        extern crate generational_arena;
+
+        use self::generational_arena::{Arena, Index};
+        use random_choice::RandomChoice;
+        use random_choice::core::collection::Weighted;
+        use rand::SeedableRng;
+
+        struct Creature {
+            fitness: f64,
+        }
+
+        impl Weighted for Creature {
+            fn weight(&self) -> f64 {
+                self.fitness
+            }
+        }
+
+        #[test]
+        fn test_arena_keys_match_indices() {
+            let mut population: Arena<Creature> = Arena::new();
+            let keys: Vec<Index> = (0..600)
+                .map(|i| population.insert(Creature { fitness: (i % 5) as f64 }))
+                .collect();
+
+            population.remove(keys[4]);
+            population.remove(keys[300]);
+
+            let entries: Vec<(Index, f64)> = population.iter()
+                .map(|(index, creature)| (index, creature.fitness))
+                .collect();
+            let weights: Vec<f64> = entries.iter().map(|&(_, w)| w).collect();
+
+            let rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let chosen = RandomChoice::new(rng).random_choice_keys_from(&population, 100);
+
+            let rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let indices = RandomChoice::new(rng).random_choice_indices_f64(&weights, 100);
+
+            assert_eq!(chosen, indices.iter().map(|&i| entries[i].0).collect::<Vec<_>>());
+            assert!(!chosen.contains(&keys[4]));
+        }
+    }
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "arrow")]
+
+extern crate arrow_array;
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use arrow_array::{Array, Float64Array, UInt64Array};
+    use random_choice::random_choice;
+    use random_choice::RandomChoice;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_random_choice_arrow_f64_matches_slice() {
+        let capacity: usize = 500;
+        let weights: Vec<f64> = (0..capacity).map(|i| i as f64).collect();
+        let array = Float64Array::from(weights.clone());
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let indices = RandomChoice::new(rng).random_choice_arrow_f64(&array, 1000);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let expected = RandomChoice::new(rng).random_choice_indices_f64(&weights, 1000);
+
+        assert_eq!(indices.len(), 1000);
+        assert_eq!(indices.null_count(), 0);
+
+        for (index, expected) in indices.values().iter().zip(expected) {
+            assert_eq!(*index as usize, expected);
+        }
+    }
+
+    #[test]
+    fn test_random_choice_arrow_f64_skips_nulls() {
+        let capacity: usize = 500;
+        let weights: Vec<Option<f64>> = (0..capacity)
+            .map(|i| if i % 2 == 0 { None } else { Some(1000.0) })
+            .collect();
+        let array = Float64Array::from(weights);
+
+        let indices = random_choice().random_choice_arrow_f64(&array, 10000);
+
+        assert_eq!(indices.len(), 10000);
+        assert!(indices.values().iter().all(|&i| i % 2 == 1));
+    }
+
+    #[test]
+    fn test_random_choice_arrow_u64_sliced() {
+        let array = UInt64Array::from(vec![Some(1000), None, Some(0), Some(3), None, Some(7)]);
+        // the slice starts at index 1 of the original array
+        let sliced = array.slice(1, 5);
+
+        let indices = random_choice().random_choice_arrow_u64(&sliced, 1000);
+
+        assert_eq!(indices.len(), 1000);
+        assert!(indices.values().iter().all(|&i| i == 2 || i == 4));
+    }
+
+    #[test]
+    fn test_random_choice_arrow_all_null() {
+        let array = Float64Array::from(vec![None, None, Some(0.0)]);
+
+        let empty = Float64Array::from(Vec::<f64>::new());
+
+        assert_eq!(random_choice().random_choice_arrow_f64(&array, 10).len(), 0);
+        assert_eq!(random_choice().random_choice_arrow_f64(&empty, 10).len(), 0);
+    }
+
+    #[test]
+    fn test_random_choice_arrow_invalid_entries_count_as_null() {
+        let array = Float64Array::from(vec![None, Some(f64::NAN), Some(2.0), Some(-5.0),
+                                            Some(f64::INFINITY), Some(1.0)]);
+
+        let indices = random_choice().random_choice_arrow_f64(&array, 1000);
+
+        assert_eq!(indices.len(), 1000);
+        assert!(indices.values().iter().all(|&i| i == 2 || i == 5));
+
+        let invalid = Float64Array::from(vec![None, Some(f64::NAN), Some(-1.0)]);
+        assert_eq!(random_choice().random_choice_arrow_f64(&invalid, 10).len(), 0);
+    }
+}
@@ -0,0 +1,120 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::strategies::bandit::{BetaBandit, Exp3};
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_bandit_updates_posteriors() {
+        let mut bandit = BetaBandit::new(2);
+
+        bandit.update(0, 1.0);
+        bandit.update(0, 1.0);
+        bandit.update(1, 0.0);
+        bandit.update(1, 0.25);
+
+        assert_eq!(bandit.posterior(0), (3.0, 1.0));
+        assert_eq!(bandit.posterior(1), (1.25, 2.75));
+        assert_eq!(bandit.means(), vec![0.75, 0.3125]);
+    }
+
+    #[test]
+    fn test_bandit_converges_to_best_arm() {
+        let rates = [0.1, 0.3, 0.6];
+        let mut bandit = BetaBandit::new(rates.len());
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut pulls = [0usize; 3];
+
+        for _ in 0..5000 {
+            let arm = bandit.select_arm(&mut rng).unwrap();
+            let reward = if rng.next_f64() < rates[arm] { 1.0 } else { 0.0 };
+
+            bandit.update(arm, reward);
+            pulls[arm] += 1;
+        }
+
+        assert!(pulls[2] > 4500, "pulls: {:?}", pulls);
+        assert!((bandit.means()[2] - 0.6).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_bandit_selects_by_probability_of_being_best() {
+        // both posteriors are equal, so both arms are the best one half of the time
+        let bandit = BetaBandit::with_prior(2, 0.5, 0.5);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        let first = (0..10000).filter(|_| bandit.select_arm(&mut rng) == Some(0)).count();
+
+        assert!((first as f64 / 10000.0 - 0.5).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_bandit_without_arms() {
+        let mut bandit = BetaBandit::new(0);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(bandit.is_empty());
+        assert_eq!(bandit.select_arm(&mut rng), None);
+
+        assert_eq!(bandit.add_arm(), 0);
+        assert_eq!(bandit.len(), 1);
+        assert_eq!(bandit.select_arm(&mut rng), Some(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bandit_rejects_invalid_reward() {
+        BetaBandit::new(1).update(0, 2.0);
+    }
+
+    #[test]
+    fn test_exp3_updates_weights() {
+        let mut bandit = Exp3::new(3, 0.3);
+        assert_eq!(bandit.probabilities(), vec![1.0 / 3.0; 3]);
+
+        // p = 1 / 3, so the estimated loss is 3.0 and the weight is multiplied by exp(-0.1 * 3.0)
+        bandit.update(0, 1.0);
+        assert!((bandit.weights()[0] - (-0.3f64).exp()).abs() < 1e-12);
+        assert_eq!(&bandit.weights()[1..], &[1.0, 1.0]);
+
+        bandit.update(1, 0.0);
+        assert_eq!(bandit.weights()[1], 1.0);
+
+        let probabilities = bandit.probabilities();
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(probabilities[0] < probabilities[1]);
+        assert!(probabilities.iter().all(|&p| p >= 0.1));
+    }
+
+    #[test]
+    fn test_exp3_avoids_lossy_arms() {
+        let losses = [0.9, 0.1, 0.5, 0.7];
+        let mut bandit = Exp3::new(losses.len(), 0.1);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut pulls = [0usize; 4];
+
+        for _ in 0..5000 {
+            let arm = bandit.draw(&mut rng).unwrap();
+            let loss = if rng.next_f64() < losses[arm] { 1.0 } else { 0.0 };
+
+            bandit.update(arm, loss);
+            pulls[arm] += 1;
+        }
+
+        assert!(pulls[1] > 3500, "pulls: {:?}", pulls);
+        assert!(bandit.weights().iter().all(|w| w.is_finite()));
+        assert!(bandit.probabilities()[0] >= 0.1 / 4.0);
+    }
+
+    #[test]
+    fn test_exp3_without_arms() {
+        let bandit = Exp3::new(0, 0.5);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(bandit.is_empty());
+        assert_eq!(bandit.gamma(), 0.5);
+        assert_eq!(bandit.draw(&mut rng), None);
+    }
+}
@@ -0,0 +1,58 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_best_of_maximizes_score_among_candidates() {
+        let samples: Vec<usize> = (0..100).collect();
+        let weights: Vec<f64> = (0..100).map(|i| (i % 10) as f64).collect();
+        // the score prefers small samples, the weights prefer large ones
+        let score = |&s: &usize| -(((s * 37) % 101) as f64);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let best = RandomChoice::new(rng).choose_best_of(&samples, &weights, 8, score);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let candidates = RandomChoice::new(rng).random_choice_indices_f64(&weights, 8);
+        let expected = candidates.iter()
+            .map(|&i| &samples[i])
+            .fold(None, |best: Option<&usize>, s| match best {
+                Some(b) if score(b) >= score(s) => Some(b),
+                _ => Some(s),
+            });
+
+        assert_eq!(best, expected);
+    }
+
+    #[test]
+    fn test_best_of_scores_distinct_candidates_once() {
+        let weights: Vec<f64> = vec![1.0, 0.0, 3.0];
+        let mut scored: Vec<usize> = Vec::new();
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let best = RandomChoice::new(rng).choose_best_index_of(&weights, 100, |i| {
+            scored.push(i);
+            i as f64
+        });
+
+        assert_eq!(best, Some(2));
+        assert_eq!(scored, vec![0, 2]);
+    }
+
+    #[test]
+    fn test_best_of_ties_and_nan() {
+        let samples = vec!["a", "b", "c"];
+        let weights: Vec<f64> = vec![1.0, 1.0, 1.0];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        assert_eq!(random_choice.choose_best_of(&samples, &weights, 3, |_| 1.0), Some(&"a"));
+        assert_eq!(random_choice.choose_best_of(&samples, &weights, 3, |_| f64::NAN), None);
+        assert_eq!(random_choice.choose_best_of(&samples, &weights, 0, |_| 1.0), None);
+        assert_eq!(random_choice.choose_best_of(&samples, &[], 3, |_| 1.0), None);
+    }
+}
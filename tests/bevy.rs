@@ -0,0 +1,166 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+#[cfg(feature = "bevy")]
+mod tests {
+    extern crate bevy_ecs;
+
+    use self::bevy_ecs::prelude::*;
+    use random_choice::integrations::bevy::WeightedQuerySampler;
+    use random_choice::core::collection::Weighted;
+    use rand::{SeedableRng, StdRng};
+
+    #[derive(Component)]
+    struct Fitness(f64);
+
+    impl Weighted for Fitness {
+        fn weight(&self) -> f64 {
+            self.0
+        }
+    }
+
+    #[derive(Resource)]
+    struct Draws {
+        rng: StdRng,
+        chosen: Vec<Entity>,
+        rebuilds: usize,
+        skip: bool,
+    }
+
+    fn draw(mut sampler: WeightedQuerySampler<Fitness>, mut draws: ResMut<Draws>) {
+        let draws = &mut *draws;
+
+        if draws.skip {
+            return;
+        }
+
+        draws.chosen = sampler.sample_n(&mut draws.rng, 1000);
+        draws.rebuilds = sampler.rebuilds();
+    }
+
+    fn setup() -> (World, Schedule, Vec<Entity>) {
+        let mut world = World::new();
+        world.insert_resource(Draws {
+            rng: StdRng::from_seed(&[5000, 44, 55, 199]),
+            chosen: Vec::new(),
+            rebuilds: 0,
+            skip: false,
+        });
+
+        let entities = [1.0, 2.0, 0.0, 7.0]
+            .iter()
+            .map(|&weight| world.spawn(Fitness(weight)).id())
+            .collect();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(draw);
+
+        (world, schedule, entities)
+    }
+
+    fn count(world: &World, entity: Entity) -> usize {
+        world.resource::<Draws>().chosen.iter().filter(|&&e| e == entity).count()
+    }
+
+    #[test]
+    fn test_samples_by_weight() {
+        let (mut world, mut schedule, entities) = setup();
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Draws>().chosen.len(), 1000);
+        assert_eq!(count(&world, entities[0]), 100);
+        assert_eq!(count(&world, entities[1]), 200);
+        assert_eq!(count(&world, entities[2]), 0);
+        assert_eq!(count(&world, entities[3]), 700);
+    }
+
+    #[test]
+    fn test_cache_is_reused_without_changes() {
+        let (mut world, mut schedule, _) = setup();
+
+        for _ in 0..5 {
+            schedule.run(&mut world);
+        }
+
+        assert_eq!(world.resource::<Draws>().rebuilds, 1);
+    }
+
+    #[test]
+    fn test_cache_is_rebuilt_on_change() {
+        let (mut world, mut schedule, entities) = setup();
+        schedule.run(&mut world);
+
+        world.get_mut::<Fitness>(entities[3]).unwrap().0 = 0.0;
+        world.get_mut::<Fitness>(entities[2]).unwrap().0 = 7.0;
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Draws>().rebuilds, 2);
+        assert_eq!(count(&world, entities[2]), 700);
+        assert_eq!(count(&world, entities[3]), 0);
+
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Draws>().rebuilds, 2);
+    }
+
+    #[test]
+    fn test_cache_is_rebuilt_on_spawn_and_despawn() {
+        let (mut world, mut schedule, entities) = setup();
+        schedule.run(&mut world);
+
+        world.despawn(entities[3]);
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Draws>().rebuilds, 2);
+        assert_eq!(count(&world, entities[0]), 333);
+        assert_eq!(count(&world, entities[3]), 0);
+
+        let spawned = world.spawn(Fitness(3.0)).id();
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Draws>().rebuilds, 3);
+        assert_eq!(count(&world, spawned), 500);
+    }
+
+    #[test]
+    fn test_cache_sees_changes_of_runs_without_draws() {
+        let (mut world, mut schedule, entities) = setup();
+        schedule.run(&mut world);
+
+        world.resource_mut::<Draws>().skip = true;
+        world.despawn(entities[3]);
+        world.get_mut::<Fitness>(entities[1]).unwrap().0 = 7.0;
+
+        // long enough for the removal events to expire
+        for _ in 0..5 {
+            schedule.run(&mut world);
+            world.clear_trackers();
+        }
+
+        world.resource_mut::<Draws>().skip = false;
+        schedule.run(&mut world);
+
+        assert_eq!(world.resource::<Draws>().rebuilds, 2);
+        assert_eq!(count(&world, entities[0]), 125);
+        assert_eq!(count(&world, entities[1]), 875);
+        assert_eq!(count(&world, entities[3]), 0);
+    }
+
+    #[test]
+    fn test_no_positive_weight() {
+        let mut world = World::new();
+        world.insert_resource(Draws {
+            rng: StdRng::from_seed(&[5000, 44, 55, 199]),
+            chosen: vec![Entity::PLACEHOLDER],
+            rebuilds: 0,
+            skip: false,
+        });
+        world.spawn(Fitness(0.0));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(draw);
+        schedule.run(&mut world);
+
+        assert!(world.resource::<Draws>().chosen.is_empty());
+    }
+}
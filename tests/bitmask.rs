@@ -0,0 +1,58 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::strategies::bitmask::{self, WORD_BITS};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_bitmask_marks_chosen_indices() {
+        let weights: Vec<f64> = (0..200).map(|i| (i % 7) as f64).collect();
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mask = RandomChoice::new(rng).random_choice_bitmask(&weights, 50);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut expected = RandomChoice::new(rng).random_choice_indices_f64(&weights, 50);
+        expected.dedup();
+
+        assert_eq!(mask.len(), 200 / WORD_BITS + 1);
+        assert_eq!(bitmask::selected_indices(&mask), expected);
+
+        for (i, &weight) in weights.iter().enumerate() {
+            assert_eq!(bitmask::is_selected(&mask, i), expected.contains(&i));
+
+            if weight == 0.0 {
+                assert!(!bitmask::is_selected(&mask, i));
+            }
+        }
+
+        assert!(!bitmask::is_selected(&mask, 1000));
+    }
+
+    #[test]
+    fn test_bitmask_word_boundaries() {
+        let mut weights: Vec<f64> = vec![0.0; 128];
+        weights[0] = 1.0;
+        weights[63] = 1.0;
+        weights[64] = 1.0;
+        weights[127] = 1.0;
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mask = RandomChoice::new(rng).random_choice_bitmask(&weights, 4);
+
+        assert_eq!(mask, vec![1 | 1 << 63, 1 | 1 << 63]);
+        assert_eq!(bitmask::selected_indices(&mask), vec![0, 63, 64, 127]);
+    }
+
+    #[test]
+    fn test_bitmask_empty() {
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        assert!(random_choice.random_choice_bitmask(&[], 10).is_empty());
+        assert_eq!(random_choice.random_choice_bitmask(&[1.0, 2.0], 0), vec![0]);
+    }
+}
@@ -0,0 +1,104 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::prepared::fenwick::FenwickSampler;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_budgeted_stops_at_first_unaffordable_pick() {
+        let weights: Vec<f64> = vec![5.6, 7.8, 0.0, 9.7, 1.1];
+        let costs: Vec<f64> = vec![1.0, 2.0, 0.5, 3.0, 1.5];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let sample = RandomChoice::new(rng).random_choice_budgeted_f64(&weights, &costs, 20.0);
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let sampler = FenwickSampler::new(&weights);
+        let draws: Vec<usize> = (0..sample.indices.len() + 1)
+            .map(|_| sampler.sample(&mut rng).unwrap())
+            .collect();
+
+        let spent: f64 = sample.indices.iter().map(|&i| costs[i]).sum();
+
+        assert_eq!(sample.indices[..], draws[..sample.indices.len()]);
+        assert!((spent + sample.remaining - 20.0).abs() < 1e-12);
+        assert!(costs[draws[sample.indices.len()]] > sample.remaining);
+        assert!(!sample.indices.contains(&2));
+    }
+
+    #[test]
+    fn test_budgeted_without_replacement_picks_distinct_indices() {
+        let weights: Vec<f64> = vec![5.6, 7.8, 0.0, 9.7, 1.1];
+        let costs: Vec<f64> = vec![1.0, 2.0, 0.5, 3.0, 1.5];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let sample = RandomChoice::new(rng)
+            .random_choice_budgeted_without_replacement_f64(&weights, &costs, 100.0);
+
+        let mut indices = sample.indices.clone();
+        indices.sort_unstable();
+
+        // everything with a positive weight is affordable
+        assert_eq!(indices, vec![0, 1, 3, 4]);
+        assert_eq!(sample.remaining, 92.5);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let order = RandomChoice::new(rng)
+            .random_choice_indices_without_replacement_f64(&weights, 5);
+        assert_eq!(sample.indices, order);
+    }
+
+    #[test]
+    fn test_budgeted_without_replacement_stops_early() {
+        let weights: Vec<f64> = vec![1.0, 1.0, 1.0];
+        let costs: Vec<f64> = vec![2.0, 2.0, 2.0];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let sample = RandomChoice::new(rng)
+            .random_choice_budgeted_without_replacement_f64(&weights, &costs, 5.0);
+
+        assert_eq!(sample.indices.len(), 2);
+        assert_eq!(sample.remaining, 1.0);
+    }
+
+    #[test]
+    fn test_budgeted_degenerate() {
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        let sample = random_choice.random_choice_budgeted_f64(&[0.0, 0.0], &[1.0, 1.0], 10.0);
+        assert!(sample.indices.is_empty());
+        assert_eq!(sample.remaining, 10.0);
+
+        let sample = random_choice.random_choice_budgeted_f64(&[1.0], &[1.0], 0.5);
+        assert!(sample.indices.is_empty());
+        assert_eq!(sample.remaining, 0.5);
+
+        let sample = random_choice.random_choice_budgeted_without_replacement_f64(&[], &[], 1.0);
+        assert!(sample.indices.is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_budgeted_rejects_free_items() {
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        RandomChoice::new(rng).random_choice_budgeted_f64(&[1.0], &[0.0], 1.0);
+    }
+
+    #[test]
+    fn test_budgeted_stops_at_negligible_cost() {
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        let sample = random_choice.random_choice_budgeted_f64(&[1.0], &[1e-20], 1.0);
+        assert!(sample.indices.is_empty());
+        assert_eq!(sample.remaining, 1.0);
+
+        let sample = random_choice.random_choice_budgeted_f64(&[1.0, 1.0], &[1e-20, 0.25], 1.0);
+        assert!(sample.indices.iter().all(|&i| i == 1));
+        assert!(sample.remaining >= 0.0);
+    }
+}
@@ -0,0 +1,97 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+
+    use random_choice::strategies::capacity::CapacitySampler;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_capacity_sampler_exhausts_capacities() {
+        let capacities: Vec<usize> = vec![3, 0, 5, 1, 2];
+        let sampler = CapacitySampler::new(&[1.0, 2.0, 3.0, 4.0, 0.0], &capacities);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        let choices = sampler.sample_n(&mut rng, 100);
+        let mut counter = vec![0usize; capacities.len()];
+
+        for &i in &choices {
+            counter[i] += 1;
+        }
+
+        // the index without weight is never chosen, despite its capacity
+        assert_eq!(counter, vec![3, 0, 5, 1, 0]);
+        assert_eq!(sampler.total_remaining(), 2);
+        assert_eq!(sampler.sample(&mut rng), None);
+    }
+
+    #[test]
+    fn test_capacity_sampler_frequencies() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_samplers = 20000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for _ in 0..number_samplers {
+            let sampler = CapacitySampler::new(&weights, &[10, 10, 10, 10]);
+            counter[sampler.sample(&mut rng).unwrap()] += 1;
+        }
+
+        for i in 0..weights.len() {
+            let expected = number_samplers as f64 * weights[i] / 10.0;
+            assert!((counter[i] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_capacity_sampler_restock() {
+        let sampler = CapacitySampler::new(&[1.0, 1.0], &[1, 0]);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert_eq!(sampler.sample(&mut rng), Some(0));
+        assert_eq!(sampler.sample(&mut rng), None);
+
+        sampler.restock(1, 2);
+        assert_eq!(sampler.remaining(1), 2);
+        assert_eq!(sampler.sample_n(&mut rng, 5), vec![1, 1]);
+    }
+
+    #[test]
+    fn test_capacity_sampler_shared_between_threads() {
+        let capacities: Vec<usize> = (0..50).map(|i| i % 7 + 1).collect();
+        let weights: Vec<f64> = (0..50).map(|i| (i % 5 + 1) as f64).collect();
+        let sampler = Arc::new(CapacitySampler::new(&weights, &capacities));
+
+        let handles: Vec<_> = (0..4)
+            .map(|t| {
+                let sampler = sampler.clone();
+
+                thread::spawn(move || {
+                    let mut rng = ::rand::StdRng::from_seed(&[5000, 44, 55, t]);
+                    let mut choices = Vec::new();
+
+                    while let Some(i) = sampler.sample(&mut rng) {
+                        choices.push(i);
+                    }
+
+                    choices
+                })
+            })
+            .collect();
+
+        let mut counter = vec![0usize; capacities.len()];
+
+        for handle in handles {
+            for i in handle.join().unwrap() {
+                counter[i] += 1;
+            }
+        }
+
+        // every unit of capacity was handed out exactly once
+        assert_eq!(counter, capacities);
+        assert_eq!(sampler.total_remaining(), 0);
+    }
+}
@@ -0,0 +1,75 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::strategies::categorical::{Categorical, CategoricalError};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_categorical_frequencies() {
+        let categorical = Categorical::new(&[("a", 1.0), ("b", 2.0), ("c", 3.0), ("d", 4.0),
+                                             ("never", 0.0)])
+            .unwrap();
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 40000;
+        let mut counter = [0usize; 5];
+
+        for _ in 0..number_choices {
+            let label = categorical.sample(&mut rng).unwrap();
+            counter[categorical.labels().iter().position(|l| l == label).unwrap()] += 1;
+        }
+
+        assert_eq!(counter[4], 0);
+
+        for (i, &count) in counter[..4].iter().enumerate() {
+            let expected = number_choices as f64 * (i + 1) as f64 / 10.0;
+            assert!((count as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_categorical_sample_n() {
+        let categorical = Categorical::new(&[("x", 1.0), ("y", 3.0)]).unwrap();
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert_eq!(categorical.sample_n(&mut rng, 4), vec!["x", "y", "y", "y"]);
+    }
+
+    #[test]
+    fn test_categorical_probabilities() {
+        let pairs = vec![("sunny".to_string(), 6.0), ("cloudy".to_string(), 2.0),
+                         ("dry".to_string(), 0.0)];
+        let categorical = Categorical::new(&pairs).unwrap();
+
+        assert_eq!(categorical.len(), 3);
+        assert_eq!(categorical.total_weight(), 8.0);
+        assert_eq!(categorical.weight("cloudy"), Some(2.0));
+        assert_eq!(categorical.probability("sunny"), Some(0.75));
+        assert!((categorical.log_prob("cloudy").unwrap() - 0.25f64.ln()).abs() < 1e-12);
+        assert_eq!(categorical.log_prob("dry"), Some(f64::NEG_INFINITY));
+        assert_eq!(categorical.log_prob("snowy"), None);
+    }
+
+    #[test]
+    fn test_categorical_errors() {
+        assert_eq!(Categorical::new(&[("a", 1.0), ("b", 2.0), ("a", 3.0)]).unwrap_err(),
+                   CategoricalError::DuplicateLabel("a".to_string()));
+        assert_eq!(Categorical::new(&[("a", 1.0), ("b", -2.0)]).unwrap_err(),
+                   CategoricalError::InvalidWeight("b".to_string(), -2.0));
+        assert!(Categorical::new(&[("a", f64::NAN)]).is_err());
+        assert_eq!(CategoricalError::DuplicateLabel("a".to_string()).to_string(),
+                   "label \"a\" appears more than once");
+    }
+
+    #[test]
+    fn test_categorical_empty() {
+        let pairs: [(&str, f64); 0] = [];
+        let categorical = Categorical::new(&pairs).unwrap();
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(categorical.is_empty());
+        assert_eq!(categorical.sample(&mut rng), None);
+        assert!(categorical.sample_n(&mut rng, 10).is_empty());
+    }
+}
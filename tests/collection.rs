@@ -0,0 +1,94 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::core::collection::{ChunkedPrefixSums, WeightedCollection};
+    use random_choice::prepared::PreparedChoice;
+    use rand::SeedableRng;
+
+    fn weights() -> Vec<f64> {
+        (0..1000).map(|i| ((i * 13) % 17) as f64).collect()
+    }
+
+    /// Splits the weights into chunks of different sizes, including empty ones.
+    fn shards(weights: &[f64]) -> Vec<Vec<f64>> {
+        let mut shards: Vec<Vec<f64>> = vec![Vec::new()];
+        let mut start = 0;
+        let mut size = 1;
+
+        while start < weights.len() {
+            let end = (start + size).min(weights.len());
+            shards.push(weights[start..end].to_vec());
+            shards.push(Vec::new());
+            start = end;
+            size = size * 3 % 101 + 1;
+        }
+
+        shards
+    }
+
+    #[test]
+    fn test_chunked_weights_match_contiguous_weights() {
+        let weights = weights();
+        let shards = shards(&weights);
+        let slices: Vec<&[f64]> = shards.iter().map(|shard| &shard[..]).collect();
+
+        for &n in &[1, 10, 1000, 10000] {
+            let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let expected = RandomChoice::new(rng).random_choice_indices_f64(&weights, n);
+
+            let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let from_slices = RandomChoice::new(rng).random_choice_indices_from(&slices[..], n);
+
+            let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let from_vecs = RandomChoice::new(rng).random_choice_indices_from(&shards, n);
+
+            assert_eq!(from_slices, expected);
+            assert_eq!(from_vecs, expected);
+        }
+    }
+
+    #[test]
+    fn test_chunked_prefix_sums_match_prepared_choice() {
+        let weights = weights();
+        let shards = shards(&weights);
+        let chunked = ChunkedPrefixSums::new(&shards);
+        let prepared = PreparedChoice::new(&weights);
+
+        assert_eq!(chunked.len(), weights.len());
+        assert_eq!(chunked.chunks(), shards.iter().filter(|shard| !shard.is_empty()).count());
+        assert_eq!(chunked.total_weight(), prepared.total_weight());
+
+        for &n in &[1, 3, 1000, 10000] {
+            let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let mut rng_prepared = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+            assert_eq!(chunked.sample_n(&mut rng, n), prepared.sample_n(&mut rng_prepared, n));
+        }
+    }
+
+    #[test]
+    fn test_weighted_collection_weights() {
+        let weights = weights();
+        let shards = shards(&weights);
+
+        assert_eq!(WeightedCollection::len(&shards), weights.len());
+        assert_eq!(shards.total_weight(), weights.total_weight());
+
+        for (i, &weight) in weights.iter().enumerate() {
+            assert_eq!(shards.weight(i), weight);
+        }
+    }
+
+    #[test]
+    fn test_chunked_weights_empty() {
+        let shards: Vec<Vec<f64>> = vec![Vec::new(), Vec::new()];
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(WeightedCollection::is_empty(&shards));
+        assert!(RandomChoice::new(rng).random_choice_indices_from(&shards, 10).is_empty());
+        assert!(ChunkedPrefixSums::new(&shards).sample_n(&mut rng, 10).is_empty());
+    }
+}
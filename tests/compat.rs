@@ -0,0 +1,102 @@
+extern crate random_choice;
+#[cfg(feature = "rand-compat")]
+extern crate rand_compat;
+
+#[cfg(test)]
+#[cfg(feature = "rand-compat")]
+mod tests {
+    use random_choice::prepared::alias::AliasTable;
+    use random_choice::integrations::compat::{WeightError, WeightedIndex};
+    use random_choice::prepared::PreparedChoice;
+    use rand_compat::distr::weighted::WeightedIndex as RandWeightedIndex;
+    use rand_compat::distr::Distribution;
+    use rand_compat::rngs::SmallRng;
+    use rand_compat::SeedableRng;
+
+    #[test]
+    fn test_weighted_index_frequencies() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 0.0];
+        let dist = WeightedIndex::new(&weights).unwrap();
+        let mut rng = SmallRng::seed_from_u64(5000);
+        let number_choices = 100000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for _ in 0..number_choices {
+            counter[dist.sample(&mut rng)] += 1;
+        }
+
+        assert_eq!(counter[4], 0);
+
+        for (i, &weight) in weights.iter().enumerate() {
+            let expected = number_choices as f64 * weight / 10.0;
+            assert!((counter[i] as f64 - expected).abs() <= expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_weighted_index_errors_match_rand() {
+        let cases: Vec<Vec<f64>> = vec![vec![],
+                                         vec![1.0, -1.0],
+                                         vec![1.0, f64::NAN],
+                                         vec![0.0, 0.0],
+                                         vec![f64::MAX, f64::MAX],
+                                         vec![f64::INFINITY]];
+
+        for weights in &cases {
+            let expected = RandWeightedIndex::<f64>::new(weights).map(|_| ()).unwrap_err();
+            assert_eq!(WeightedIndex::new(weights).unwrap_err(), expected);
+        }
+
+        assert_eq!(WeightedIndex::new(Vec::<f64>::new()).unwrap_err(), WeightError::InvalidInput);
+    }
+
+    #[test]
+    fn test_weighted_index_update_weights() {
+        let mut dist = WeightedIndex::new(vec![1.0, 2.0, 3.0]).unwrap();
+        let mut rand_dist = RandWeightedIndex::new(vec![1.0, 2.0, 3.0]).unwrap();
+
+        dist.update_weights(&[(0, &0.0), (2, &5.0)]).unwrap();
+        rand_dist.update_weights(&[(0, &0.0), (2, &5.0)]).unwrap();
+
+        assert_eq!(dist.weights().collect::<Vec<f64>>(), vec![0.0, 2.0, 5.0]);
+        assert_eq!(dist.weights().collect::<Vec<f64>>(),
+                   rand_dist.weights().collect::<Vec<f64>>());
+        assert_eq!(dist.total_weight(), 7.0);
+        assert_eq!(dist.weight(1), Some(2.0));
+        assert_eq!(dist.weight(3), None);
+
+        let before = dist.clone();
+
+        assert_eq!(dist.update_weights(&[(2, &1.0), (1, &1.0)]),
+                   Err(WeightError::InvalidInput));
+        assert_eq!(dist.update_weights(&[(3, &1.0)]), Err(WeightError::InvalidInput));
+        assert_eq!(dist.update_weights(&[(1, &-1.0)]), Err(WeightError::InvalidWeight));
+        assert_eq!(dist.update_weights(&[(1, &0.0), (2, &0.0)]),
+                   Err(WeightError::InsufficientNonZero));
+        assert_eq!(dist, before);
+
+        let mut rng = SmallRng::seed_from_u64(5000);
+        assert!((0..1000).all(|_| dist.sample(&mut rng) != 0));
+    }
+
+    #[test]
+    fn test_conversion_from_rand() {
+        let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+        let rand_dist = RandWeightedIndex::new(&weights).unwrap();
+
+        let dist = WeightedIndex::from(&rand_dist);
+        assert_eq!(dist.total_weight(), rand_dist.total_weight());
+        assert_eq!(dist.alias_table(), &AliasTable::from(&rand_dist));
+
+        let prepared = PreparedChoice::from(&rand_dist);
+        assert_eq!(prepared.len(), weights.len());
+
+        for (i, &weight) in weights.iter().enumerate() {
+            assert!((dist.weight(i).unwrap() - weight).abs() < 1e-12);
+            assert!((prepared.weight(i) - weight).abs() < 1e-12);
+        }
+
+        let owned: WeightedIndex = rand_dist.into();
+        assert_eq!(owned, dist);
+    }
+}
@@ -0,0 +1,109 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::strategies::conditional_poisson::{calibrate, ConditionalPoisson,
+                                             ConditionalPoissonError};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_conditional_poisson_inclusion_probabilities() {
+        let working_probabilities: Vec<f64> = vec![0.1, 0.5, 0.9, 0.5];
+        let design = ConditionalPoisson::new(&working_probabilities, 2).unwrap();
+
+        // enumerate all subsets of size 2 with the product of the odds as weight
+        let odds: Vec<f64> = working_probabilities.iter().map(|p| p / (1.0 - p)).collect();
+        let mut expected = [0.0; 4];
+        let mut total = 0.0;
+
+        for i in 0..4 {
+            for j in (i + 1)..4 {
+                total += odds[i] * odds[j];
+                expected[i] += odds[i] * odds[j];
+                expected[j] += odds[i] * odds[j];
+            }
+        }
+
+        let probabilities = design.inclusion_probabilities();
+
+        for i in 0..4 {
+            assert!((probabilities[i] - expected[i] / total).abs() < 1e-12);
+        }
+
+        let sum: f64 = probabilities.iter().sum();
+        assert!((sum - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_conditional_poisson_calibration() {
+        let targets: Vec<f64> = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.9, 1.0];
+        let working_probabilities = calibrate(&targets, 1e-12, 1000).unwrap();
+
+        let sum: f64 = working_probabilities.iter().sum();
+        assert!((sum - 4.0).abs() < 1e-9);
+        assert_eq!(working_probabilities[7], 1.0);
+
+        let design = ConditionalPoisson::new(&working_probabilities, 4).unwrap();
+
+        for (p, t) in design.inclusion_probabilities().iter().zip(&targets) {
+            assert!((p - t).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_conditional_poisson_sample_frequencies() {
+        let targets: Vec<f64> = vec![0.05, 0.15, 0.3, 0.5, 0.0, 0.7, 0.3];
+        let design = ConditionalPoisson::calibrated(&targets, 1e-12, 1000).unwrap();
+
+        let rng = rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+        let number_samples = 20000;
+        let mut counter = vec![0usize; targets.len()];
+
+        for _ in 0..number_samples {
+            let sample = design.sample(&mut random_choice);
+            assert_eq!(sample.len(), 2);
+            assert!(sample[0] < sample[1]);
+
+            for i in sample {
+                counter[i] += 1;
+            }
+        }
+
+        assert_eq!(counter[4], 0);
+
+        for (&count, &target) in counter.iter().zip(&targets) {
+            let expected = number_samples as f64 * target;
+            assert!((count as f64 - expected).abs() < 0.05 * expected + 50.0);
+        }
+    }
+
+    #[test]
+    fn test_conditional_poisson_errors() {
+        assert_eq!(ConditionalPoisson::new(&[0.5, 1.5], 1).unwrap_err(),
+                   ConditionalPoissonError::InvalidProbability(1));
+        assert_eq!(ConditionalPoisson::new(&[0.5, 0.0], 2).unwrap_err(),
+                   ConditionalPoissonError::InfeasibleSampleSize(2));
+        assert_eq!(ConditionalPoisson::new(&[1.0, 1.0, 0.5], 1).unwrap_err(),
+                   ConditionalPoissonError::InfeasibleSampleSize(1));
+        assert_eq!(calibrate(&[0.5, 0.7], 1e-12, 100).unwrap_err(),
+                   ConditionalPoissonError::NonIntegerSampleSize(1.2));
+
+        match calibrate(&[0.01, 0.99, 0.5, 0.5], 1e-15, 0) {
+            Err(ConditionalPoissonError::NotConverged { iterations: 0, .. }) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_conditional_poisson_empty_design() {
+        let design = ConditionalPoisson::new(&[], 0).unwrap();
+        let mut random_choice = random_choice::random_choice();
+
+        assert!(design.is_empty());
+        assert!(design.sample(&mut random_choice).is_empty());
+        assert!(design.inclusion_probabilities().is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+#[cfg(feature = "config")]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use random_choice::strategies::categorical::{Categorical, CategoricalError};
+    use random_choice::integrations::config::ConfigError;
+    use rand::SeedableRng;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("random_choice_config_{}_{}", std::process::id(), name))
+    }
+
+    #[test]
+    fn test_from_toml_file() {
+        let path = temp_path("split.toml");
+        fs::write(&path, "# traffic split\nzeta = 90\nalpha = 7.5\nmid = 2.5\n").unwrap();
+
+        let split = Categorical::from_toml(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // the labels keep the order of the file, not the alphabetical one
+        assert_eq!(split.labels(), ["zeta", "alpha", "mid"]);
+        assert_eq!(split.weights(), [90.0, 7.5, 2.5]);
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        assert_eq!(split.sample_n(&mut rng, 100).iter().filter(|&&l| l == "zeta").count(),
+                   90);
+    }
+
+    #[test]
+    fn test_from_yaml_file() {
+        let path = temp_path("loot.yaml");
+        fs::write(&path, "gold: 70\nsword: 20\nshield: 10\n").unwrap();
+
+        let loot = Categorical::from_yaml(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        assert_eq!(loot.labels(), ["gold", "sword", "shield"]);
+        assert_eq!(loot.probability("sword"), Some(0.2));
+    }
+
+    #[test]
+    fn test_config_validation() {
+        match Categorical::from_toml_str("a = 1\nb = -2") {
+            Err(ConfigError::Invalid(CategoricalError::InvalidWeight(label, weight))) => {
+                assert_eq!((label.as_str(), weight), ("b", -2.0));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        // both formats reject duplicate keys while parsing
+        assert!(Categorical::from_toml_str("a = 1\na = 2").is_err());
+        assert!(Categorical::from_yaml_str("a: 1\na: 2").is_err());
+
+        assert!(matches!(Categorical::from_toml_str("a = \"heavy\""), Err(ConfigError::Toml(_))));
+        assert!(matches!(Categorical::from_yaml_str("- a\n- b"), Err(ConfigError::Yaml(_))));
+        assert!(matches!(Categorical::from_yaml(temp_path("missing.yaml")),
+                         Err(ConfigError::Io(_))));
+    }
+
+    #[test]
+    fn test_config_error_messages() {
+        let error = Categorical::from_yaml_str("a: -1").unwrap_err();
+
+        assert_eq!(error.to_string(),
+                   "invalid distribution: weight -1 of label \"a\" is not a finite, non-negative \
+                    number");
+        assert!(std::error::Error::source(&error).is_some());
+    }
+}
@@ -0,0 +1,72 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::core::counter::{CounterBased, CounterRng, Threefry2x64};
+    use random_choice::core::entropy::{EntropySource, Philox4x32};
+
+    #[test]
+    fn test_threefry_known_answers() {
+        // Random123 known answer tests of threefry2x64_20
+        assert_eq!(Threefry2x64::keyed_block([0, 0], [0, 0]),
+                   [0xc2b6e3a8c2c69865, 0x6f81ed42f350084d]);
+        assert_eq!(Threefry2x64::keyed_block([u64::MAX, u64::MAX], [u64::MAX, u64::MAX]),
+                   [0xe02cb7c4d95d277a, 0xd06633d0893b8b68]);
+        assert_eq!(Threefry2x64::keyed_block([0xa4093822299f31d0, 0x082efa98ec4e6c89],
+                                             [0x243f6a8885a308d3, 0x13198a2e03707344]),
+                   [0x263c7d30bb0f0af1, 0x56be8361d3311526]);
+    }
+
+    #[test]
+    fn test_draws_are_pure_functions() {
+        let mut rng = CounterRng::<Threefry2x64>::new(5000, 7);
+
+        for i in 0..10 {
+            assert_eq!(rng.next_bits(), CounterRng::<Threefry2x64>::draw(5000, 7, i));
+        }
+
+        assert_eq!(rng.position(), 10);
+        assert_eq!(CounterRng::<Philox4x32>::draw(5000, 7, 3),
+                   Philox4x32::block(5000, [1, 7])[1]);
+        assert_eq!(CounterRng::<Threefry2x64>::draw(5000, 7, 4),
+                   <Threefry2x64 as CounterBased>::block(5000, [2, 7])[0]);
+    }
+
+    #[test]
+    fn test_workers_compute_disjoint_draws() {
+        let mut sequential: CounterRng = CounterRng::new(44, 0);
+        let all: Vec<u64> = (0..30).map(|_| sequential.next_bits()).collect();
+
+        // three workers with 10 draws each, starting at odd and even positions
+        let parts: Vec<u64> = (0..3)
+            .flat_map(|w| {
+                let mut worker: CounterRng = CounterRng::at(44, 0, w * 10);
+                (0..10).map(move |_| worker.next_bits())
+            })
+            .collect();
+
+        assert_eq!(parts, all);
+
+        let mut other_stream: CounterRng = CounterRng::new(44, 1);
+        assert!((0..30).all(|i| other_stream.next_bits() != all[i]));
+    }
+
+    #[test]
+    fn test_seek_and_random_choice() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 0.0];
+
+        let mut rng = CounterRng::<Threefry2x64>::new(5000, 3);
+        let first = RandomChoice::new(&mut rng).random_choice_indices_f64(&weights, 20);
+        let position = rng.position();
+        let second = RandomChoice::new(&mut rng).random_choice_indices_f64(&weights, 20);
+
+        rng.seek(position);
+        assert_eq!(RandomChoice::new(&mut rng).random_choice_indices_f64(&weights, 20), second);
+
+        rng.seek(0);
+        assert_eq!(RandomChoice::new(&mut rng).random_choice_indices_f64(&weights, 20), first);
+        assert_eq!((rng.seed(), rng.stream()), (5000, 3));
+    }
+}
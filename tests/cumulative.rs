@@ -0,0 +1,91 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::core::cumulative::CumulativeWeights;
+    use random_choice::prepared::PreparedChoice;
+    use rand::SeedableRng;
+
+    static TABLE: CumulativeWeights<4> = CumulativeWeights::new_const([0.5, 0.25, 0.15, 0.1]);
+
+    const HALVES: CumulativeWeights<2> = CumulativeWeights::new_const([1.0, 1.0]);
+    const HALVES_TOTAL: f64 = HALVES.total_weight();
+
+    #[test]
+    fn test_cumulative_const_evaluation() {
+        assert_eq!(TABLE.prefix_sums(), &[0.5, 0.75, 0.9, 1.0]);
+        assert_eq!(TABLE.len(), 4);
+        assert_eq!(TABLE.total_weight(), 1.0);
+        assert_eq!(TABLE.weight(1), 0.25);
+        assert_eq!(HALVES_TOTAL, 2.0);
+    }
+
+    #[test]
+    fn test_cumulative_frequencies() {
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 40000;
+        let mut counter = [0usize; 4];
+
+        for _ in 0..number_choices {
+            counter[TABLE.sample(&mut rng).unwrap()] += 1;
+        }
+
+        for (i, &count) in counter.iter().enumerate() {
+            let expected = number_choices as f64 * TABLE.weight(i);
+            assert!((count as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_cumulative_zero_weights() {
+        let table = CumulativeWeights::new_const([0.0, 2.0, 0.0, 1.0, 0.0]);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        let choices = table.sample_n(&mut rng, 300);
+
+        assert_eq!(choices.len(), 300);
+        assert_eq!(choices.iter().filter(|&&i| i == 1).count(), 200);
+        assert_eq!(choices.iter().filter(|&&i| i == 3).count(), 100);
+    }
+
+    #[test]
+    fn test_cumulative_empty() {
+        let table = CumulativeWeights::new_const([]);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(table.is_empty());
+        assert_eq!(table.total_weight(), 0.0);
+        assert_eq!(table.sample(&mut rng), None);
+        assert!(table.sample_n(&mut rng, 5).is_empty());
+    }
+
+    #[test]
+    fn test_cumulative_same_as_prepared() {
+        let weights = [5.6, 7.8, 9.7, 1.1, 2.0, 0.0, 3.3];
+        let table = CumulativeWeights::new_const(weights);
+        let prepared = PreparedChoice::new(&weights);
+
+        assert_eq!(table.to_prepared().prefix_sums(), prepared.prefix_sums());
+
+        for n in 0..50 {
+            let mut table_rng = super::rand::StdRng::from_seed(&[5000, 44, 55, n]);
+            let mut prepared_rng = super::rand::StdRng::from_seed(&[5000, 44, 55, n]);
+
+            assert_eq!(table.sample_n(&mut table_rng, n),
+                       prepared.sample_n(&mut prepared_rng, n));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cumulative_negative_weight() {
+        CumulativeWeights::new_const([1.0, -1.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_cumulative_infinite_sum() {
+        CumulativeWeights::new_const([f64::MAX, f64::MAX]);
+    }
+}
@@ -0,0 +1,91 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::random_choice;
+    use random_choice::RandomChoice;
+    use rand::SeedableRng;
+
+    fn counts(indices: &[usize], len: usize) -> Vec<usize> {
+        let mut counts = vec![0; len];
+
+        for &i in indices {
+            counts[i] += 1;
+        }
+
+        counts
+    }
+
+    #[test]
+    fn test_capped_limits_dominant_weight() {
+        let weights: Vec<f64> = vec![1000.0, 10.0, 5.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        for _ in 0..100 {
+            let indices = random_choice.random_choice_indices_capped_f64(&weights, 20, 0.25);
+            let counts = counts(&indices, weights.len());
+
+            assert_eq!(indices.len(), 20);
+            assert!(indices.windows(2).all(|pair| pair[0] <= pair[1]));
+            assert!(counts.iter().all(|&count| count <= 5));
+            // the overflow of the first weight caps the second one, too
+            assert_eq!(&counts[..2], &[5, 5]);
+        }
+    }
+
+    #[test]
+    fn test_capped_keeps_proportions_of_the_rest() {
+        let weights: Vec<f64> = vec![100.0, 1.0, 2.0, 3.0, 4.0];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+        let mut total = vec![0; weights.len()];
+
+        for _ in 0..1000 {
+            let indices = random_choice.random_choice_indices_capped_f64(&weights, 10, 0.5);
+
+            for (i, count) in counts(&indices, weights.len()).into_iter().enumerate() {
+                total[i] += count;
+            }
+        }
+
+        // 5 choices go to the first index, the other 5 in proportion 1:2:3:4
+        assert_eq!(total[0], 5000);
+
+        for i in 1..5 {
+            let expected = 5000.0 * weights[i] / 10.0;
+            assert!((total[i] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_capped_without_cap_pressure_is_plain_sampling() {
+        let weights: Vec<f64> = vec![1.0, 1.0, 1.0, 1.0];
+        let indices = random_choice().random_choice_indices_capped_f64(&weights, 8, 1.0);
+
+        assert_eq!(counts(&indices, weights.len()), vec![2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_capped_infeasible_returns_less() {
+        let samples = vec!["a", "b", "c"];
+        let weights: Vec<f64> = vec![5.0, 1.0, 0.0];
+        let chosen = random_choice().random_choice_capped_f64(&samples, &weights, 10, 0.2);
+
+        assert_eq!(chosen, vec![&"a", &"a", &"b", &"b"]);
+    }
+
+    #[test]
+    fn test_capped_zero_elements() {
+        assert!(random_choice().random_choice_indices_capped_f64(&[], 10, 0.5).is_empty());
+        assert!(random_choice().random_choice_indices_capped_f64(&[1.0], 0, 0.5).is_empty());
+        assert!(random_choice().random_choice_indices_capped_f64(&[0.0, 0.0], 10, 0.5).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_capped_rejects_zero_ratio() {
+        random_choice().random_choice_indices_capped_f64(&[1.0], 10, 0.0);
+    }
+}
@@ -0,0 +1,101 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+#[cfg(feature = "linalg")]
+mod tests {
+    use random_choice::strategies::dpp::KDpp;
+    use random_choice::random_choice;
+    use rand::SeedableRng;
+
+    fn identity(n: usize) -> Vec<f64> {
+        (0..n * n).map(|ij| if ij / n == ij % n { 1.0 } else { 0.0 }).collect()
+    }
+
+    #[test]
+    fn test_k_dpp_single_index_by_weight() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 0.0];
+        let dpp = KDpp::new(&weights, &identity(weights.len()));
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 20000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for _ in 0..number_choices {
+            counter[dpp.sample(&mut rng, 1)[0]] += 1;
+        }
+
+        assert_eq!(counter[4], 0);
+
+        for i in 0..4 {
+            let expected = number_choices as f64 * weights[i] / 10.0;
+            assert!((counter[i] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_k_dpp_subset_probabilities() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 1.5];
+        let similarity: Vec<f64> = vec![1.0, 0.6, 0.2,
+                                        0.6, 1.0, 0.3,
+                                        0.2, 0.3, 1.0];
+        let dpp = KDpp::new(&weights, &similarity);
+
+        // det(L_S) of the subsets {0, 1}, {0, 2}, {1, 2}
+        let determinant = |i: usize, j: usize| {
+            weights[i] * weights[j] * (1.0 - similarity[i * 3 + j] * similarity[i * 3 + j])
+        };
+        let subsets = [(0, 1), (0, 2), (1, 2)];
+        let total: f64 = subsets.iter().map(|&(i, j)| determinant(i, j)).sum();
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 30000;
+        let mut counter = [0usize; 3];
+
+        for _ in 0..number_choices {
+            let subset = dpp.sample(&mut rng, 2);
+            let s = subsets.iter().position(|&(i, j)| subset == vec![i, j]).unwrap();
+            counter[s] += 1;
+        }
+
+        for s in 0..3 {
+            let (i, j) = subsets[s];
+            let expected = number_choices as f64 * determinant(i, j) / total;
+            assert!((counter[s] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_k_dpp_avoids_duplicates() {
+        let weights: Vec<f64> = vec![5.0, 5.0, 1.0, 1.0];
+        let mut similarity = identity(4);
+        similarity[1] = 1.0;
+        similarity[4] = 1.0;
+
+        let samples = vec!["a", "a'", "b", "c"];
+        let mut random_choice = random_choice();
+
+        for _ in 0..100 {
+            let subset = random_choice.random_dpp_subset_f64(&samples, &weights, &similarity, 3);
+
+            assert_eq!(subset.len(), 3);
+            assert!(!(subset.contains(&&"a") && subset.contains(&&"a'")));
+        }
+    }
+
+    #[test]
+    fn test_k_dpp_infeasible() {
+        let mut random_choice = random_choice();
+        // rank 1: every pair is linearly dependent
+        let similarity: Vec<f64> = vec![1.0; 9];
+
+        assert!(random_choice.random_dpp_subset_indices_f64(&[1.0, 2.0, 3.0], &similarity, 2)
+            .is_empty());
+        assert_eq!(random_choice.random_dpp_subset_indices_f64(&[1.0, 2.0, 3.0], &similarity, 1)
+                       .len(),
+                   1);
+        assert!(random_choice.random_dpp_subset_indices_f64(&[1.0], &[1.0], 0).is_empty());
+        assert!(random_choice.random_dpp_subset_indices_f64(&[], &[], 1).is_empty());
+        assert!(KDpp::new(&[], &[]).is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::core::entropy::{EntropyRng, EntropySource, Philox4x32};
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_philox_known_answers() {
+        // Random123 known answer tests of philox4x32_10
+        assert_eq!(Philox4x32::block(0, [0, 0]),
+                   [0xe169c58d_6627e8d5, 0x9b00dbd8_bc57ac4c]);
+        assert_eq!(Philox4x32::block(u64::MAX, [u64::MAX, u64::MAX]),
+                   [0x41c83b0e_408f276d, 0x6d5451fd_a20bc7c6]);
+        assert_eq!(Philox4x32::block(0x299f31d0_a4093822,
+                                     [0x85a308d3_243f6a88, 0x03707344_13198a2e]),
+                   [0x94fdcceb_d16cfe09, 0x24126ea1_5001e420]);
+    }
+
+    #[test]
+    fn test_philox_sequence() {
+        let mut philox = Philox4x32::new(5000);
+        let values: Vec<u64> = (0..4).map(|_| philox.next_bits()).collect();
+
+        assert_eq!(&values[..2], &Philox4x32::block(5000, [0, 0]));
+        assert_eq!(&values[2..], &Philox4x32::block(5000, [1, 0]));
+        assert_eq!(philox.counter(), [2, 0]);
+
+        // the counter carries into the second word
+        let mut philox = Philox4x32::with_counter(5000, [u64::MAX, 0]);
+        philox.next_bits();
+        assert_eq!(philox.counter(), [0, 1]);
+    }
+
+    #[test]
+    fn test_rand_rng_is_entropy_source() {
+        let mut rng = rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut expected = rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert_eq!(rng.next_bits(), expected.next_u64());
+
+        let mut adapted = EntropyRng::new(&mut rng);
+        assert_eq!(adapted.next_u64(), expected.next_u64());
+    }
+
+    #[test]
+    fn test_random_choice_from_entropy() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 0.0];
+        let mut random_choice = RandomChoice::from_entropy(Philox4x32::new(44));
+        let number_choices = 100000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for _ in 0..number_choices {
+            counter[random_choice.random_choice_indices_f64(&weights, 1)[0]] += 1;
+        }
+
+        assert_eq!(counter[4], 0);
+
+        for (i, &weight) in weights.iter().enumerate() {
+            let expected = number_choices as f64 * weight / 10.0;
+            assert!((counter[i] as f64 - expected).abs() <= expected * 0.05);
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_rdrand() {
+        use random_choice::core::entropy::RdRand;
+
+        if let Some(mut rdrand) = RdRand::new() {
+            let values: Vec<u64> = (0..4).map(|_| rdrand.next_bits()).collect();
+            assert!(values.windows(2).any(|w| w[0] != w[1]));
+
+            let choices = RandomChoice::from_entropy(rdrand).random_choice_indices_f64(&[1.0], 3);
+            assert_eq!(choices, vec![0, 0, 0]);
+        }
+    }
+}
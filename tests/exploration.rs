@@ -0,0 +1,76 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::strategies::exploration::explored_probabilities;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_explored_probabilities_have_a_floor() {
+        let weights: Vec<f64> = vec![5.6, 7.8, 0.0, 1.1, 2.0];
+        let epsilon = 0.1;
+        let probabilities = explored_probabilities(&weights, epsilon);
+
+        assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(probabilities.iter().all(|&p| p >= epsilon / 5.0));
+        assert!((probabilities[2] - 0.02).abs() < 1e-12);
+        assert!((probabilities[1] - (0.9 * 7.8 / 16.5 + 0.02)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_explored_probabilities_edge_cases() {
+        let weights: Vec<f64> = vec![3.0, 1.0];
+
+        assert_eq!(explored_probabilities(&weights, 0.0), vec![0.75, 0.25]);
+        assert_eq!(explored_probabilities(&weights, 1.0), vec![0.5, 0.5]);
+        assert_eq!(explored_probabilities(&[0.0, 0.0], 0.3), vec![0.5, 0.5]);
+        assert!(explored_probabilities(&[], 0.3).is_empty());
+    }
+
+    #[test]
+    fn test_exploration_matches_mixed_weights() {
+        let weights: Vec<f64> = vec![5.6, 7.8, 0.0, 1.1, 0.0];
+        let probabilities = explored_probabilities(&weights, 0.25);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let explored = RandomChoice::new(rng).with_exploration(0.25)
+            .random_choice_indices_f64(&weights, 1000);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let expected = RandomChoice::new(rng).random_choice_indices_f64(&probabilities, 1000);
+
+        assert_eq!(explored, expected);
+
+        for (i, &probability) in probabilities.iter().enumerate() {
+            let count = explored.iter().filter(|&&e| e == i).count();
+            assert!((count as f64 - probability * 1000.0).abs() <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_exploration_samples() {
+        let samples = vec!["hi", "this", "is", "a", "test!"];
+        let weights: Vec<f64> = vec![0.0, 0.0, 0.0, 0.0, 1.0];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+        let mut exploring = random_choice.with_exploration(0.5);
+
+        assert_eq!(exploring.epsilon(), 0.5);
+
+        let chosen = exploring.random_choice_f64(&samples, &weights, 10);
+
+        assert_eq!(chosen, vec![&"hi", &"this", &"is", &"a",
+                                &"test!", &"test!", &"test!", &"test!", &"test!", &"test!"]);
+        assert!(exploring.random_choice_f64(&samples, &[], 10).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_exploration_rejects_invalid_epsilon() {
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        RandomChoice::new(rng).with_exploration(1.5);
+    }
+}
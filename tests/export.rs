@@ -0,0 +1,60 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::strategies::export::{export_distribution, export_distribution_with_draw,
+                                            ExportFormat};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_export_csv() {
+        let weights: Vec<f64> = vec![2.0, 0.0, 6.0];
+
+        assert_eq!(export_distribution(&weights, ExportFormat::Csv),
+                   "index,weight,probability,cumulative\n\
+                    0,2,0.25,0.25\n\
+                    1,0,0,0.25\n\
+                    2,6,0.75,1\n");
+    }
+
+    #[test]
+    fn test_export_json_with_draw() {
+        let weights: Vec<f64> = vec![2.0, 0.0, 6.0];
+
+        assert_eq!(export_distribution_with_draw(&weights, &[0, 2, 2, 2], ExportFormat::Json),
+                   "[{\"index\":0,\"weight\":2,\"probability\":0.25,\"cumulative\":0.25,\
+                    \"frequency\":0.25},\
+                    {\"index\":1,\"weight\":0,\"probability\":0,\"cumulative\":0.25,\
+                    \"frequency\":0},\
+                    {\"index\":2,\"weight\":6,\"probability\":0.75,\"cumulative\":1,\
+                    \"frequency\":0.75}]");
+    }
+
+    #[test]
+    fn test_export_frequencies_follow_probabilities() {
+        let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let draw = RandomChoice::new(rng).random_choice_indices_f64(&weights, 10000);
+        let csv = export_distribution_with_draw(&weights, &draw, ExportFormat::Csv);
+
+        for line in csv.lines().skip(1) {
+            let values: Vec<f64> = line.split(',').map(|v| v.parse().unwrap()).collect();
+            assert_eq!(values.len(), 5);
+            assert!((values[2] - values[4]).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_export_degenerate_weights() {
+        assert_eq!(export_distribution(&[], ExportFormat::Json), "[]");
+        assert_eq!(export_distribution(&[0.0, 0.0], ExportFormat::Csv),
+                   "index,weight,probability,cumulative\n0,0,0,0\n1,0,0,0\n");
+        assert_eq!(export_distribution(&[f64::INFINITY], ExportFormat::Json),
+                   "[{\"index\":0,\"weight\":null,\"probability\":null,\"cumulative\":null}]");
+        assert_eq!(export_distribution_with_draw(&[1.0], &[], ExportFormat::Csv),
+                   "index,weight,probability,cumulative,frequency\n0,1,1,1,0\n");
+    }
+}
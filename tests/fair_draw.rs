@@ -0,0 +1,136 @@
+#![cfg(feature = "fair-draw")]
+
+extern crate random_choice;
+extern crate sha2;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::strategies::fair_draw::{Commitment, FairDraw, FairDrawError};
+
+    fn samples_and_weights() -> (Vec<usize>, Vec<f64>) {
+        let capacity: usize = 100;
+        let mut samples: Vec<usize> = Vec::with_capacity(capacity);
+        let mut weights: Vec<f64> = Vec::with_capacity(capacity);
+
+        for i in 0..capacity {
+            samples.push(i);
+            weights.push((i + 1) as f64);
+        }
+
+        (samples, weights)
+    }
+
+    #[test]
+    fn test_commitment_hex_round_trip() {
+        let commitment = Commitment::new(b"secret seed");
+        let hex = commitment.to_string();
+
+        assert_eq!(hex.len(), 64);
+        assert_eq!(Commitment::from_hex(&hex), Ok(commitment));
+        assert_eq!(Commitment::from_hex(&hex.to_uppercase()), Ok(commitment));
+        assert_eq!(Commitment::from_hex("abc"), Err(FairDrawError::InvalidHex));
+        assert_eq!(Commitment::from_hex(&hex.replace("a", "x")),
+                   Err(FairDrawError::InvalidHex));
+    }
+
+    #[test]
+    fn test_reveal_wrong_seed() {
+        let mut draw = FairDraw::new(Commitment::new(b"secret seed"));
+        draw.contribute(b"participant");
+
+        assert_eq!(draw.reveal(b"other seed"), Err(FairDrawError::CommitmentMismatch));
+        assert!(draw.reveal(b"secret seed").is_ok());
+    }
+
+    #[test]
+    fn test_draw_is_reproducible_and_order_independent() {
+        let (samples, weights) = samples_and_weights();
+        let seed = b"secret seed";
+
+        let mut first = FairDraw::new(Commitment::new(seed));
+        first.contribute(b"alice");
+        first.contribute(b"bob");
+
+        let mut second = FairDraw::new(Commitment::new(seed));
+        second.contribute(b"bob");
+        second.contribute(b"alice");
+
+        let first = first.reveal(seed).unwrap();
+        let second = second.reveal(seed).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first.random_choice_f64(&samples, &weights, 50),
+                   second.random_choice_f64(&samples, &weights, 50));
+    }
+
+    #[test]
+    fn test_contributions_change_the_draw() {
+        let seed = b"secret seed";
+
+        let mut first = FairDraw::new(Commitment::new(seed));
+        first.contribute(b"alice");
+
+        let mut second = FairDraw::new(Commitment::new(seed));
+        second.contribute(b"mallory");
+
+        assert!(first.reveal(seed).unwrap().combined_seed() !=
+                second.reveal(seed).unwrap().combined_seed());
+    }
+
+    #[test]
+    fn test_verify_published_result() {
+        let (samples, weights) = samples_and_weights();
+        let weights_f32: Vec<f32> = weights.iter().map(|&w| w as f32).collect();
+        let seed = b"secret seed";
+
+        let mut draw = FairDraw::new(Commitment::new(seed));
+        draw.contribute(b"alice");
+        let revealed = draw.reveal(seed).unwrap();
+
+        let choices: Vec<usize> = revealed.random_choice_f64(&samples, &weights, 10)
+            .into_iter()
+            .cloned()
+            .collect();
+
+        assert!(revealed.verify_f64(&samples, &weights, 10, &choices));
+
+        let mut tampered = choices.clone();
+        tampered[3] = (tampered[3] + 1) % samples.len();
+        assert!(!revealed.verify_f64(&samples, &weights, 10, &tampered));
+        assert!(!revealed.verify_f64(&samples, &weights, 10, &choices[..9]));
+
+        let choices_f32 = revealed.random_choice_f32(&samples, &weights_f32, 10);
+        assert!(revealed.verify_f32(&samples, &weights_f32, 10, choices_f32));
+    }
+
+    #[test]
+    fn test_combined_seed_is_secret_until_reveal() {
+        use sha2::{Digest, Sha256};
+
+        let seed = b"operator secret";
+        let commitment = Commitment::new(seed);
+        let mut draw = FairDraw::new(commitment);
+        draw.contribute(b"entropy of alice");
+        draw.contribute(b"entropy of bob");
+
+        // everything, which is public before the reveal
+        let mut contributions: Vec<Vec<u8>> = [&b"entropy of alice"[..], &b"entropy of bob"[..]]
+            .iter()
+            .map(|entropy| Sha256::digest(entropy).to_vec())
+            .collect();
+        contributions.sort();
+
+        let mut public = Sha256::new();
+        public.update(b"random_choice fair draw v1");
+        public.update(commitment.as_bytes());
+        public.update(2u64.to_le_bytes());
+
+        for contribution in &contributions {
+            public.update(contribution);
+        }
+
+        let combined_seed = *draw.reveal(seed).unwrap().combined_seed();
+
+        assert!(public.finalize()[..] != combined_seed[..]);
+    }
+}
@@ -0,0 +1,79 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::prepared::fenwick::FenwickSampler;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_fenwick_prefix_sums() {
+        let weights: Vec<f64> = (0..100).map(|i| (i % 7) as f64).collect();
+        let sampler = FenwickSampler::new(&weights);
+
+        for end in 0..=weights.len() {
+            let expected: f64 = weights[..end].iter().sum();
+            assert_eq!(sampler.prefix_sum(end), expected);
+        }
+
+        assert_eq!(sampler.total_weight(), weights.iter().sum::<f64>());
+    }
+
+    #[test]
+    fn test_fenwick_frequencies_after_updates() {
+        let mut sampler = FenwickSampler::new(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        sampler.set_weight(4, 0.0);
+        sampler.set_weight(0, 4.0);
+        sampler.set_weight(1, 0.0);
+
+        let weights = sampler.weights().to_vec();
+        assert_eq!(weights, vec![4.0, 0.0, 3.0, 4.0, 0.0]);
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 100000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for index in sampler.sample_n(&mut rng, number_choices) {
+            counter[index] += 1;
+        }
+
+        assert_eq!(counter[1], 0);
+        assert_eq!(counter[4], 0);
+
+        for &i in &[0, 2, 3] {
+            let expected = number_choices as f64 * weights[i] / 11.0;
+            assert!((counter[i] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_fenwick_rounding_errors_after_clearing() {
+        let weights: Vec<f64> = (0..1000).map(|i| 0.1 + i as f64 * 1e-3).collect();
+        let mut sampler = FenwickSampler::new(&weights);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        for i in 0..999 {
+            sampler.set_weight(i, 0.0);
+        }
+
+        // only the last weight is left, whatever the rounding errors of the tree are
+        assert!(sampler.sample_n(&mut rng, 100).iter().all(|&i| i == 999));
+
+        sampler.set_weight(999, 0.0);
+        assert!(!sampler.has_positive_weight());
+        assert_eq!(sampler.sample(&mut rng), None);
+
+        sampler.rebuild();
+        assert_eq!(sampler.total_weight(), 0.0);
+    }
+
+    #[test]
+    fn test_fenwick_empty() {
+        let sampler = FenwickSampler::new(&[]);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(sampler.is_empty());
+        assert_eq!(sampler.sample(&mut rng), None);
+        assert!(sampler.sample_n(&mut rng, 10).is_empty());
+    }
+}
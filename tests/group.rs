@@ -0,0 +1,82 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::random_choice;
+    use random_choice::RandomChoice;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_sample_per_group() {
+        let capacity: usize = 3000;
+        let mut group_ids: Vec<&str> = Vec::with_capacity(capacity);
+        let mut weights: Vec<f64> = Vec::with_capacity(capacity);
+
+        for i in 0..capacity {
+            group_ids.push(["alice", "bob", "carol"][i % 3]);
+            weights.push((i / 3) as f64);
+        }
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let groups = RandomChoice::new(rng).sample_per_group(&group_ids, &weights, 500);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0].0, "alice");
+        assert_eq!(groups[1].0, "bob");
+        assert_eq!(groups[2].0, "carol");
+
+        for &(group_id, ref indices) in &groups {
+            assert_eq!(indices.len(), 500);
+            assert!(indices.iter().all(|&i| group_ids[i] == group_id));
+            assert!(indices.windows(2).all(|pair| pair[0] <= pair[1]));
+
+            // the greater half of the weights of a group gets 3/4 of the spokes
+            let greater_half = indices.iter().filter(|&&i| i >= capacity / 2).count();
+            assert!((greater_half as i32 - 375).abs() <= 2);
+        }
+    }
+
+    #[test]
+    fn test_sample_per_group_matches_single_group() {
+        let capacity: usize = 500;
+        let weights: Vec<f64> = (0..capacity).map(|i| i as f64).collect();
+        let group_ids = vec![7u32; capacity];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let groups = RandomChoice::new(rng).sample_per_group(&group_ids, &weights, 100);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let indices = RandomChoice::new(rng).random_choice_indices_f64(&weights, 100);
+
+        assert_eq!(groups, vec![(7, indices)]);
+    }
+
+    #[test]
+    fn test_sample_per_group_zero_elements() {
+        let group_ids = vec![1, 2, 1];
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0];
+
+        assert_eq!(random_choice().sample_per_group(&group_ids, &weights, 0),
+                   vec![(1, vec![]), (2, vec![])]);
+        assert!(random_choice().sample_per_group::<u32>(&[], &[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_sample_per_group_zero_weights() {
+        let group_ids = vec!["a", "b", "a", "b"];
+        let weights: Vec<f64> = vec![0.0, 1.0, 0.0, 3.0];
+
+        let groups = random_choice().sample_per_group(&group_ids, &weights, 4);
+
+        assert_eq!(groups[0], ("a", vec![]));
+        assert_eq!(groups[1].1.len(), 4);
+        assert!(groups[1].1.iter().all(|&i| i == 1 || i == 3));
+    }
+
+    #[test]
+    #[should_panic(expected = "every weight needs a group id")]
+    fn test_sample_per_group_length_mismatch() {
+        random_choice().sample_per_group(&[1, 2], &[1.0, 2.0, 3.0], 1);
+    }
+}
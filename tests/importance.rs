@@ -0,0 +1,71 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_prioritized_sample_frequencies() {
+        let priorities: Vec<f64> = vec![1.0, 4.0, 9.0, 0.0, 16.0];
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(&mut rng);
+        let number_draws = 50000;
+        let mut counter = vec![0usize; priorities.len()];
+
+        for _ in 0..number_draws {
+            let batch = random_choice.prioritized_sample_f64(&priorities, 1, 0.5, 0.4);
+            counter[batch.indices[0]] += 1;
+        }
+
+        // square roots of the priorities: 1, 2, 3, 0, 4
+        assert_eq!(counter[3], 0);
+
+        for (i, &root) in [1.0, 2.0, 3.0, 0.0, 4.0].iter().enumerate() {
+            let expected = number_draws as f64 * root / 10.0;
+            assert!((counter[i] as f64 - expected).abs() <= expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_prioritized_sample_weights() {
+        let priorities: Vec<f64> = vec![1.0, 4.0, 9.0, 0.0, 16.0];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let batch = RandomChoice::new(rng).prioritized_sample_f64(&priorities, 10, 0.5, 0.4);
+
+        assert_eq!(batch.indices.len(), 10);
+        assert_eq!(batch.weights.len(), 10);
+        assert!(batch.indices.windows(2).all(|w| w[0] <= w[1]));
+
+        for (&i, &weight) in batch.indices.iter().zip(batch.weights.iter()) {
+            let probability = priorities[i].sqrt() / 10.0;
+            // the least likely transition with P = 0.1 has the greatest weight
+            let expected = (5.0 * probability).powf(-0.4) / 0.5f64.powf(-0.4);
+            assert!((weight - expected).abs() < 1e-12);
+        }
+
+        // SUS draws the least likely transition once
+        assert_eq!(batch.indices[0], 0);
+        assert_eq!(batch.weights[0], 1.0);
+    }
+
+    #[test]
+    fn test_prioritized_sample_edge_cases() {
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        // without correction, all weights are one
+        let batch = random_choice.prioritized_sample_f64(&[1.0, 5.0, 3.0], 6, 1.0, 0.0);
+        assert!(batch.weights.iter().all(|&w| w == 1.0));
+
+        // alpha = 0 is uniform, so no correction is needed
+        let batch = random_choice.prioritized_sample_f64(&[1.0, 5.0, 3.0], 6, 0.0, 1.0);
+        assert_eq!(batch.indices, vec![0, 0, 1, 1, 2, 2]);
+        assert!(batch.weights.iter().all(|&w| (w - 1.0).abs() < 1e-12));
+
+        assert!(random_choice.prioritized_sample_f64(&[0.0, 0.0], 3, 0.6, 0.4).indices.is_empty());
+        assert!(random_choice.prioritized_sample_f64(&[], 3, 0.6, 0.4).indices.is_empty());
+        assert!(random_choice.prioritized_sample_f64(&[1.0], 0, 0.6, 0.4).weights.is_empty());
+    }
+}
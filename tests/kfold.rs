@@ -0,0 +1,80 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::strategies::kfold::weighted_kfold;
+    use rand::SeedableRng;
+
+    fn totals(folds: &[usize], weights: &[f64], k: usize) -> Vec<f64> {
+        let mut totals = vec![0.0; k];
+
+        for (&fold, &weight) in folds.iter().zip(weights.iter()) {
+            totals[fold] += weight;
+        }
+
+        totals
+    }
+
+    #[test]
+    fn test_kfold_balances_weights() {
+        let weights: Vec<f64> = (0..1000).map(|i| ((i * 7919) % 101) as f64 + 0.5).collect();
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        for &k in &[1, 2, 5, 10, 33] {
+            let folds = weighted_kfold(&weights, k, &mut rng);
+            let totals = totals(&folds, &weights, k);
+
+            let max = totals.iter().cloned().fold(0.0, f64::max);
+            let min = totals.iter().cloned().fold(f64::INFINITY, f64::min);
+
+            assert_eq!(folds.len(), 1000);
+            assert!(folds.iter().all(|&fold| fold < k));
+            assert!(max - min <= 100.5, "k: {}, totals: {:?}", k, totals);
+        }
+    }
+
+    #[test]
+    fn test_kfold_is_randomized() {
+        let weights: Vec<f64> = vec![1.0; 20];
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let first = weighted_kfold(&weights, 4, &mut rng);
+        let second = weighted_kfold(&weights, 4, &mut rng);
+
+        assert_ne!(first, second);
+
+        for folds in &[first, second] {
+            assert_eq!(totals(folds, &weights, 4), vec![5.0; 4]);
+        }
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut same_rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        assert_eq!(weighted_kfold(&weights, 4, &mut rng),
+                   weighted_kfold(&weights, 4, &mut same_rng));
+    }
+
+    #[test]
+    fn test_kfold_spreads_zero_weights() {
+        let weights: Vec<f64> = vec![0.0; 9];
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let folds = weighted_kfold(&weights, 3, &mut rng);
+
+        for fold in 0..3 {
+            assert_eq!(folds.iter().filter(|&&f| f == fold).count(), 3);
+        }
+    }
+
+    #[test]
+    fn test_kfold_empty() {
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        assert!(weighted_kfold(&[], 3, &mut rng).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_kfold_rejects_zero_folds() {
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        weighted_kfold(&[1.0], 0, &mut rng);
+    }
+}
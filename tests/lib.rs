@@ -3,12 +3,14 @@ extern crate rand;
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "legacy")]
     use std::collections::BTreeMap;
     use random_choice::random_choice;
     use random_choice::RandomChoice;
     use rand::SeedableRng;
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_f64() {
         let capacity: usize = 500;
         let mut samples: Vec<usize> = Vec::with_capacity(capacity);
@@ -33,7 +35,7 @@ mod tests {
 
         let mut last_value: usize = 0;
 
-        for (_, value) in &weight_counter {
+        for value in weight_counter.values() {
             assert!((last_value as i32 - (*value) as i32).abs() <= 2);
             last_value = *value;
             // println!("({}, {})", key, value);
@@ -41,6 +43,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_f32() {
         let capacity: usize = 500;
         let mut samples: Vec<usize> = Vec::with_capacity(capacity);
@@ -65,7 +68,7 @@ mod tests {
 
         let mut last_value: usize = 0;
 
-        for (_, value) in &weight_counter {
+        for value in weight_counter.values() {
             assert!((last_value as i32 - (*value) as i32).abs() <= 2);
             last_value = *value;
             // println!("({}, {})", key, value);
@@ -73,6 +76,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_small_n_f32() {
         let capacity: usize = 500;
         let mut samples: Vec<usize> = Vec::with_capacity(capacity);
@@ -98,7 +102,7 @@ mod tests {
 
         let mut last_value: usize = 0;
 
-        for (_, value) in &weight_counter {
+        for value in weight_counter.values() {
             assert!((last_value as i32 - (*value) as i32).abs() <= 2);
             last_value = *value;
             // println!("({}, {})", key, value);
@@ -106,6 +110,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_small_n_f64() {
         let capacity: usize = 500;
         let mut samples: Vec<usize> = Vec::with_capacity(capacity);
@@ -131,7 +136,7 @@ mod tests {
 
         let mut last_value: usize = 0;
 
-        for (_, value) in &weight_counter {
+        for value in weight_counter.values() {
             assert!((last_value as i32 - (*value) as i32).abs() <= 2);
             last_value = *value;
             // println!("({}, {})", key, value);
@@ -139,6 +144,7 @@ mod tests {
     }
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_zero_elements_f64() {
         let capacity: usize = 1000;
         let mut samples: Vec<usize> = Vec::with_capacity(capacity);
@@ -149,12 +155,13 @@ mod tests {
             weights.push((i + 1usize) as f64);
         }
 
-        let choices = random_choice().random_choice_f64(&samples, &weights, 0 as usize);
+        let choices = random_choice().random_choice_f64(&samples, &weights, 0_usize);
 
-        assert!(choices.len() == 0);
+        assert!(choices.is_empty());
     }
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_zero_elements_f32() {
         let capacity: usize = 1000;
         let mut samples: Vec<usize> = Vec::with_capacity(capacity);
@@ -165,12 +172,13 @@ mod tests {
             weights.push((i + 1usize) as f32);
         }
 
-        let choices = random_choice().random_choice_f32(&samples, &weights, 0 as usize);
+        let choices = random_choice().random_choice_f32(&samples, &weights, 0_usize);
 
-        assert!(choices.len() == 0);
+        assert!(choices.is_empty());
     }
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_samples_zero_f64() {
         let capacity: usize = 1000;
         let samples: Vec<usize> = Vec::new();
@@ -182,10 +190,11 @@ mod tests {
 
         let choices = random_choice().random_choice_f64(&samples, &weights, capacity);
 
-        assert!(choices.len() == 0);
+        assert!(choices.is_empty());
     }
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_samples_zero_f32() {
         let capacity: usize = 1000;
         let samples: Vec<usize> = Vec::new();
@@ -197,10 +206,11 @@ mod tests {
 
         let choices = random_choice().random_choice_f32(&samples, &weights, capacity);
 
-        assert!(choices.len() == 0);
+        assert!(choices.is_empty());
     }
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_weights_zero_f64() {
         let capacity: usize = 1000;
         let mut samples: Vec<usize> = Vec::with_capacity(capacity);
@@ -212,10 +222,11 @@ mod tests {
 
         let choices = random_choice().random_choice_f64(&samples, &weights, capacity);
 
-        assert!(choices.len() == 0);
+        assert!(choices.is_empty());
     }
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_weights_zero_f32() {
         let capacity: usize = 1000;
         let mut samples: Vec<usize> = Vec::with_capacity(capacity);
@@ -227,10 +238,11 @@ mod tests {
 
         let choices = random_choice().random_choice_f32(&samples, &weights, capacity);
 
-        assert!(choices.len() == 0);
+        assert!(choices.is_empty());
     }
 
     #[test]
+    #[cfg(feature = "legacy")]
     fn test_random_choice_with_seed_f64() {
         let capacity: usize = 500;
         let mut samples: Vec<usize> = Vec::with_capacity(capacity);
@@ -258,9 +270,99 @@ mod tests {
 
         let mut last_value: usize = 0;
 
-        for (_, value) in &weight_counter {
+        for value in weight_counter.values() {
             assert!((last_value as i32 - (*value) as i32).abs() <= 2);
             last_value = *value;
         }
     }
+
+    #[test]
+    #[cfg(feature = "legacy")]
+    fn test_random_choice_indices_f64() {
+        let capacity: usize = 500;
+        let mut samples: Vec<usize> = Vec::with_capacity(capacity);
+        let mut weights: Vec<f64> = Vec::with_capacity(capacity);
+
+        for i in 0..capacity {
+            samples.push(i);
+            weights.push(i as f64);
+        }
+
+        let number_choices = 10000;
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let indices = RandomChoice::new(rng).random_choice_indices_f64(&weights, number_choices);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let choices = RandomChoice::new(rng).random_choice_f64(&samples, &weights, number_choices);
+
+        assert_eq!(indices.len(), number_choices);
+
+        for (index, choice) in indices.iter().zip(choices) {
+            assert_eq!(index, choice);
+        }
+
+        assert!(random_choice().random_choice_indices_f64(&weights, 0).is_empty());
+        assert!(random_choice().random_choice_indices_f64(&[], 10).is_empty());
+    }
+
+    #[test]
+    fn test_random_choice_indices_without_replacement_f64() {
+        let weights: Vec<f64> = vec![0.0, 1.0, 2.0, 3.0, 4.0, 0.0];
+        let mut random_choice = random_choice();
+
+        for _ in 0..100 {
+            let mut indices = random_choice.random_choice_indices_without_replacement_f64(&weights,
+                                                                                          3);
+            assert_eq!(indices.len(), 3);
+
+            indices.sort();
+            indices.dedup();
+            assert_eq!(indices.len(), 3);
+            assert!(indices.iter().all(|&i| i != 0 && i != 5));
+        }
+
+        // there are only four positive weights
+        let mut indices = random_choice.random_choice_indices_without_replacement_f64(&weights, 10);
+        indices.sort();
+        assert_eq!(indices, vec![1, 2, 3, 4]);
+
+        assert!(random_choice.random_choice_indices_without_replacement_f64(&weights, 0).is_empty());
+    }
+
+    #[test]
+    fn test_random_choice_indices_without_replacement_first_draw() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        let number_draws = 20000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for _ in 0..number_draws {
+            let first = random_choice.random_choice_indices_without_replacement_f64(&weights, 2)[0];
+            counter[first] += 1;
+        }
+
+        // the first drawn index is distributed by the weights
+        for i in 0..weights.len() {
+            let expected = number_draws as f64 * weights[i] / 10.0;
+            assert!((counter[i] as f64 - expected).abs() < expected * 0.1);
+        }
+    }
+
+    #[test]
+    fn test_layout_paths() {
+        use random_choice::core;
+
+        let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let layout = core::RandomChoice::new(rng).random_choice_indices_f64(&weights, 10);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        assert_eq!(layout, RandomChoice::new(rng).random_choice_indices_f64(&weights, 10));
+
+        assert_eq!(core::random_choice().random_choice_indices_f64(&weights, 3).len(), 3);
+    }
 }
@@ -0,0 +1,80 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::strategies::markov::MarkovSampler;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_markov_sampler_counts_transitions() {
+        let mut chain = MarkovSampler::new();
+        chain.observe_sequence(vec!["a", "b", "a", "c", "a", "b"]);
+        chain.observe_weighted("c", "c", 2.5);
+
+        assert_eq!(chain.len(), 3);
+        assert_eq!(chain.weight(&"a", &"b"), 2.0);
+        assert_eq!(chain.weight(&"a", &"c"), 1.0);
+        assert_eq!(chain.weight(&"c", &"c"), 2.5);
+        assert_eq!(chain.weight(&"b", &"c"), 0.0);
+        assert_eq!(chain.successors(&"a"), vec![(&"b", 2.0), (&"c", 1.0)]);
+        assert!(chain.successors(&"d").is_empty());
+    }
+
+    #[test]
+    fn test_markov_sampler_matches_weighted_choice() {
+        let mut chain = MarkovSampler::new();
+        let weights: Vec<f64> = vec![5.6, 7.8, 0.0, 1.1, 2.0];
+
+        for (to, &weight) in weights.iter().enumerate() {
+            chain.observe_weighted(0, to, weight);
+        }
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let chosen = chain.sample_next_n(&mut rng, &0, 100);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let expected = RandomChoice::new(rng).random_choice_indices_f64(&weights, 100);
+
+        assert_eq!(chosen, expected);
+    }
+
+    #[test]
+    fn test_markov_sampler_observation_after_sampling() {
+        let mut chain = MarkovSampler::new();
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        chain.observe('x', 'y');
+        assert_eq!(chain.sample_next(&mut rng, &'x'), Some('y'));
+
+        chain.observe_weighted('x', 'z', 1e9);
+        let chosen = chain.sample_next_n(&mut rng, &'x', 10);
+
+        assert_eq!(chosen, vec!['z'; 10]);
+    }
+
+    #[test]
+    fn test_markov_sampler_walk_stops_in_dead_end() {
+        let mut chain = MarkovSampler::new();
+        chain.observe_sequence(1..5);
+        chain.observe_weighted(5, 6, 0.0);
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert_eq!(chain.walk(&mut rng, 1, 10), vec![1, 2, 3, 4]);
+        assert_eq!(chain.walk(&mut rng, 2, 1), vec![2, 3]);
+        assert_eq!(chain.walk(&mut rng, 5, 10), vec![5]);
+        assert_eq!(chain.sample_next(&mut rng, &7), None);
+    }
+
+    #[test]
+    fn test_markov_sampler_empty() {
+        let mut chain: MarkovSampler<u8> = MarkovSampler::default();
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(chain.is_empty());
+        assert!(chain.sample_next_n(&mut rng, &0, 5).is_empty());
+        assert_eq!(chain.walk(&mut rng, 0, 5), vec![0]);
+    }
+}
@@ -0,0 +1,101 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::strategies::numpy_compat::{choice, choice_with, ChoiceError};
+    use random_choice::RandomChoice;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_choice_with_replacement_frequencies() {
+        let p = [0.1, 0.2, 0.3, 0.4, 0.0];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 100000;
+
+        let indices = choice_with(&mut RandomChoice::new(rng), 5, number_choices, true, Some(&p))
+            .unwrap();
+        let mut counter = [0usize; 5];
+
+        for i in indices {
+            counter[i] += 1;
+        }
+
+        assert_eq!(counter[4], 0);
+
+        for i in 0..4 {
+            let expected = number_choices as f64 * p[i];
+            assert!((counter[i] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_choice_without_replacement_is_distinct() {
+        let items = vec!["a", "b", "c", "d", "e", "f"];
+
+        for seed in 0..100 {
+            let mut chosen = choice(&items, 6, false, None, Some(seed)).unwrap();
+            chosen.sort();
+            assert_eq!(chosen, items);
+        }
+
+        let p = [0.5, 0.0, 0.25, 0.25];
+        let mut chosen = choice(4, 3, false, Some(&p), Some(7)).unwrap();
+        chosen.sort();
+        assert_eq!(chosen, vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn test_choice_is_reproducible_by_seed() {
+        let p = [0.25, 0.25, 0.5];
+
+        assert_eq!(choice(3, 20, true, Some(&p), Some(42)),
+                   choice(3, 20, true, Some(&p), Some(42)));
+        assert_eq!(choice(10, 5, false, None, Some(42)), choice(10, 5, false, None, Some(42)));
+    }
+
+    #[test]
+    fn test_choice_empty() {
+        let empty: &[u8] = &[];
+
+        assert_eq!(choice(0, 0, true, None, None), Ok(Vec::new()));
+        assert_eq!(choice(empty, 0, false, None, None), Ok(Vec::new()));
+        // like NumPy, empty probabilities don't sum up to 1
+        assert_eq!(choice(empty, 0, false, Some(&[]), None),
+                   Err(ChoiceError::NotNormalized(0.0)));
+    }
+
+    #[test]
+    fn test_choice_error_messages() {
+        let empty: &[u8] = &[];
+        let messages = [(choice(0, 1, true, None, None).unwrap_err(),
+                         "a must be greater than 0 unless no samples are taken"),
+                        (choice(empty, 1, true, None, None).unwrap_err(),
+                         "'a' cannot be empty unless no samples are taken"),
+                        (choice(3, 1, true, Some(&[0.5, 0.5]), None).unwrap_err(),
+                         "'a' and 'p' must have same size"),
+                        (choice(2, 1, true, Some(&[f64::NAN, 1.0]), None).unwrap_err(),
+                         "probabilities contain NaN"),
+                        (choice(2, 1, true, Some(&[-0.5, 1.5]), None).unwrap_err(),
+                         "probabilities are not non-negative"),
+                        (choice(2, 1, true, Some(&[0.5, 0.6]), None).unwrap_err(),
+                         "probabilities do not sum to 1"),
+                        (choice(2, 3, false, None, None).unwrap_err(),
+                         "Cannot take a larger sample than population when 'replace=False'"),
+                        (choice(3, 2, false, Some(&[1.0, 0.0, 0.0]), None).unwrap_err(),
+                         "Fewer non-zero entries in p than size")];
+
+        for &(ref error, message) in messages.iter() {
+            assert_eq!(error.to_string(), message);
+        }
+    }
+
+    #[test]
+    fn test_choice_sum_tolerance() {
+        assert!(choice(2, 1, true, Some(&[0.5, 0.5 + 1e-9]), None).is_ok());
+        match choice(2, 1, true, Some(&[0.5, 0.5 + 1e-7]), None) {
+            Err(ChoiceError::NotNormalized(sum)) => assert!((sum - 1.0 - 1e-7).abs() < 1e-12),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,102 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+
+    use random_choice::RandomChoice;
+    use random_choice::streaming::output::{OutputFormat, CHUNK_SIZE};
+    use rand::SeedableRng;
+
+    /// Counts the bytes and the calls of `write`.
+    struct CountingWriter {
+        bytes: usize,
+        writes: usize,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.bytes += buf.len();
+            self.writes += 1;
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_sample_to_writer_binary() {
+        let weights: Vec<f64> = (0..100).map(|i| i as f64).collect();
+        let number_choices = 3 * CHUNK_SIZE + 17;
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut bytes: Vec<u8> = Vec::new();
+        let written = RandomChoice::new(rng)
+            .sample_to_writer(&weights, number_choices, &mut bytes, OutputFormat::Binary)
+            .unwrap();
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let expected = RandomChoice::new(rng).random_choice_indices_f64(&weights, number_choices);
+
+        let decoded: Vec<usize> = bytes.chunks(8)
+            .map(|b| {
+                let mut le = [0u8; 8];
+                le.copy_from_slice(b);
+                u64::from_le_bytes(le) as usize
+            })
+            .collect();
+
+        assert_eq!(written, number_choices);
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn test_sample_to_writer_csv() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut csv: Vec<u8> = Vec::new();
+        RandomChoice::new(rng)
+            .sample_to_writer(&weights, 1000, &mut csv, OutputFormat::Csv)
+            .unwrap();
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let expected = RandomChoice::new(rng).random_choice_indices_f64(&weights, 1000);
+
+        let csv = String::from_utf8(csv).unwrap();
+        let mut lines = csv.lines();
+
+        assert_eq!(lines.next(), Some("index"));
+        assert_eq!(lines.map(|line| line.parse().unwrap()).collect::<Vec<usize>>(), expected);
+    }
+
+    #[test]
+    fn test_sample_to_writer_writes_chunks() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0];
+        let number_choices = 10 * CHUNK_SIZE;
+        let mut writer = CountingWriter { bytes: 0, writes: 0 };
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        RandomChoice::new(rng)
+            .sample_to_writer(&weights, number_choices, &mut writer, OutputFormat::Binary)
+            .unwrap();
+
+        assert_eq!(writer.bytes, 8 * number_choices);
+        assert_eq!(writer.writes, 10);
+    }
+
+    #[test]
+    fn test_sample_to_writer_empty() {
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut bytes: Vec<u8> = Vec::new();
+        let written = RandomChoice::new(rng)
+            .sample_to_writer(&[], 100, &mut bytes, OutputFormat::Binary)
+            .unwrap();
+
+        assert_eq!(written, 0);
+        assert!(bytes.is_empty());
+    }
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "polars")]
+
+extern crate polars_core;
+extern crate polars_lazy;
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use polars_core::prelude::*;
+    use polars_lazy::prelude::*;
+    use random_choice::integrations::polars::{sample_weighted, sample_weighted_expr};
+    use random_choice::RandomChoice;
+    use rand::SeedableRng;
+
+    fn data_frame() -> DataFrame {
+        df!("id" => &[0u32, 1, 2, 3, 4, 5],
+            "weight" => &[Some(1.0), None, Some(0.0), Some(3.0), Some(2.0), Some(4.0)])
+            .unwrap()
+    }
+
+    fn ids(df: &DataFrame) -> Vec<u32> {
+        df.column("id").unwrap().u32().unwrap().into_no_null_iter().collect()
+    }
+
+    #[test]
+    fn test_sample_weighted_with_replacement() {
+        let sample = sample_weighted(&data_frame(), "weight", 1000, true).unwrap();
+
+        assert_eq!(sample.height(), 1000);
+        assert_eq!(sample.width(), 2);
+        assert!(ids(&sample).iter().all(|&id| id != 1 && id != 2));
+    }
+
+    #[test]
+    fn test_sample_weighted_without_replacement() {
+        let rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        let sample = random_choice.sample_weighted(&data_frame(), "weight", 4, false).unwrap();
+        let mut ids = ids(&sample);
+        ids.sort();
+
+        assert_eq!(ids, vec![0, 3, 4, 5]);
+
+        // only four rows have a positive weight
+        assert!(random_choice.sample_weighted(&data_frame(), "weight", 5, false).is_err());
+    }
+
+    #[test]
+    fn test_sample_weighted_integer_weights() {
+        let df = df!("id" => &[0u32, 1, 2], "weight" => &[0i64, 5, 0]).unwrap();
+        let sample = sample_weighted(&df, "weight", 10, true).unwrap();
+
+        assert_eq!(ids(&sample), vec![1; 10]);
+    }
+
+    #[test]
+    fn test_sample_weighted_invalid_weights() {
+        let negative = df!("weight" => &[1.0, -1.0]).unwrap();
+        let infinite = df!("weight" => &[1.0, f64::INFINITY]).unwrap();
+        let zero = df!("weight" => &[0.0, 0.0]).unwrap();
+
+        assert!(sample_weighted(&negative, "weight", 1, true).is_err());
+        assert!(sample_weighted(&infinite, "weight", 1, true).is_err());
+        assert!(sample_weighted(&zero, "weight", 1, true).is_err());
+        assert!(sample_weighted(&zero, "missing", 1, true).is_err());
+    }
+
+    #[test]
+    fn test_sample_weighted_expr() {
+        let rng = ::rand::ChaChaRng::from_seed(&[42, 0]);
+        let expected = RandomChoice::new(rng)
+            .sample_weighted(&data_frame(), "weight", 4, false)
+            .unwrap();
+
+        let indices = sample_weighted_expr(col("weight"), 4, false, Some(42));
+        let sample = data_frame()
+            .lazy()
+            .select([col("id").gather(indices, false)])
+            .collect()
+            .unwrap();
+
+        assert_eq!(ids(&sample), ids(&expected));
+
+        let indices = sample_weighted_expr(col("weight"), 1000, true, None).alias("index");
+        let sample = data_frame().lazy().select([indices]).collect().unwrap();
+        let indices = sample.column("index").unwrap().idx().unwrap();
+
+        assert_eq!(indices.len(), 1000);
+        assert!(indices.into_no_null_iter().all(|i| i != 1 && i != 2));
+
+        // the errors of the weights fail the query
+        let indices = sample_weighted_expr(col("weight"), 5, false, None);
+        assert!(data_frame().lazy().select([indices]).collect().is_err());
+    }
+}
@@ -0,0 +1,79 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_resize_counts_have_exact_size() {
+        let weights: Vec<f64> = vec![5.6, 7.8, 0.0, 9.7, 1.1, 2.0];
+        let sum: f64 = weights.iter().sum();
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        for &new_size in &[1, 2, 5, 6, 7, 100, 1001] {
+            let counts = random_choice.resize_counts(&weights, new_size);
+
+            assert_eq!(counts.iter().sum::<usize>(), new_size);
+            assert_eq!(counts[2], 0);
+
+            for (&count, &weight) in counts.iter().zip(weights.iter()) {
+                let expected = new_size as f64 * weight / sum;
+                assert!(count as f64 >= expected.floor() && count as f64 <= expected.ceil());
+            }
+        }
+    }
+
+    #[test]
+    fn test_resize_counts_preserve_expected_proportions() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 4.0];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+        let mut totals = [0usize; 3];
+
+        for _ in 0..7000 {
+            let counts = random_choice.resize_counts(&weights, 3);
+            for (total, count) in totals.iter_mut().zip(counts) {
+                *total += count;
+            }
+        }
+
+        // 3 / 7, 6 / 7 and 12 / 7 copies per resize
+        assert!((totals[0] as f64 - 3000.0).abs() < 150.0, "totals: {:?}", totals);
+        assert!((totals[1] as f64 - 6000.0).abs() < 150.0, "totals: {:?}", totals);
+        assert!((totals[2] as f64 - 12000.0).abs() < 150.0, "totals: {:?}", totals);
+    }
+
+    #[test]
+    fn test_resize_population_shrinks_and_grows() {
+        let population = vec!["a", "b", "c", "d"];
+        let fitness: Vec<f64> = vec![4.0, 2.0, 1.0, 1.0];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        let (grown, weights) = random_choice.resize_population(&population, &fitness, 16);
+        assert_eq!(grown, vec!["a", "a", "a", "a", "a", "a", "a", "a",
+                               "b", "b", "b", "b", "c", "c", "d", "d"]);
+        assert_eq!(weights[7..9], [4.0, 2.0]);
+
+        let (shrunk, weights) = random_choice.resize_population(&population, &fitness, 3);
+        assert_eq!(shrunk.len(), 3);
+        assert_eq!(weights.len(), 3);
+        assert_eq!(shrunk[0], "a");
+    }
+
+    #[test]
+    fn test_resize_population_degenerate() {
+        let population = vec![1, 2];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        assert_eq!(random_choice.resize_population(&population, &[0.0, 0.0], 5), (vec![], vec![]));
+        assert_eq!(random_choice.resize_population(&population, &[1.0, 1.0], 0), (vec![], vec![]));
+        assert_eq!(random_choice.resize_counts(&[], 5), Vec::<usize>::new());
+    }
+}
@@ -0,0 +1,320 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use random_choice::RandomChoice;
+    use random_choice::prepared::{NotCumulative, Placement, PreparedChoice};
+    use random_choice::core::progress::Phase;
+    use rand::SeedableRng;
+
+    fn skewed_weights(capacity: usize, dominant: usize) -> Vec<f64> {
+        let mut weights: Vec<f64> = vec![1e-9; capacity];
+        weights[dominant] = 1e9;
+        weights
+    }
+
+    fn assert_same_as_random_choice(weights: &[f64], n: usize) {
+        let prepared = PreparedChoice::new(weights);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let expected = RandomChoice::new(rng).random_choice_indices_f64(weights, n);
+
+        for &placement in &[Placement::Linear,
+                            Placement::Gallop,
+                            Placement::BinarySearch,
+                            Placement::Auto] {
+            let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let choices = prepared.sample_n_with(&mut rng, n, placement);
+
+            assert_eq!(choices, expected, "{:?}", placement);
+        }
+    }
+
+    #[test]
+    fn test_prepared_choice_matches_random_choice() {
+        let linear: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+        let sparse: Vec<f64> = (0..1000).map(|i| if i % 97 == 0 { 1.0 } else { 0.0 }).collect();
+
+        assert_same_as_random_choice(&linear, 1);
+        assert_same_as_random_choice(&linear, 10000);
+        assert_same_as_random_choice(&sparse, 50);
+        assert_same_as_random_choice(&skewed_weights(10000, 0), 100);
+        assert_same_as_random_choice(&skewed_weights(10000, 5000), 100);
+        assert_same_as_random_choice(&skewed_weights(10000, 9999), 100);
+    }
+
+    #[test]
+    fn test_prepared_choice_dominant_weight() {
+        let capacity: usize = 1000000;
+        let dominant = 765432;
+        let prepared = PreparedChoice::new(&skewed_weights(capacity, dominant));
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        for _ in 0..1000 {
+            let choices = prepared.sample_n(&mut rng, 10);
+            assert_eq!(choices.len(), 10);
+            assert!(choices.iter().all(|&i| i == dominant));
+        }
+    }
+
+    #[test]
+    fn test_prepared_choice_dominant_first_weight() {
+        // the tiny weights sum up to 1e-3, which is less than the gap between two spokes
+        let capacity: usize = 1000000;
+        let prepared = PreparedChoice::new(&skewed_weights(capacity, 0));
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        let choices = prepared.sample_n(&mut rng, 1000000);
+        let others = choices.iter().filter(|&&i| i != 0).count();
+
+        assert_eq!(choices.len(), 1000000);
+        assert!(others <= 1);
+    }
+
+    #[test]
+    fn test_prepared_choice_few_spokes_on_many_weights() {
+        let weights: Vec<f64> = (0..1000000).map(|i| (i % 10) as f64).collect();
+        let prepared = PreparedChoice::new(&weights);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        let choices = prepared.sample_n_with(&mut rng, 10, Placement::BinarySearch);
+
+        assert_eq!(choices.len(), 10);
+        assert!(choices.windows(2).all(|pair| pair[1] - pair[0] > 50000));
+        assert!(choices.iter().all(|&i| weights[i] > 0.0));
+    }
+
+    #[test]
+    fn test_prepared_choice_weights() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 0.0, 4.0];
+        let prepared = PreparedChoice::new(&weights);
+
+        assert_eq!(prepared.len(), 4);
+        assert_eq!(prepared.total_weight(), 7.0);
+        assert_eq!(*prepared.prefix_sums(), [1.0, 3.0, 3.0, 7.0]);
+        assert_eq!(prepared.weight(0), 1.0);
+        assert_eq!(prepared.weight(2), 0.0);
+        assert_eq!(prepared.weight(3), 4.0);
+    }
+
+    #[test]
+    fn test_prepared_choice_with_progress() {
+        let weights: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+        let mut processed: Vec<usize> = Vec::new();
+
+        let prepared = PreparedChoice::with_progress(&weights, 250, |progress| {
+                assert_eq!(progress.phase, Phase::Summing);
+                processed.push(progress.processed);
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+
+        assert_eq!(prepared, PreparedChoice::new(&weights));
+        assert_eq!(processed, vec![250, 500, 750, 1000]);
+
+        let error = PreparedChoice::with_progress(&weights, 100, |_| ControlFlow::Break(()))
+            .unwrap_err();
+        assert_eq!(error.progress.processed, 100);
+    }
+
+    #[test]
+    fn test_prepared_choice_empty() {
+        let prepared = PreparedChoice::new(&[]);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(prepared.is_empty());
+        assert_eq!(prepared.total_weight(), 0.0);
+        assert_eq!(prepared.sample(&mut rng), None);
+        assert!(prepared.sample_n(&mut rng, 10).is_empty());
+    }
+
+    #[test]
+    fn test_prepared_choice_from_cumulative() {
+        let weights: Vec<f64> = vec![5.6, 7.8, 0.0, 9.7, 1.1, 2.0];
+        let prepared = PreparedChoice::new(&weights);
+        let cumulative = PreparedChoice::from_cumulative(prepared.prefix_sums()).unwrap();
+
+        assert_eq!(cumulative, prepared);
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let choices = cumulative.sample_n(&mut rng, 100);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        assert_eq!(choices, RandomChoice::new(rng).random_choice_indices_f64(&weights, 100));
+
+        let cdf = PreparedChoice::from_cumulative(vec![0.25, 0.25, 1.0]).unwrap();
+        assert_eq!(cdf.weight(2), 0.75);
+        assert!(PreparedChoice::from_cumulative(Vec::new()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prepared_choice_from_cumulative_rejects_invalid_values() {
+        assert_eq!(PreparedChoice::from_cumulative(vec![1.0, 2.0, 1.5]),
+                   Err(NotCumulative { index: 2 }));
+        assert_eq!(PreparedChoice::from_cumulative(vec![-1.0, 2.0]),
+                   Err(NotCumulative { index: 0 }));
+        assert_eq!(PreparedChoice::from_cumulative(vec![1.0, f64::INFINITY]),
+                   Err(NotCumulative { index: 1 }));
+        assert_eq!(PreparedChoice::from_cumulative(vec![1.0, f64::NAN]),
+                   Err(NotCumulative { index: 1 }));
+    }
+
+    #[test]
+    fn test_prepared_choice_update_many() {
+        let mut weights: Vec<f64> = (0..1000).map(|i| (i % 13) as f64).collect();
+        let mut prepared = PreparedChoice::new(&weights);
+
+        prepared.update_many(&[(3, 100.0), (500, 0.0), (999, 2.5)]);
+        weights[3] = 100.0;
+        weights[500] = 0.0;
+        weights[999] = 2.5;
+
+        let expected = PreparedChoice::new(&weights);
+
+        assert_eq!(prepared.total_weight(), expected.total_weight());
+        assert_eq!(prepared.weight(3), 100.0);
+        assert_eq!(prepared, expected);
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let choices = prepared.sample_n(&mut rng, 5000);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let expected_choices = expected.sample_n(&mut rng, 5000);
+
+        // the tree and the prefix sums only differ, if a spoke hits a prefix sum exactly
+        let differences = choices.iter().zip(expected_choices.iter()).filter(|(a, b)| a != b);
+        assert!(differences.count() <= 5);
+        assert!(!choices.contains(&500));
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let excluding = prepared.sample_excluding(&mut rng, 100, &[3]);
+        assert_eq!(excluding.len(), 100);
+        assert!(!excluding.contains(&3) && !excluding.contains(&500));
+
+        prepared.update_many(&[(3, 1.0)]);
+        weights[3] = 1.0;
+        prepared.flush();
+
+        assert_eq!(*prepared.prefix_sums(), *PreparedChoice::new(&weights).prefix_sums());
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let rng_expected = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        assert_eq!(prepared.sample_n(&mut rng, 100),
+                   RandomChoice::new(rng_expected).random_choice_indices_f64(&weights, 100));
+    }
+
+    #[test]
+    fn test_prepared_choice_sample_excluding() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 5.0, 0.0, 6.0];
+        let excluded = [6, 1, 3, 1, 100];
+        let prepared = PreparedChoice::new(&weights);
+
+        // the same as drawing from the weights without the excluded ones
+        let mut remaining = weights.clone();
+        remaining[1] = 0.0;
+        remaining[3] = 0.0;
+        remaining[6] = 0.0;
+
+        for n in 1..20 {
+            let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let choices = prepared.sample_excluding(&mut rng, n, &excluded);
+
+            let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let expected = RandomChoice::new(rng).random_choice_indices_f64(&remaining, n);
+
+            assert_eq!(choices, expected);
+        }
+
+        assert_eq!(prepared, PreparedChoice::new(&weights));
+    }
+
+    #[test]
+    fn test_prepared_choice_sample_excluding_frequencies() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+        let prepared = PreparedChoice::new(&weights);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_draws = 50000;
+        let mut counter = [0usize; 4];
+
+        for _ in 0..number_draws {
+            counter[prepared.sample_excluding(&mut rng, 1, &[2])[0]] += 1;
+        }
+
+        assert_eq!(counter[2], 0);
+
+        for (i, &weight) in [1.0, 2.0, 0.0, 4.0].iter().enumerate() {
+            let expected = number_draws as f64 * weight / 7.0;
+            assert!((counter[i] as f64 - expected).abs() <= expected * 0.05);
+        }
+
+        assert!(prepared.sample_excluding(&mut rng, 3, &[0, 1, 2, 3]).is_empty());
+        assert!(prepared.sample_excluding(&mut rng, 0, &[]).is_empty());
+        assert_eq!(prepared.sample_excluding(&mut rng, 2, &[0, 1, 3]), vec![2, 2]);
+    }
+
+    #[cfg(feature = "mmap")]
+    mod mapped {
+        use std::env;
+        use std::fs::{self, File};
+        use std::io::{ErrorKind, Write};
+        use std::path::PathBuf;
+
+        use random_choice::prepared::{MappedPreparedChoice, PreparedChoice};
+        use rand::SeedableRng;
+
+        fn temp_path(name: &str) -> PathBuf {
+            env::temp_dir().join(format!("random_choice_{}_{}", name, ::std::process::id()))
+        }
+
+        #[test]
+        fn test_mapped_prepared_choice_matches_prepared_choice() {
+            let mut weights: Vec<f64> = (0..10000).map(|i| ((i * 7) % 23) as f64).collect();
+            weights[1234] = 1e9;
+            let path = temp_path("mapped_weights");
+
+            MappedPreparedChoice::create(&path, &weights).unwrap();
+            let prepared = PreparedChoice::new(&weights);
+
+            for &block_size in &[1, 7, 100, 4096, 100000] {
+                let mapped = MappedPreparedChoice::open_with_block_size(&path, block_size).unwrap();
+
+                assert_eq!(mapped.len(), weights.len());
+                assert_eq!(mapped.block_size(), block_size);
+                assert_eq!(mapped.total_weight(), prepared.total_weight());
+                assert_eq!(mapped.weight(1234), 1e9);
+
+                for &n in &[1, 10, 10000, 100000] {
+                    let mut rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+                    let mut rng_prepared = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+                    assert_eq!(mapped.sample_n(&mut rng, n),
+                               prepared.sample_n(&mut rng_prepared, n));
+                }
+            }
+
+            fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn test_mapped_prepared_choice_rejects_truncated_file() {
+            let path = temp_path("mapped_weights_truncated");
+            File::create(&path).unwrap().write_all(&[0u8; 12]).unwrap();
+
+            let error = MappedPreparedChoice::open(&path).unwrap_err();
+            assert_eq!(error.kind(), ErrorKind::InvalidData);
+
+            // an empty file has no weights
+            File::create(&path).unwrap();
+            let mapped = MappedPreparedChoice::open(&path).unwrap();
+            let mut rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+            assert!(mapped.is_empty());
+            assert_eq!(mapped.sample(&mut rng), None);
+
+            fs::remove_file(&path).unwrap();
+        }
+    }
+}
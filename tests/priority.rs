@@ -0,0 +1,87 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::streaming::priority::PrioritySketch;
+    use rand::SeedableRng;
+
+    fn flows() -> Vec<(usize, f64)> {
+        // heavy-tailed flow sizes of 4 hosts
+        (0..200)
+            .map(|i| (i % 4, 1.0 + ((i * 37) % 101) as f64 * ((i % 5) as f64).powi(3)))
+            .collect()
+    }
+
+    #[test]
+    fn test_priority_sketch_is_unbiased() {
+        let flows = flows();
+        let exact: f64 = flows.iter().filter(|&&(host, _)| host == 1).map(|&(_, w)| w).sum();
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+        let number_sketches = 5000;
+        let mut mean = 0.0;
+        let mut mean_variance = 0.0;
+        let mut squares = 0.0;
+
+        for _ in 0..number_sketches {
+            let sketch = random_choice.priority_sample_f64(flows.iter().cloned(), 20);
+            let estimate = sketch.estimate_subset_sum(|&host| host == 1);
+
+            assert_eq!(sketch.len(), 20);
+            mean += estimate / number_sketches as f64;
+            squares += estimate * estimate / number_sketches as f64;
+            mean_variance += sketch.estimate_subset_variance(|&host| host == 1) /
+                             number_sketches as f64;
+        }
+
+        let variance = squares - mean * mean;
+
+        assert!((mean - exact).abs() < 0.02 * exact);
+        assert!((mean_variance - variance).abs() < 0.1 * variance);
+    }
+
+    #[test]
+    fn test_priority_sketch_exact_for_few_items() {
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut sketch = PrioritySketch::new(5);
+
+        for &(item, weight) in &[("a", 1.0), ("b", 2.0), ("c", 0.0), ("d", 4.0)] {
+            sketch.offer(&mut rng, item, weight);
+        }
+
+        assert_eq!(sketch.seen(), 4);
+        assert_eq!(sketch.len(), 3);
+        assert_eq!(sketch.threshold(), 0.0);
+        assert_eq!(sketch.estimate_total(), 7.0);
+        assert_eq!(sketch.estimate_subset_variance(|_| true), 0.0);
+    }
+
+    #[test]
+    fn test_priority_sketch_estimates_at_least_the_threshold() {
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let sketch = RandomChoice::new(rng).priority_sample_f64(flows(), 10);
+        let threshold = sketch.threshold();
+
+        assert!(threshold > 0.0);
+        assert_eq!(sketch.samples().len(), 10);
+
+        let samples = sketch.into_samples();
+        assert_eq!(samples.len(), 10);
+
+        for &(_, estimate) in &samples {
+            assert!(estimate >= threshold);
+        }
+    }
+
+    #[test]
+    fn test_priority_sketch_empty() {
+        let sketch: PrioritySketch<usize> = PrioritySketch::new(0);
+
+        assert!(sketch.is_empty());
+        assert_eq!(sketch.estimate_total(), 0.0);
+        assert!(sketch.into_samples().is_empty());
+    }
+}
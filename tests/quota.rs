@@ -0,0 +1,92 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::random_choice;
+    use random_choice::RandomChoice;
+    use random_choice::strategies::quota::{QuotaError, Shortfall};
+    use rand::SeedableRng;
+
+    fn population() -> (Vec<(u8, char)>, Vec<f64>) {
+        let mut cells: Vec<(u8, char)> = Vec::new();
+        let mut weights: Vec<f64> = Vec::new();
+
+        for i in 0..600 {
+            cells.push(((i % 3) as u8, ['n', 's'][i % 2]));
+            weights.push((i % 5) as f64);
+        }
+
+        (cells, weights)
+    }
+
+    #[test]
+    fn test_quotas_are_met() {
+        let (cells, weights) = population();
+        let quotas = [((0, 'n'), 10), ((1, 's'), 25), ((2, 'n'), 0), ((2, 's'), 40)];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let sample = RandomChoice::new(rng).sample_quotas(&cells, &weights, &quotas).unwrap();
+
+        assert_eq!(sample.len(), 75);
+        assert!(sample.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(sample.iter().all(|&i| weights[i] > 0.0));
+
+        for &(cell, quota) in quotas.iter() {
+            assert_eq!(sample.iter().filter(|&&i| cells[i] == cell).count(), quota);
+        }
+    }
+
+    #[test]
+    fn test_quotas_weighted_within_cell() {
+        let cells = ["a", "a", "a", "b"];
+        let weights = [1.0, 9.0, 0.0, 1.0];
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut counter = [0usize; 4];
+
+        for _ in 0..10000 {
+            let mut random_choice = RandomChoice::new(&mut rng);
+
+            for i in random_choice.sample_quotas(&cells, &weights, &[("a", 1)]).unwrap() {
+                counter[i] += 1;
+            }
+        }
+
+        assert_eq!(counter[2] + counter[3], 0);
+        assert!((counter[1] as f64 - 9000.0).abs() < 200.0);
+    }
+
+    #[test]
+    fn test_quotas_take_whole_cell() {
+        let cells = ["a", "a", "a", "b"];
+        let weights = [1.0, 2.0, 3.0, 1.0];
+
+        assert_eq!(random_choice().sample_quotas(&cells, &weights, &[("a", 3)]),
+                   Ok(vec![0, 1, 2]));
+        assert_eq!(random_choice().sample_quotas(&cells, &weights, &[]), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_quotas_infeasible() {
+        let cells = ["a", "a", "b", "b"];
+        let weights = [1.0, 0.0, 1.0, 1.0];
+        let quotas = [("a", 2), ("b", 2), ("c", 1)];
+
+        let error = random_choice().sample_quotas(&cells, &weights, &quotas).unwrap_err();
+
+        assert_eq!(error,
+                   QuotaError {
+                       shortfalls: vec![Shortfall { cell: "a", quota: 2, available: 1 },
+                                        Shortfall { cell: "c", quota: 1, available: 0 }],
+                   });
+        assert_eq!(error.to_string(),
+                   "quotas are infeasible: cell \"a\" needs 2 items, but has 1; \
+                    cell \"c\" needs 1 items, but has 0");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_quotas_duplicate_cell() {
+        let _ = random_choice().sample_quotas(&["a"], &[1.0], &[("a", 1), ("a", 1)]);
+    }
+}
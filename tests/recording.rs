@@ -0,0 +1,65 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::prepared::PreparedChoice;
+    use random_choice::core::recording::{Draw, RecordingRng, ReplayRng};
+    use random_choice::RandomChoice;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_replay_chooses_the_same_items() {
+        let weights: Vec<f64> = (0..100).map(|i| (i % 7) as f64).collect();
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut recorder = RecordingRng::new(rng);
+
+        let indices = RandomChoice::new(&mut recorder).random_choice_indices_f64(&weights, 20);
+        let without = RandomChoice::new(&mut recorder)
+            .random_choice_indices_without_replacement_f64(&weights, 5);
+        let prepared = PreparedChoice::new(&weights).sample(&mut recorder);
+
+        let (_, draws) = recorder.into_parts();
+        let mut replay = ReplayRng::new(draws);
+
+        assert_eq!(RandomChoice::new(&mut replay).random_choice_indices_f64(&weights, 20),
+                   indices);
+        assert_eq!(RandomChoice::new(&mut replay)
+                       .random_choice_indices_without_replacement_f64(&weights, 5),
+                   without);
+        assert_eq!(PreparedChoice::new(&weights).sample(&mut replay), prepared);
+        assert_eq!(replay.remaining(), 0);
+    }
+
+    #[test]
+    fn test_recording_passes_values_through() {
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut expected = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut recorder = RecordingRng::new(rng);
+
+        let a = recorder.next_u32();
+        let b = recorder.next_u64();
+
+        assert_eq!(a, expected.next_u32());
+        assert_eq!(b, expected.next_u64());
+        assert_eq!(recorder.draws(), &[Draw::U32(a), Draw::U64(b)]);
+        assert_eq!(recorder.take_draws().len(), 2);
+        assert!(recorder.draws().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "replay exhausted after 1 values")]
+    fn test_replay_exhausted() {
+        let mut replay = ReplayRng::new(vec![Draw::U64(7)]);
+
+        assert_eq!(replay.next_u64(), 7);
+        assert_eq!(replay.position(), 1);
+        replay.next_u64();
+    }
+
+    #[test]
+    #[should_panic(expected = "replay diverged at value 0")]
+    fn test_replay_diverged() {
+        ReplayRng::new(vec![Draw::U64(7)]).next_u32();
+    }
+}
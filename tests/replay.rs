@@ -0,0 +1,99 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::strategies::replay::PrioritizedReplayBuffer;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_replay_buffer_matches_prioritized_sample() {
+        let priorities: Vec<f64> = vec![1.0, 4.0, 9.0, 0.0, 16.0];
+        let mut buffer = PrioritizedReplayBuffer::new(8, 0.5, 0.4);
+
+        for (i, &priority) in priorities.iter().enumerate() {
+            assert_eq!(buffer.push(i * 10, priority), i);
+        }
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let batch = buffer.sample(&mut rng, 10);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let expected = RandomChoice::new(rng).prioritized_sample_f64(&priorities, 10, 0.5, 0.4);
+
+        assert_eq!(batch.indices, expected.indices);
+
+        for (weight, expected) in batch.weights.iter().zip(expected.weights.iter()) {
+            assert!((weight - expected).abs() < 1e-12);
+        }
+
+        assert_eq!(buffer.len(), 5);
+        assert_eq!(buffer.get(2), Some(&20));
+        assert_eq!(buffer.priority(4), 16.0);
+        assert_eq!(buffer.max_priority(), 16.0);
+    }
+
+    #[test]
+    fn test_replay_buffer_overwrites_oldest() {
+        let mut buffer = PrioritizedReplayBuffer::new(3, 1.0, 1.0);
+
+        for i in 0..5 {
+            buffer.push(i, 1.0);
+        }
+
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.capacity(), 3);
+        assert_eq!(buffer.items(), &[3, 4, 2]);
+        assert_eq!(buffer.push(5, 2.0), 2);
+        assert_eq!(buffer.items(), &[3, 4, 5]);
+    }
+
+    #[test]
+    fn test_replay_buffer_update_priorities() {
+        let mut buffer = PrioritizedReplayBuffer::new(4, 1.0, 1.0);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        for i in 0..4 {
+            buffer.push(i, 1.0);
+        }
+
+        buffer.update_priorities(&[0, 2], &[0.0, 3.0]);
+
+        let number_draws = 20000;
+        let mut counter = [0usize; 4];
+
+        for _ in 0..number_draws {
+            counter[buffer.sample(&mut rng, 1).indices[0]] += 1;
+        }
+
+        assert_eq!(counter[0], 0);
+
+        for (i, &priority) in [0.0, 1.0, 3.0, 1.0].iter().enumerate() {
+            let expected = number_draws as f64 * priority / 5.0;
+            assert!((counter[i] as f64 - expected).abs() <= expected * 0.05);
+        }
+
+        // the least likely transitions 1 and 3 have the greatest weight
+        let batch = buffer.sample(&mut rng, 5);
+        assert_eq!(batch.indices, vec![1, 2, 2, 2, 3]);
+        assert_eq!(batch.weights[0], 1.0);
+        assert!((batch.weights[1] - 1.0 / 3.0).abs() < 1e-12);
+
+        buffer.set_beta(0.0);
+        assert!(buffer.sample(&mut rng, 5).weights.iter().all(|&w| w == 1.0));
+    }
+
+    #[test]
+    fn test_replay_buffer_empty() {
+        let mut buffer: PrioritizedReplayBuffer<u8> = PrioritizedReplayBuffer::new(4, 0.6, 0.4);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(buffer.is_empty());
+        assert!(buffer.sample(&mut rng, 3).indices.is_empty());
+
+        buffer.push(1, 0.0);
+        assert!(buffer.sample(&mut rng, 3).indices.is_empty());
+        assert_eq!(buffer.max_priority(), 1.0);
+    }
+}
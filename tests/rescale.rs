@@ -0,0 +1,127 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::core::collection::ChunkedPrefixSums;
+    use random_choice::core::rescale::{rescale_in_place, Rescaling, RELATIVE_EPSILON, TINY};
+    use random_choice::prepared::PreparedChoice;
+    use random_choice::{RandomChoice, Sampler};
+    use rand::SeedableRng;
+
+    fn random_choice() -> RandomChoice<super::rand::StdRng> {
+        RandomChoice::new(super::rand::StdRng::from_seed(&[5000, 44, 55, 199]))
+    }
+
+    #[test]
+    fn test_rescaling_detection() {
+        assert_eq!(Rescaling::detect(&[1.0, 2.0, 0.0]), None);
+        assert_eq!(Rescaling::detect(&[]), None);
+        assert_eq!(Rescaling::detect(&[0.0, 0.0]), None);
+        assert_eq!(Rescaling::detect(&[1.0, 1e-310]).map(|r| r.exponent()), Some(0));
+        assert_eq!(Rescaling::detect(&[1.0, RELATIVE_EPSILON]), None);
+
+        // far below the greatest weight, but not subnormal
+        let rescaling = Rescaling::detect(&[1e20, 1.0, 3e4]).unwrap();
+        assert_eq!(rescaling.exponent(), 0);
+        assert_eq!(rescaling.apply(1.0), 0.0);
+        assert_eq!(rescaling.apply(3e4), 3e4);
+
+        let rescaling = Rescaling::detect(&[1e-300 * 1e-10, TINY / 4.0]).unwrap();
+        assert!(rescaling.exponent() > 0);
+        assert_eq!(rescaling.apply(TINY / 4.0), 1.0);
+    }
+
+    #[test]
+    fn test_rescaled_draw_is_accurate() {
+        // exactly 3:1, but subnormal
+        let weights: Vec<f64> = vec![f64::from_bits(3000), f64::from_bits(1000)];
+
+        for n in [4, 100, 1000, 4000].iter() {
+            let indices = random_choice().random_choice_indices_f64(&weights, *n);
+
+            assert_eq!(indices.len(), *n);
+            assert_eq!(indices.iter().filter(|&&i| i == 0).count(), n * 3 / 4);
+        }
+    }
+
+    #[test]
+    fn test_rescaled_draw_matches_scaled_weights() {
+        let weights: Vec<f64> = (1..200).map(|i| i as f64 * 1e-315).collect();
+        let scaled: Vec<f64> = weights.iter()
+            .map(|&w| w * 2f64.powi(600) * 2f64.powi(450))
+            .collect();
+        let samples: Vec<usize> = (0..weights.len()).collect();
+
+        let expected = random_choice().random_choice_indices_f64(&scaled, 1000);
+
+        assert_eq!(random_choice().random_choice_indices_f64(&weights, 1000), expected);
+        assert_eq!(Sampler::new(super::rand::StdRng::from_seed(&[5000, 44, 55, 199]))
+                       .sample(&samples, &weights, 1000)
+                       .unwrap()
+                       .into_iter()
+                       .cloned()
+                       .collect::<Vec<usize>>(),
+                   expected);
+
+    }
+
+    #[test]
+    fn test_every_path_rescales_the_same_way() {
+        // a few ulps each, so that the unscaled spoke gap is rounded to whole ulps
+        let weights: Vec<f64> = (1..200).map(f64::from_bits).collect();
+        let expected = random_choice().random_choice_indices_f64(&weights, 1000);
+
+        let mut unscaled = random_choice().with_rescaling(false);
+        assert_ne!(unscaled.random_choice_indices_f64(&weights, 1000), expected);
+
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        assert_eq!(PreparedChoice::new(&weights).sample_n(&mut rng, 1000), expected);
+
+        let shards: Vec<&[f64]> = vec![&weights[..50], &weights[50..]];
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        assert_eq!(ChunkedPrefixSums::new(&shards[..]).sample_n(&mut rng, 1000), expected);
+        assert_eq!(random_choice().random_choice_indices_from(&shards[..], 1000), expected);
+
+        let group_ids = vec!["tiny"; weights.len()];
+        assert_eq!(random_choice().sample_per_group(&group_ids, &weights, 1000),
+                   vec![("tiny", expected)]);
+    }
+
+    #[test]
+    #[cfg(feature = "legacy")]
+    fn test_legacy_wrappers_dont_rescale() {
+        let weights: Vec<f64> = (1..200).map(f64::from_bits).collect();
+        let samples: Vec<usize> = (0..weights.len()).collect();
+
+        let mut unscaled = random_choice().with_rescaling(false);
+        let expected = unscaled.random_choice_indices_f64(&weights, 1000);
+
+        assert_eq!(random_choice().random_choice_f64(&samples, &weights, 1000)
+                       .into_iter()
+                       .cloned()
+                       .collect::<Vec<usize>>(),
+                   expected);
+    }
+
+    #[test]
+    fn test_subnormal_weights_are_flushed() {
+        let mut weights: Vec<f64> = vec![1e-310; 1000];
+        weights.push(1.0);
+
+        let indices = random_choice().random_choice_indices_f64(&weights, 50);
+        assert_eq!(indices, vec![1000; 50]);
+
+        assert!(rescale_in_place(&mut weights).is_some());
+        assert_eq!(weights.iter().filter(|&&w| w > 0.0).count(), 1);
+        assert!(rescale_in_place(&mut weights).is_none());
+    }
+
+    #[test]
+    fn test_rescaling_can_be_switched_off() {
+        let weights: Vec<f64> = vec![f64::from_bits(3000), f64::from_bits(1000)];
+        let mut random_choice = random_choice().with_rescaling(false);
+
+        assert_eq!(random_choice.random_choice_indices_f64(&weights, 100).len(), 100);
+    }
+}
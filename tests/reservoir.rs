@@ -0,0 +1,184 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::streaming::reservoir::{VarOptReservoir, WeightedReservoir};
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_reservoir_first_draw_frequencies() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0, 0.0];
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_draws = 50000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for _ in 0..number_draws {
+            let mut reservoir = WeightedReservoir::new(2);
+
+            for (i, &weight) in weights.iter().enumerate() {
+                reservoir.offer(&mut rng, i, weight);
+            }
+
+            let samples = reservoir.into_samples();
+            assert_eq!(samples.len(), 2);
+            assert!(samples[0] != samples[1]);
+
+            // the first sample is drawn proportionally to the weights
+            counter[samples[0]] += 1;
+        }
+
+        assert_eq!(counter[4], 0);
+
+        for i in 0..4 {
+            let expected = number_draws as f64 * weights[i] / 10.0;
+            assert!((counter[i] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_reservoir_matches_without_replacement() {
+        let weights: Vec<f64> = (0..100).map(|i| (i % 7) as f64).collect();
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let samples = RandomChoice::new(rng)
+            .reservoir_sample_f64(weights.iter().cloned().enumerate(), 10);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let indices = RandomChoice::new(rng)
+            .random_choice_indices_without_replacement_f64(&weights, 10);
+
+        assert_eq!(samples, indices);
+    }
+
+    #[test]
+    fn test_reservoir_counts() {
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut reservoir = WeightedReservoir::new(5);
+
+        for (i, &weight) in [1.0, 0.0, -1.0, 2.5, f64::NAN].iter().enumerate() {
+            reservoir.offer(&mut rng, i, weight);
+        }
+
+        assert_eq!(reservoir.capacity(), 5);
+        assert_eq!(reservoir.seen(), 5);
+        assert_eq!(reservoir.len(), 2);
+        assert_eq!(reservoir.total_weight(), 3.5);
+
+        let mut empty = WeightedReservoir::new(0);
+        empty.offer(&mut rng, 0, 1.0);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_varopt_is_unbiased() {
+        let weights: Vec<f64> = (0..100)
+            .map(|i| 1.0 + ((i * 37) % 11) as f64 * (i % 3) as f64)
+            .collect();
+        let exact: f64 = weights.iter().step_by(2).sum();
+        let total: f64 = weights.iter().sum();
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+        let number_summaries = 5000;
+        let mut mean = 0.0;
+
+        for _ in 0..number_summaries {
+            let items = weights.iter().cloned().enumerate();
+            let reservoir = random_choice.varopt_sample_f64(items, 10);
+
+            assert_eq!(reservoir.len(), 10);
+            // the total weight is preserved exactly
+            assert!((reservoir.estimate_total() - total).abs() < 1e-9 * total);
+
+            mean += reservoir.estimate_subset_sum(|&i| i % 2 == 0) / number_summaries as f64;
+        }
+
+        assert!((mean - exact).abs() < 0.02 * exact);
+    }
+
+    #[test]
+    fn test_varopt_keeps_heavy_items() {
+        let mut weights: Vec<f64> = vec![1.0; 50];
+        weights[7] = 1000.0;
+        weights[42] = 500.0;
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let reservoir = RandomChoice::new(rng)
+            .varopt_sample_f64(weights.iter().cloned().enumerate(), 5);
+        let threshold = reservoir.threshold();
+        let samples = reservoir.into_samples();
+
+        assert!(samples.contains(&(7, 1000.0)));
+        assert!(samples.contains(&(42, 500.0)));
+
+        // the light items share the threshold as adjusted weight
+        assert_eq!(samples.iter().filter(|&&(_, weight)| weight == threshold).count(), 3);
+        assert!((threshold - 48.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_varopt_exact_for_few_items() {
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut reservoir = VarOptReservoir::new(4);
+
+        for &(item, weight) in &[("a", 1.0), ("b", 0.0), ("c", 2.0)] {
+            reservoir.offer(&mut rng, item, weight);
+        }
+
+        assert_eq!(reservoir.seen(), 3);
+        assert_eq!(reservoir.len(), 2);
+        assert_eq!(reservoir.threshold(), 0.0);
+        assert_eq!(reservoir.estimate_subset_sum(|&item| item == "c"), 2.0);
+        assert!(VarOptReservoir::<usize>::new(0).is_empty());
+    }
+
+    #[cfg(feature = "async")]
+    mod stream {
+        extern crate futures;
+
+        use self::futures::executor::block_on;
+        use self::futures::stream::{self, StreamExt};
+        use random_choice::RandomChoice;
+        use random_choice::streaming::reservoir::{ReservoirStreamBuilder, WeightedReservoir};
+        use rand::SeedableRng;
+
+        #[test]
+        fn test_stream_builder_matches_iterator() {
+            let weights: Vec<f64> = (0..1000).map(|i| i as f64).collect();
+
+            // weights arriving in pages of 100
+            let pages = stream::iter(0..10).then(|page| {
+                let items: Vec<(usize, f64)> = (page * 100..(page + 1) * 100)
+                    .map(|i| (i, i as f64))
+                    .collect();
+                futures::future::ready(stream::iter(items))
+            });
+
+            let rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let reservoir = block_on(ReservoirStreamBuilder::new(rng, 20).build(pages.flatten()));
+
+            let rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let expected = RandomChoice::new(rng)
+                .reservoir_sample_f64(weights.iter().cloned().enumerate(), 20);
+
+            assert_eq!(reservoir.seen(), 1000);
+            assert_eq!(reservoir.into_samples(), expected);
+        }
+
+        #[test]
+        fn test_stream_builder_extend() {
+            let rng = ::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+            let mut reservoir = WeightedReservoir::new(3);
+            reservoir.offer(&mut ::rand::thread_rng(), "first", 1.0);
+
+            let stream = stream::iter(vec![("second", 1.0), ("third", 1.0), ("fourth", 0.0)]);
+            let reservoir = block_on(ReservoirStreamBuilder::new(rng, 3).extend(reservoir, stream));
+
+            assert_eq!(reservoir.seen(), 4);
+            assert_eq!(reservoir.len(), 3);
+            assert!(!reservoir.into_samples().contains(&"fourth"));
+        }
+    }
+}
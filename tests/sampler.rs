@@ -0,0 +1,139 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::core::test_vectors::{Algorithm, TEST_VECTORS};
+    use random_choice::{RandomChoice, SampleError, Sampler, Strategy};
+    use rand::SeedableRng;
+
+    fn sampler() -> Sampler<super::rand::StdRng> {
+        Sampler::new(super::rand::StdRng::from_seed(&[5000, 44, 55, 199]))
+    }
+
+    #[test]
+    fn test_sampler_stochastic_matches_indices() {
+        let weights: Vec<f64> = (0..500).map(|i| i as f64).collect();
+        let samples: Vec<usize> = (0..500).collect();
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let expected = RandomChoice::new(rng).random_choice_indices_f64(&weights, 10000);
+
+        assert_eq!(sampler().strategy(), Strategy::Stochastic);
+        assert_eq!(sampler().sample_indices(&weights, 10000), Ok(expected.clone()));
+        assert_eq!(sampler().sample(&samples, &weights, 10000).unwrap(),
+                   expected.iter().collect::<Vec<&usize>>());
+    }
+
+    #[test]
+    fn test_sampler_matches_test_vectors() {
+        for vector in TEST_VECTORS.iter() {
+            let choices = match vector.algorithm {
+                Algorithm::RandomChoiceF64 => {
+                    Sampler::new(vector.rng()).sample_indices(vector.weights, vector.n)
+                }
+                Algorithm::RandomChoiceF32 => {
+                    let weights: Vec<f32> = vector.weights.iter().map(|&w| w as f32).collect();
+                    Sampler::new(vector.rng()).sample_indices(&weights, vector.n)
+                }
+                Algorithm::IndicesWithoutReplacementF64 => {
+                    Sampler::new(vector.rng())
+                        .with_strategy(Strategy::WithoutReplacement)
+                        .sample_indices(vector.weights, vector.n)
+                }
+                _ => continue,
+            };
+
+            assert_eq!(choices.unwrap(), vector.expected);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "legacy")]
+    fn test_sampler_matches_legacy_f32() {
+        let samples: Vec<usize> = (0..6).collect();
+        let weights: Vec<f32> = vec![5.6, 7.8, 9.7, 1.1, 2.0, 0.0];
+
+        // only weights, which aren't rescaled by the sampler, are drawn the same way
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let legacy = RandomChoice::new(rng).random_choice_f32(&samples, &weights, 100);
+
+        assert_eq!(sampler().sample(&samples, &weights, 100).unwrap(), legacy);
+    }
+
+    #[test]
+    fn test_sampler_independent_frequencies() {
+        let weights: Vec<f32> = vec![1.0, 2.0, 0.0, 3.0, 4.0];
+        let number_choices = 40000;
+
+        let choices = sampler()
+            .with_strategy(Strategy::Independent)
+            .sample_indices(&weights, number_choices)
+            .unwrap();
+
+        assert_eq!(choices.len(), number_choices);
+        // independent draws aren't sorted
+        assert!(choices.windows(2).any(|pair| pair[0] > pair[1]));
+
+        for (i, &weight) in weights.iter().enumerate() {
+            let count = choices.iter().filter(|&&c| c == i).count() as f64;
+            let expected = number_choices as f64 * weight as f64 / 10.0;
+            assert!((count - expected).abs() <= expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_sampler_without_replacement() {
+        let weights: Vec<f64> = vec![0.0, 1.0, 2.0, 3.0, 4.0, 0.0];
+        let mut sampler = sampler().with_strategy(Strategy::WithoutReplacement);
+
+        let mut choices = sampler.sample_indices(&weights, 4).unwrap();
+        choices.sort_unstable();
+
+        assert_eq!(choices, vec![1, 2, 3, 4]);
+        assert_eq!(sampler.sample_indices(&weights, 5),
+                   Err(SampleError::NotEnoughPositiveWeights { n: 5, positive: 4 }));
+    }
+
+    #[test]
+    fn test_sampler_errors() {
+        let mut sampler = sampler();
+
+        assert_eq!(sampler.sample(&[1, 2], &[1.0], 1),
+                   Err(SampleError::LengthMismatch { samples: 2, weights: 1 }));
+        assert_eq!(sampler.sample_indices(&[1.0, -2.0], 1),
+                   Err(SampleError::InvalidWeight { index: 1, weight: -2.0 }));
+        assert_eq!(sampler.sample_indices(&[f64::INFINITY], 1),
+                   Err(SampleError::InvalidWeight { index: 0, weight: f64::INFINITY }));
+        assert_eq!(sampler.sample_indices(&[f64::MAX, f64::MAX], 1), Err(SampleError::Overflow));
+        assert_eq!(sampler.sample_indices(&[0.0, 0.0], 1), Err(SampleError::NoPositiveWeight));
+        assert_eq!(sampler.sample_indices::<f64>(&[], 1), Err(SampleError::NoPositiveWeight));
+
+        match sampler.sample_indices(&[f64::NAN], 1) {
+            Err(SampleError::InvalidWeight { index: 0, weight }) => assert!(weight.is_nan()),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        assert_eq!(SampleError::NoPositiveWeight.to_string(), "no weight is positive");
+    }
+
+    #[test]
+    fn test_sampler_zero_elements() {
+        let mut sampler = sampler();
+
+        assert_eq!(sampler.sample_indices(&[0.0, 0.0], 0), Ok(vec![]));
+        assert_eq!(sampler.sample_indices::<f32>(&[], 0), Ok(vec![]));
+        assert_eq!(sampler.sample(&["a"], &[1.0], 0), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_sampler_lends_random_choice() {
+        let weights: Vec<f64> = vec![5.6, 7.8, 9.7, 1.1, 2.0];
+        let mut sampler = sampler();
+
+        let indices = sampler.random_choice_mut().random_choice_indices_f64(&weights, 3);
+
+        assert_eq!(indices.len(), 3);
+        let _: super::rand::StdRng = sampler.into_rng();
+    }
+}
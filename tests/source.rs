@@ -0,0 +1,115 @@
+extern crate random_choice;
+extern crate rand;
+#[cfg(feature = "async")]
+extern crate futures;
+
+#[cfg(test)]
+#[cfg(feature = "async")]
+mod tests {
+    use std::cell::Cell;
+
+    use futures::executor::block_on;
+    use random_choice::integrations::source::{Page, TwoPhaseSampler, WeightSource};
+    use rand::SeedableRng;
+
+    /// Serves its weights in pages of three keys and counts the lookups.
+    struct PagedSource {
+        weights: Vec<f64>,
+        bounds: Vec<f64>,
+        lookups: Cell<usize>,
+    }
+
+    impl PagedSource {
+        fn new(weights: Vec<f64>, bounds: Vec<f64>) -> Self {
+            PagedSource {
+                weights,
+                bounds,
+                lookups: Cell::new(0),
+            }
+        }
+    }
+
+    impl WeightSource for PagedSource {
+        type Key = usize;
+        type Cursor = usize;
+
+        async fn weight(&self, key: &usize) -> f64 {
+            self.lookups.set(self.lookups.get() + 1);
+            self.weights[*key]
+        }
+
+        async fn keys_page(&self, cursor: Option<usize>) -> Page<usize, usize> {
+            let start = cursor.unwrap_or(0);
+            let end = (start + 3).min(self.bounds.len());
+
+            Page {
+                entries: (start..end).map(|i| (i, self.bounds[i])).collect(),
+                next: if end < self.bounds.len() { Some(end) } else { None },
+            }
+        }
+    }
+
+    #[test]
+    fn test_two_phase_sampler_pages_through_keys() {
+        let source = PagedSource::new(vec![1.0; 7], vec![2.0; 7]);
+        let sampler = block_on(TwoPhaseSampler::build(source));
+
+        assert_eq!(sampler.len(), 7);
+        assert_eq!(sampler.keys(), &[0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(sampler.bounds(), &[2.0; 7]);
+        assert_eq!(sampler.source().lookups.get(), 0);
+    }
+
+    #[test]
+    fn test_two_phase_sampler_follows_current_weights() {
+        // the bounds are stale: the weights have changed since the index was built
+        let weights: Vec<f64> = vec![1.0, 4.0, 0.0, 3.0, 2.0];
+        let bounds: Vec<f64> = vec![4.0, 4.0, 4.0, 4.0, 4.0];
+        let sampler = block_on(TwoPhaseSampler::build(PagedSource::new(weights.clone(), bounds)));
+
+        let mut rng = rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 50000;
+        let choices = block_on(sampler.sample_n(&mut rng, number_choices));
+
+        assert_eq!(choices.len(), number_choices);
+
+        let mut counter = vec![0usize; weights.len()];
+
+        for &key in &choices {
+            counter[key] += 1;
+        }
+
+        assert_eq!(counter[2], 0);
+
+        for (i, &weight) in weights.iter().enumerate() {
+            let expected = number_choices as f64 * weight / 10.0;
+            assert!((counter[i] as f64 - expected).abs() <= expected * 0.05);
+        }
+
+        // sum of bounds / sum of weights = 2 lookups per draw
+        let lookups = sampler.source().lookups.get() as f64 / number_choices as f64;
+        assert!((lookups - 2.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_two_phase_sampler_rejects_vanished_weights() {
+        let source = PagedSource::new(vec![0.0, 0.0], vec![1.0, 1.0]);
+        let sampler = block_on(TwoPhaseSampler::build(source)).with_max_attempts(10);
+        let mut rng = rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert_eq!(block_on(sampler.sample(&mut rng)), None);
+        assert_eq!(sampler.source().lookups.get(), 10);
+        assert!(block_on(sampler.sample_n(&mut rng, 5)).is_empty());
+    }
+
+    #[test]
+    fn test_two_phase_sampler_empty_source() {
+        let source = PagedSource::new(Vec::new(), Vec::new());
+        let sampler = block_on(TwoPhaseSampler::build(source));
+        let mut rng = rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(sampler.is_empty());
+        assert_eq!(block_on(sampler.sample(&mut rng)), None);
+        assert_eq!(sampler.source().lookups.get(), 0);
+    }
+}
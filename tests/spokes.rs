@@ -0,0 +1,178 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::random_choice;
+    use random_choice::RandomChoice;
+    use random_choice::streaming::spokes::{CancellationToken, Cancelled};
+    use rand::SeedableRng;
+
+    fn weights(capacity: usize) -> Vec<f64> {
+        (0..capacity).map(|i| i as f64).collect()
+    }
+
+    #[test]
+    fn test_spokes_match_indices() {
+        let weights = weights(500);
+        let number_choices = 10000;
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let spokes = RandomChoice::new(rng).spokes_f64(&weights, number_choices);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let indices = RandomChoice::new(rng).random_choice_indices_f64(&weights, number_choices);
+
+        assert_eq!(spokes.len(), number_choices);
+        assert_eq!(spokes.collect::<Vec<usize>>(), indices);
+    }
+
+    #[test]
+    fn test_spokes_match_indices_of_tiny_weights() {
+        // subnormal weights are rescaled, otherwise the spoke gap would be a few ulps
+        let weights: Vec<f64> = vec![1e-320, 2e-320, 3e-321, 4e-320, 0.0, 5e-322];
+        let number_choices = 1000;
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let spokes = RandomChoice::new(rng).spokes_f64(&weights, number_choices);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let indices = RandomChoice::new(rng).random_choice_indices_f64(&weights, number_choices);
+
+        assert_eq!(spokes.collect::<Vec<usize>>(), indices);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let counts = RandomChoice::new(rng).random_choice_unique_with_counts(&weights, 1000);
+
+        for &(i, count) in &counts {
+            assert_eq!(count as usize, indices.iter().filter(|&&j| j == i).count());
+        }
+    }
+
+    #[test]
+    fn test_spoke_chunks() {
+        let weights = weights(500);
+        let token = CancellationToken::new();
+
+        let chunks: Vec<Vec<usize>> = random_choice()
+            .spokes_f64(&weights, 1000)
+            .chunks(300, token)
+            .map(|chunk| chunk.unwrap())
+            .collect();
+
+        let sizes: Vec<usize> = chunks.iter().map(|chunk| chunk.len()).collect();
+        assert_eq!(sizes, vec![300, 300, 300, 100]);
+    }
+
+    #[test]
+    fn test_spoke_chunks_cancelled() {
+        let weights = weights(500);
+        let token = CancellationToken::new();
+
+        let mut random_choice = random_choice();
+        let mut chunks = random_choice.spokes_f64(&weights, 1000).chunks(300, token.clone());
+
+        assert_eq!(chunks.next().unwrap().unwrap().len(), 300);
+        assert_eq!(chunks.next().unwrap().unwrap().len(), 300);
+
+        token.cancel();
+
+        assert_eq!(chunks.next(), Some(Err(Cancelled { drawn: 600 })));
+        assert_eq!(chunks.next(), None);
+    }
+
+    #[test]
+    fn test_spokes_zero_elements() {
+        let weights = weights(500);
+        let token = CancellationToken::new();
+
+        assert_eq!(random_choice().spokes_f64(&weights, 0).count(), 0);
+        assert_eq!(random_choice().spokes_f64(&[], 10).count(), 0);
+        assert_eq!(random_choice().spokes_f64(&[], 10).chunks(10, token).count(), 0);
+    }
+
+    #[test]
+    fn test_sample_count_stream_matches_spokes() {
+        let weights = weights(500);
+        let number_choices = 10000;
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let counts: Vec<(usize, u64)> = RandomChoice::new(rng)
+            .sample_count_stream(&weights, number_choices as u64)
+            .collect();
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut expected: Vec<(usize, u64)> = Vec::new();
+
+        for i in RandomChoice::new(rng).spokes_f64(&weights, number_choices) {
+            match expected.last_mut() {
+                Some(&mut (last, ref mut count)) if last == i => *count += 1,
+                _ => expected.push((i, 1)),
+            }
+        }
+
+        assert_eq!(counts, expected);
+    }
+
+    #[test]
+    fn test_sample_count_stream_huge_n() {
+        let weights = weights(1000);
+        let sum: f64 = weights.iter().sum();
+        let number_choices: u64 = 10_000_000_000;
+
+        let counts: Vec<(usize, u64)> = random_choice()
+            .sample_count_stream(&weights, number_choices)
+            .collect();
+
+        assert_eq!(counts.iter().map(|&(_, count)| count).sum::<u64>(), number_choices);
+        assert!(counts.windows(2).all(|pair| pair[0].0 < pair[1].0));
+
+        for &(i, count) in counts.iter() {
+            let expected = number_choices as f64 * weights[i] / sum;
+            assert!((count as f64 - expected).abs() <= 1.0);
+        }
+    }
+
+    #[test]
+    fn test_sample_count_stream_zero_elements() {
+        assert_eq!(random_choice().sample_count_stream(&weights(500), 0).count(), 0);
+        assert_eq!(random_choice().sample_count_stream(&[], 10).count(), 0);
+        assert_eq!(random_choice().sample_count_stream(&[0.0, 0.0], 10).count(), 0);
+        assert_eq!(random_choice().sample_count_stream(&[0.0, 2.0, 0.0], 10).collect::<Vec<_>>(),
+                   vec![(1, 10)]);
+    }
+
+    #[test]
+    fn test_unique_with_counts_matches_indices() {
+        let weights = weights(500);
+        let number_choices = 10000;
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let counts = RandomChoice::new(rng)
+            .random_choice_unique_with_counts(&weights, number_choices);
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let indices = RandomChoice::new(rng).random_choice_indices_f64(&weights, number_choices);
+        let mut expected: Vec<(usize, u32)> = Vec::new();
+
+        for i in indices {
+            match expected.last_mut() {
+                Some(&mut (last, ref mut count)) if last == i => *count += 1,
+                _ => expected.push((i, 1)),
+            }
+        }
+
+        assert_eq!(counts, expected);
+        assert_eq!(counts.iter().map(|&(_, count)| count as usize).sum::<usize>(),
+                   number_choices);
+    }
+
+    #[test]
+    fn test_unique_with_counts_zero_elements() {
+        assert!(random_choice().random_choice_unique_with_counts(&weights(500), 0).is_empty());
+        assert!(random_choice().random_choice_unique_with_counts(&[], 10).is_empty());
+        assert!(random_choice().random_choice_unique_with_counts(&[0.0, 0.0], 10).is_empty());
+        assert_eq!(random_choice().random_choice_unique_with_counts(&[0.0, 2.0, 0.0], 10),
+                   vec![(1, 10)]);
+    }
+}
@@ -0,0 +1,76 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::strategies::spread::spread;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_spread_largest_remainder() {
+        let probabilities: Vec<f64> = vec![0.1, 0.25, 0.4, 0.25];
+        let n = 9;
+        let counts = spread(n, &probabilities);
+
+        // expected counts 0.9, 2.25, 3.6, 2.25
+        assert_eq!(counts, vec![1, 2, 4, 2]);
+        assert_eq!(counts.iter().sum::<usize>(), n);
+    }
+
+    #[test]
+    fn test_spread_within_rounding() {
+        let probabilities: Vec<f64> = (0..97).map(|i| ((i * 31) % 13) as f64).collect();
+        let sum: f64 = probabilities.iter().sum();
+
+        for &n in &[0, 1, 50, 1000, 12345] {
+            let counts = spread(n, &probabilities);
+            assert_eq!(counts.iter().sum::<usize>(), n);
+
+            for (&count, &p) in counts.iter().zip(&probabilities) {
+                let expected = n as f64 * p / sum;
+                assert!(count as f64 >= expected.floor() && count as f64 <= expected.ceil());
+            }
+        }
+    }
+
+    #[test]
+    fn test_spread_ties_by_index() {
+        let probabilities: Vec<f64> = vec![1.0; 4];
+
+        assert_eq!(spread(2, &probabilities), vec![1, 1, 0, 0]);
+        assert_eq!(spread(6, &probabilities), vec![2, 2, 1, 1]);
+    }
+
+    #[test]
+    fn test_spread_random_ties() {
+        let probabilities: Vec<f64> = vec![1.0; 4];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        let number_spreads = 10000;
+        let mut counter = vec![0usize; 4];
+
+        for _ in 0..number_spreads {
+            let counts = random_choice.spread(1, &probabilities, true);
+            assert_eq!(counts.iter().sum::<usize>(), 1);
+
+            for (c, count) in counter.iter_mut().zip(counts) {
+                *c += count;
+            }
+        }
+
+        for &count in &counter {
+            assert!((count as f64 - 2500.0).abs() < 2500.0 * 0.05);
+        }
+
+        assert_eq!(random_choice.spread(2, &probabilities, false), vec![1, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_spread_without_positive_probabilities() {
+        assert!(spread(10, &[]).is_empty());
+        assert_eq!(spread(10, &[0.0, -1.0]), vec![0, 0]);
+        assert_eq!(spread(3, &[0.0, 2.0, -1.0]), vec![0, 3, 0]);
+    }
+}
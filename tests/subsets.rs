@@ -0,0 +1,105 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use random_choice::random_choice;
+    use random_choice::RandomChoice;
+    use random_choice::strategies::subsets::SubsetWeighting;
+    use rand::SeedableRng;
+
+    fn subset_frequencies(weighting: SubsetWeighting,
+                          number_draws: usize)
+                          -> BTreeMap<Vec<usize>, usize> {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+        let mut counter = BTreeMap::new();
+
+        for _ in 0..number_draws {
+            let subset = random_choice.random_subset_indices_f64(&weights, 2, weighting);
+            *counter.entry(subset).or_insert(0) += 1;
+        }
+
+        counter
+    }
+
+    fn assert_frequencies(counter: &BTreeMap<Vec<usize>, usize>,
+                          expected: &[(Vec<usize>, f64)],
+                          number_draws: usize) {
+        let total: f64 = expected.iter().map(|&(_, weight)| weight).sum();
+
+        assert_eq!(counter.len(), expected.len());
+
+        for &(ref subset, weight) in expected {
+            let expected = number_draws as f64 * weight / total;
+            assert!((counter[subset] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_random_subset_by_product() {
+        let number_draws = 50000;
+        let counter = subset_frequencies(SubsetWeighting::Product, number_draws);
+
+        assert_frequencies(&counter,
+                           &[(vec![0, 1], 2.0),
+                             (vec![0, 2], 3.0),
+                             (vec![0, 3], 4.0),
+                             (vec![1, 2], 6.0),
+                             (vec![1, 3], 8.0),
+                             (vec![2, 3], 12.0)],
+                           number_draws);
+    }
+
+    #[test]
+    fn test_random_subset_by_sum() {
+        let number_draws = 50000;
+        let counter = subset_frequencies(SubsetWeighting::Sum, number_draws);
+
+        assert_frequencies(&counter,
+                           &[(vec![0, 1], 3.0),
+                             (vec![0, 2], 4.0),
+                             (vec![0, 3], 5.0),
+                             (vec![1, 2], 5.0),
+                             (vec![1, 3], 6.0),
+                             (vec![2, 3], 7.0)],
+                           number_draws);
+    }
+
+    #[test]
+    fn test_random_subset_large_product_does_not_overflow() {
+        let capacity: usize = 2000;
+        let weights: Vec<f64> = (0..capacity).map(|i| 1000.0 + i as f64).collect();
+
+        for &weighting in &[SubsetWeighting::Product, SubsetWeighting::Sum] {
+            let subset = random_choice().random_subset_indices_f64(&weights, 1000, weighting);
+
+            assert_eq!(subset.len(), 1000);
+            assert!(subset.windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test]
+    fn test_random_subset_infeasible() {
+        let weights: Vec<f64> = vec![1.0, 0.0, 0.0];
+        let samples = vec!["a", "b", "c"];
+        let mut random_choice = random_choice();
+
+        assert!(random_choice.random_subset_indices_f64(&weights, 2, SubsetWeighting::Product)
+            .is_empty());
+        assert_eq!(random_choice.random_subset_f64(&samples, &weights, 1, SubsetWeighting::Product),
+                   vec![&"a"]);
+        assert_eq!(random_choice.random_subset_f64(&samples, &weights, 2, SubsetWeighting::Sum)
+                       .len(),
+                   2);
+
+        assert!(random_choice.random_subset_indices_f64(&weights, 4, SubsetWeighting::Sum)
+            .is_empty());
+        assert!(random_choice.random_subset_indices_f64(&weights, 0, SubsetWeighting::Sum)
+            .is_empty());
+        assert!(random_choice.random_subset_indices_f64(&[0.0, 0.0], 1, SubsetWeighting::Sum)
+            .is_empty());
+    }
+}
@@ -0,0 +1,95 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use random_choice::strategies::temporal::TemporalSampler;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_temporal_sampler_follows_schedule() {
+        let mut sampler = TemporalSampler::new(3, |t, i| if i as u64 == t % 3 { 1.0 } else { 0.0 });
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        for t in 0..30 {
+            assert_eq!(sampler.sample(&mut rng, t), Some((t % 3) as usize));
+            assert_eq!(sampler.sample_n(&mut rng, t, 5), vec![(t % 3) as usize; 5]);
+        }
+    }
+
+    #[test]
+    fn test_temporal_sampler_frequencies() {
+        let mut sampler = TemporalSampler::new(4, |t, i| (i as u64 + 1 + t) as f64);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let number_choices = 40000;
+        let mut counter = [0usize; 4];
+
+        for _ in 0..number_choices {
+            counter[sampler.sample(&mut rng, 6).unwrap()] += 1;
+        }
+
+        // weights 7, 8, 9, 10 at t = 6
+        for (i, &count) in counter.iter().enumerate() {
+            let expected = number_choices as f64 * (7 + i) as f64 / 34.0;
+            assert!((count as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_temporal_sampler_caches_tables() {
+        let evaluations = Cell::new(0);
+        let mut sampler = TemporalSampler::new(5, |t, i| {
+                evaluations.set(evaluations.get() + 1);
+                (t + i as u64) as f64
+            })
+            .with_cache_size(2);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        sampler.sample_n(&mut rng, 1, 10);
+        sampler.sample_n(&mut rng, 2, 10);
+        sampler.sample_n(&mut rng, 1, 10);
+        assert_eq!(sampler.rebuilds(), 2);
+        assert_eq!(evaluations.get(), 10);
+
+        // t = 1 was prepared first and is evicted by t = 3
+        sampler.sample_n(&mut rng, 3, 10);
+        sampler.sample_n(&mut rng, 2, 10);
+        sampler.sample_n(&mut rng, 1, 10);
+        assert_eq!(sampler.rebuilds(), 4);
+        assert_eq!(evaluations.get(), 20);
+    }
+
+    #[test]
+    fn test_temporal_sampler_invalidates_lazily() {
+        let scale = Cell::new(1.0);
+        let mut sampler = TemporalSampler::new(2, |_, i| if i == 0 { scale.get() } else { 0.0 });
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert_eq!(sampler.sample(&mut rng, 0), Some(0));
+        assert_eq!(sampler.sample(&mut rng, 1), Some(0));
+
+        scale.set(0.0);
+        sampler.invalidate();
+        assert_eq!(sampler.rebuilds(), 2);
+
+        // all weights are zero now, and only the drawn time step is prepared again
+        assert_eq!(sampler.table(0).total_weight(), 0.0);
+        assert_eq!(sampler.rebuilds(), 3);
+
+        sampler.invalidate_at(0);
+        sampler.table(0);
+        assert_eq!(sampler.rebuilds(), 4);
+    }
+
+    #[test]
+    fn test_temporal_sampler_zero_elements() {
+        let mut sampler = TemporalSampler::new(0, |_, _| 1.0);
+        let mut rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        assert!(sampler.is_empty());
+        assert_eq!(sampler.sample(&mut rng, 0), None);
+        assert!(sampler.sample_n(&mut rng, 0, 10).is_empty());
+    }
+}
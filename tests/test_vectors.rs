@@ -0,0 +1,78 @@
+extern crate random_choice;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::prepared::Placement;
+    use random_choice::strategies::subsets::SubsetWeighting;
+    use random_choice::core::test_vectors::{self, Algorithm, TEST_VECTORS};
+
+    #[test]
+    fn test_vectors_of_current_epoch() {
+        for vector in TEST_VECTORS {
+            assert_eq!(vector.draw(), vector.expected, "{:?}", vector);
+        }
+
+        assert_eq!(test_vectors::verify(), Ok(()));
+    }
+
+    #[test]
+    fn test_vectors_cover_all_algorithms() {
+        for algorithm in &[Algorithm::RandomChoiceF64,
+                           Algorithm::RandomChoiceF32,
+                           Algorithm::IndicesF64,
+                           Algorithm::IndicesWithoutReplacementF64,
+                           Algorithm::AliasTable,
+                           Algorithm::PreparedChoice(Placement::Linear),
+                           Algorithm::PreparedChoice(Placement::Gallop),
+                           Algorithm::PreparedChoice(Placement::BinarySearch),
+                           Algorithm::PreparedChoice(Placement::Auto),
+                           Algorithm::ChunkedPrefixSums,
+                           Algorithm::FenwickSampler,
+                           Algorithm::CumulativeWeights,
+                           Algorithm::Approximate(8),
+                           Algorithm::WeightedReservoir,
+                           Algorithm::VarOptReservoir,
+                           Algorithm::PrioritySketch,
+                           Algorithm::ConditionalPoisson,
+                           Algorithm::Subset(SubsetWeighting::Product),
+                           Algorithm::Subset(SubsetWeighting::Sum),
+                           Algorithm::AliasTable32,
+                           Algorithm::SamplePerGroup,
+                           Algorithm::TiesByIndex,
+                           Algorithm::TiesRandom,
+                           Algorithm::BetaBandit,
+                           Algorithm::Exp3,
+                           Algorithm::Categorical,
+                           Algorithm::TemporalSampler,
+                           Algorithm::MarkovSampler,
+                           Algorithm::CapacitySampler,
+                           Algorithm::WeightedKfold,
+                           Algorithm::ResizePopulation,
+                           Algorithm::Capped,
+                           Algorithm::SampleQuotas,
+                           Algorithm::WeightedRandomWalk] {
+            assert!(TEST_VECTORS.iter().any(|vector| vector.algorithm == *algorithm));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "wire")]
+    fn test_draw_plan_vectors() {
+        use random_choice::core::test_vectors::DRAW_PLAN_TEST_VECTORS;
+
+        assert!(!DRAW_PLAN_TEST_VECTORS.is_empty());
+
+        for vector in DRAW_PLAN_TEST_VECTORS {
+            assert_eq!(vector.algorithm, Algorithm::DrawPlan);
+            assert_eq!(vector.draw(), vector.expected, "{:?}", vector);
+        }
+    }
+
+    #[test]
+    fn test_vectors_detect_changes() {
+        let mut vector = TEST_VECTORS[0];
+        vector.weights = &[2.0, 1.1, 9.7, 7.8, 5.6];
+
+        assert!(!vector.check());
+    }
+}
@@ -0,0 +1,114 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::RandomChoice;
+    use random_choice::strategies::ties::TieBreaking;
+    use rand::SeedableRng;
+
+    fn choose(weights: &[f64], n: usize, tie_breaking: TieBreaking) -> Vec<usize> {
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        RandomChoice::new(rng).random_choice_indices_with_ties_f64(weights, n, tie_breaking)
+    }
+
+    #[test]
+    fn test_ties_by_index_order() {
+        let weights: Vec<f64> = vec![1.0, 3.0, 1.0, 3.0, 2.0];
+
+        // the wheel is 1, 3, 4, 0, 2 and every spoke hits one weight unit
+        assert_eq!(choose(&weights, 10, TieBreaking::Index),
+                   vec![1, 1, 1, 3, 3, 3, 4, 4, 0, 2]);
+    }
+
+    #[test]
+    fn test_ties_by_secondary_key_are_independent_of_the_input_order() {
+        let ids: Vec<u32> = (0..50).map(|i| i * 7 % 50).collect();
+        let weights: Vec<f64> = (0..50).map(|i| (ids[i] % 3) as f64 + 1.0).collect();
+
+        // the same samples in reversed order
+        let reversed_ids: Vec<u32> = ids.iter().rev().cloned().collect();
+        let reversed_weights: Vec<f64> = weights.iter().rev().cloned().collect();
+
+        let by_id = |a: usize, b: usize| ids[a].cmp(&ids[b]);
+        let by_reversed_id = |a: usize, b: usize| reversed_ids[a].cmp(&reversed_ids[b]);
+
+        let chosen: Vec<u32> = choose(&weights, 20, TieBreaking::SecondaryKey(&by_id))
+            .into_iter()
+            .map(|i| ids[i])
+            .collect();
+        let chosen_reversed: Vec<u32> =
+            choose(&reversed_weights, 20, TieBreaking::SecondaryKey(&by_reversed_id))
+                .into_iter()
+                .map(|i| reversed_ids[i])
+                .collect();
+
+        assert_eq!(chosen, chosen_reversed);
+    }
+
+    #[test]
+    fn test_random_ties_frequencies() {
+        let weights: Vec<f64> = vec![1.0, 1.0, 1.0, 1.0, 2.0];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        let number_draws = 30000;
+        let mut counter = vec![0usize; weights.len()];
+
+        for _ in 0..number_draws {
+            let choices =
+                random_choice.random_choice_indices_with_ties_f64(&weights, 1, TieBreaking::Random);
+
+            for i in choices {
+                counter[i] += 1;
+            }
+        }
+
+        for i in 0..weights.len() {
+            let expected = number_draws as f64 * weights[i] / 6.0;
+            assert!((counter[i] as f64 - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_ties_samples_and_empty() {
+        let samples = vec!["a", "b", "c"];
+        let weights: Vec<f64> = vec![1.0, 1.0, 1.0];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let mut random_choice = RandomChoice::new(rng);
+
+        let choices =
+            random_choice.random_choice_with_ties_f64(&samples, &weights, 3, TieBreaking::Index);
+
+        assert_eq!(choices, vec![&"a", &"b", &"c"]);
+        assert!(choose(&[], 3, TieBreaking::Index).is_empty());
+        assert!(choose(&weights, 0, TieBreaking::Random).is_empty());
+    }
+
+    /// Spins as close to a full turn as possible.
+    struct MaxRng;
+
+    impl super::rand::Rng for MaxRng {
+        fn next_u32(&mut self) -> u32 {
+            u32::MAX
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            u64::MAX
+        }
+    }
+
+    #[test]
+    fn test_ties_sum_in_wheel_order() {
+        // in index order, the tiny weights add up before 1.0 and increase the sum,
+        // in wheel order, each of them is lost in the rounding of 1.0 + 1e-16
+        let mut weights: Vec<f64> = vec![1e-16; 10];
+        weights.push(1.0);
+
+        let choices = RandomChoice::new(MaxRng)
+            .random_choice_indices_with_ties_f64(&weights, 1, TieBreaking::Index);
+
+        assert_eq!(choices.len(), 1);
+        assert!(choices[0] < weights.len());
+    }
+}
@@ -0,0 +1,67 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::random_choice;
+    use random_choice::RandomChoice;
+    use rand::SeedableRng;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_sample_until_coverage() {
+        let categories = vec!["a", "b", "c", "d", "e"];
+        let weights: Vec<f64> = vec![10.0, 5.0, 2.0, 1.0, 0.5];
+
+        let covers_all = |draws: &[&&str]| draws.iter().collect::<HashSet<_>>().len() == 5;
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let (draws, covered) =
+            RandomChoice::new(rng).sample_until(&categories, &weights, covers_all, 100000);
+
+        assert!(covered);
+        assert_eq!(draws.iter().collect::<HashSet<_>>().len(), 5);
+        // the condition is first met by the last draw
+        assert!(draws[..draws.len() - 1].iter().collect::<HashSet<_>>().len() < 5);
+    }
+
+    #[test]
+    fn test_sample_until_max_draws() {
+        let weights: Vec<f64> = vec![1.0, 0.0];
+
+        let (draws, satisfied) =
+            random_choice().sample_indices_until(&weights, |draws| draws.contains(&1), 50);
+
+        assert!(!satisfied);
+        assert_eq!(draws, vec![0; 50]);
+    }
+
+    #[test]
+    fn test_sample_until_frequencies() {
+        let weights: Vec<f64> = vec![1.0, 2.0, 3.0, 4.0];
+        let number_choices = 40000;
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let (draws, satisfied) = RandomChoice::new(rng)
+            .sample_indices_until(&weights, |draws| draws.len() == number_choices, usize::MAX);
+
+        assert!(satisfied);
+
+        for (i, &weight) in weights.iter().enumerate() {
+            let count = draws.iter().filter(|&&d| d == i).count() as f64;
+            let expected = number_choices as f64 * weight / 10.0;
+            assert!((count - expected).abs() < expected * 0.05);
+        }
+    }
+
+    #[test]
+    fn test_sample_until_no_draws() {
+        let weights: Vec<f64> = vec![1.0, 2.0];
+
+        assert_eq!(random_choice().sample_indices_until(&weights, |_| true, 10), (vec![], true));
+        assert_eq!(random_choice().sample_indices_until(&weights, |_| false, 0), (vec![], false));
+        assert_eq!(random_choice().sample_indices_until(&[], |_| false, 10), (vec![], false));
+        assert_eq!(random_choice().sample_indices_until(&[0.0, 0.0], |_| false, 10),
+                   (vec![], false));
+    }
+}
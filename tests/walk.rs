@@ -0,0 +1,79 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+mod tests {
+    use random_choice::strategies::walk::WeightedRandomWalk;
+    use rand::SeedableRng;
+
+    fn graph() -> Vec<Vec<(usize, f64)>> {
+        vec![vec![(1, 1.0), (2, 3.0)],
+             vec![(0, 1.0)],
+             vec![(0, 1.0)],
+             vec![]]
+    }
+
+    #[test]
+    fn test_walk_follows_edge_weights() {
+        let graph = graph();
+        let neighbors = |node: usize| &graph[node][..];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        let nodes: Vec<usize> = WeightedRandomWalk::new(&neighbors, 0, rng).take(20001).collect();
+
+        assert_eq!(nodes[0], 0);
+
+        for pair in nodes.windows(2) {
+            assert!(graph[pair[0]].iter().any(|&(to, _)| to == pair[1]), "{:?}", pair);
+        }
+
+        // every second node is 0, the others are 1 or 2 with 1 : 3
+        let ones = nodes.iter().filter(|&&n| n == 1).count();
+        let twos = nodes.iter().filter(|&&n| n == 2).count();
+
+        assert_eq!(ones + twos, 10000);
+        assert!((twos as f64 / 10000.0 - 0.75).abs() < 0.02);
+    }
+
+    #[test]
+    fn test_walk_restarts() {
+        let graph: Vec<Vec<(usize, f64)>> = (0..100).map(|i| vec![((i + 1) % 100, 1.0)]).collect();
+        let neighbors = |node: usize| &graph[node][..];
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let always = WeightedRandomWalk::new(&neighbors, 7, rng).with_restart(1.0);
+        assert!(always.take(100).all(|node| node == 7));
+
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+        let walk = WeightedRandomWalk::new(&neighbors, 0, rng).with_restart(0.2);
+        let nodes: Vec<usize> = walk.take(50000).collect();
+
+        // the walk is geometric with a mean of 4 steps before a restart
+        let restarts = nodes.windows(2).filter(|pair| pair[1] == 0 && pair[0] != 99).count();
+        assert!((restarts as f64 / 50000.0 - 0.2).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_walk_restarts_in_dead_ends() {
+        let graph = [vec![(1, 1.0)], vec![(2, 0.0)], vec![]];
+        let neighbors = |node: usize| &graph[node][..];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        let mut walk = WeightedRandomWalk::new(&neighbors, 0, rng);
+        assert_eq!(walk.current(), 0);
+
+        let nodes: Vec<usize> = walk.by_ref().take(6).collect();
+        assert_eq!(nodes, vec![0, 1, 0, 1, 0, 1]);
+        assert_eq!(walk.current(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_walk_rejects_invalid_restart_probability() {
+        let graph = graph();
+        let neighbors = |node: usize| &graph[node][..];
+        let rng = super::rand::StdRng::from_seed(&[5000, 44, 55, 199]);
+
+        WeightedRandomWalk::new(&neighbors, 0, rng).with_restart(-0.1);
+    }
+}
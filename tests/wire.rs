@@ -0,0 +1,93 @@
+extern crate random_choice;
+extern crate rand;
+
+#[cfg(test)]
+#[cfg(feature = "wire")]
+mod tests {
+    use random_choice::core::counter::CounterRng;
+    use random_choice::prepared::PreparedChoice;
+    use random_choice::integrations::wire::{DrawPlan, WireError, FORMAT_VERSION};
+
+    fn plan(n: u64) -> DrawPlan {
+        let weights: Vec<f64> = vec![5.6, 7.8, 0.0, 9.7, 1.1, 2.0];
+        DrawPlan::new(PreparedChoice::new(&weights), 5000, n)
+    }
+
+    #[test]
+    fn test_plan_round_trip() {
+        let plan = plan(1000);
+        let bytes = plan.to_bytes().unwrap();
+        let decoded = DrawPlan::from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded, plan);
+        assert_eq!(decoded.seed(), 5000);
+        assert_eq!(decoded.len(), 1000);
+        // version, seed and n as varints, then 6 prefix sums of 8 bytes each
+        assert!(bytes.len() < 70);
+
+        // updated weights are encoded as their prefix sums
+        let mut prepared = PreparedChoice::new(&[1.0, 2.0]);
+        prepared.update_many(&[(0, 3.0)]);
+        let updated = DrawPlan::new(prepared, 1, 10);
+        let decoded = DrawPlan::from_bytes(&updated.to_bytes().unwrap()).unwrap();
+
+        assert_eq!(*decoded.prepared().prefix_sums(), [3.0, 5.0]);
+    }
+
+    #[test]
+    fn test_plan_rejects_other_versions() {
+        let mut bytes = plan(1000).to_bytes().unwrap();
+        bytes[0] = FORMAT_VERSION as u8 + 1;
+
+        assert_eq!(DrawPlan::from_bytes(&bytes),
+                   Err(WireError::UnsupportedVersion(FORMAT_VERSION + 1)));
+
+        let bytes = plan(1000).to_bytes().unwrap();
+
+        match DrawPlan::from_bytes(&bytes[..3]) {
+            Err(WireError::Encoding(_)) => {}
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_portions_cover_the_whole_draw() {
+        let plan = plan(1003);
+        let whole = plan.sample_range(0..plan.len());
+
+        for &workers in &[1, 2, 7, 1003, 2000] {
+            let portions: Vec<usize> = (0..workers)
+                .flat_map(|worker| plan.sample_portion(worker, workers))
+                .collect();
+
+            assert_eq!(portions, whole);
+        }
+
+        assert_eq!(whole.len(), 1003);
+        assert!(!whole.contains(&2));
+    }
+
+    #[test]
+    fn test_plan_matches_prepared_choice() {
+        let plan = plan(1000);
+        let mut rng = CounterRng::<random_choice::core::entropy::Philox4x32>::new(5000, 0);
+        let expected = plan.prepared().sample_n(&mut rng, 1000);
+        let chosen = plan.sample_range(0..1000);
+
+        for i in 0..6 {
+            let count = |indices: &[usize]| indices.iter().filter(|&&c| c == i).count() as i64;
+            assert!((count(&chosen) - count(&expected)).abs() <= 1);
+        }
+    }
+
+    #[test]
+    fn test_plan_degenerate() {
+        let empty = DrawPlan::new(PreparedChoice::new(&[]), 1, 10);
+        let zero = DrawPlan::new(PreparedChoice::new(&[0.0, 0.0]), 1, 10);
+
+        assert!(empty.sample_portion(0, 1).is_empty());
+        assert!(zero.sample_portion(0, 1).is_empty());
+        assert!(plan(0).is_empty());
+        assert_eq!(plan(10).sample_range(8..20).len(), 2);
+    }
+}